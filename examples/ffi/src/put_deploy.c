@@ -40,7 +40,7 @@ int main(int argc, char **argv) {
     unsigned char response_buffer[RESPONSE_BUFFER_LEN] = {0};
     casper_error_t success = casper_put_deploy(
         RPC_ID, NODE_ADDRESS, VERBOSE, &deploy_params, &session_params,
-        &payment_params, response_buffer, RESPONSE_BUFFER_LEN);
+        &payment_params, false, response_buffer, RESPONSE_BUFFER_LEN);
     if (success == CASPER_SUCCESS) {
         printf("Got successful response\n%s\n", response_buffer);
     } else {
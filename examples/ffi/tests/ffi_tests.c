@@ -40,7 +40,7 @@ void test_should_get_last_error_after_bad_request(void) {
 
     casper_error_t success =
         casper_put_deploy("1", "", false, &deploy_params, &session_params,
-                          &payment_params, response_buffer, 1024);
+                          &payment_params, false, response_buffer, 1024);
 
     TEST_ASSERT_NOT_EQUAL_INT(CASPER_SUCCESS, success);
 
@@ -0,0 +1,388 @@
+//! Tokenizer of simple session and payment args, each of the form `NAME:TYPE=VALUE`.
+//!
+//! A value is one of:
+//!
+//! * single-quoted, e.g. `'a value'`, holding every character up to the last `'` in the string
+//!   verbatim, as simple args have always been read.  Quotes and backslashes inside the value need
+//!   no escaping, so `'it's'` is `it's` and `'C:\dir\'` is `C:\dir\`.
+//! * double-quoted, e.g. `"it's \"quoted\""`, with the escape sequences `\"`, `\'`, `\\`, `\n`,
+//!   `\r` and `\t`.
+//! * `null`, unquoted, for the `None` value of an optional type.
+//! * `@` followed by the path of a file holding the bytes of a `bytes` or `byte_array_N` value,
+//!   running to the end of the string.
+//!
+//! Several args may be given in one string, separated by commas, but only after a double-quoted
+//! or `null` value, e.g. `a:u8="1", b:opt_u8=null, c:string='x'`.  As a single-quoted value or a
+//! file reference runs to the end of the string, it can only be the last.
+
+use casper_types::CLType;
+
+use crate::{
+    cl_type,
+    error::{Error, Result},
+    help,
+};
+
+/// The form of a simple arg, as shown in error messages.
+const FORMAT: &str = r#"NAME:TYPE='VALUE', NAME:TYPE="VALUE" or NAME:TYPE=null"#;
+const NULL: &str = "null";
+
+/// A simple arg split into its constituent parts.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct SimpleArg {
+    /// The arg's name.
+    pub(crate) name: String,
+    /// The arg's type.
+    pub(crate) cl_type: CLType,
+    /// The arg's value as taken by `cl_type::parts_to_cl_value`, i.e. surrounded by single
+    /// quotes, `null` or a file reference.
+    pub(crate) value: String,
+    /// The 1-based position of the first character of the value.
+    pub(crate) value_position: usize,
+}
+
+/// Splits `input` into the one or more simple args it holds.
+pub(crate) fn tokenize(input: &str) -> Result<Vec<SimpleArg>> {
+    let tokenizer = Tokenizer {
+        input,
+        chars: input.chars().collect(),
+    };
+    let mut args = vec![];
+    let mut start = 0;
+    loop {
+        let (arg, maybe_next_start) = tokenizer.arg(start)?;
+        args.push(arg);
+        match maybe_next_start {
+            Some(next_start) => start = next_start,
+            None => return Ok(args),
+        }
+    }
+}
+
+struct Tokenizer<'a> {
+    input: &'a str,
+    chars: Vec<char>,
+}
+
+impl<'a> Tokenizer<'a> {
+    /// Returns an error at the character with the 0-based `index`.
+    fn error(&self, index: usize, error: String) -> Error {
+        Error::InvalidSimpleArg {
+            arg: self.input.to_string(),
+            position: index + 1,
+            error,
+        }
+    }
+
+    /// Returns the index of the first of `targets` at or after `start`, if any.
+    fn find(&self, start: usize, targets: &[char]) -> Option<usize> {
+        (start..self.chars.len()).find(|index| targets.contains(&self.chars[*index]))
+    }
+
+    fn collect(&self, start: usize, end: usize) -> String {
+        self.chars[start..end].iter().collect()
+    }
+
+    fn skip_whitespace(&self, mut index: usize) -> usize {
+        while self
+            .chars
+            .get(index)
+            .is_some_and(|char| char.is_whitespace())
+        {
+            index += 1;
+        }
+        index
+    }
+
+    /// Tokenizes the arg starting at `start`, returning it and the start of the next arg if any.
+    fn arg(&self, start: usize) -> Result<(SimpleArg, Option<usize>)> {
+        let name_end = match self.find(start, &[':', '=']) {
+            Some(index) if self.chars[index] == ':' => index,
+            Some(index) => {
+                return Err(self.error(
+                    index,
+                    format!("expected ':' after the arg's name, as in {}", FORMAT),
+                ))
+            }
+            None => {
+                return Err(self.error(
+                    self.chars.len(),
+                    format!("expected ':' after the arg's name, as in {}", FORMAT),
+                ))
+            }
+        };
+        let name = self.collect(start, name_end).trim().to_string();
+        if name.is_empty() {
+            return Err(self.error(start, "the arg has no name".to_string()));
+        }
+
+        let type_start = name_end + 1;
+        let type_end = self.find(type_start, &['=']).ok_or_else(|| {
+            self.error(
+                self.chars.len(),
+                format!("expected '=' after the arg's type, as in {}", FORMAT),
+            )
+        })?;
+        let type_name = self.collect(type_start, type_end);
+        let cl_type = cl_type::parse(&type_name).map_err(|_| {
+            self.error(
+                type_start,
+                format!(
+                    "unknown type '{}', expected one of {}",
+                    type_name.trim(),
+                    help::supported_cl_type_list()
+                ),
+            )
+        })?;
+
+        let value_start = type_end + 1;
+        let (value, value_end, can_be_followed) = match self.chars.get(value_start) {
+            Some('\'') => {
+                let (value, value_end) = self.single_quoted(value_start)?;
+                (value, value_end, false)
+            }
+            Some('"') => {
+                let (value, value_end) = self.double_quoted(value_start)?;
+                (value, value_end, true)
+            }
+            Some(&cl_type::FILE_REFERENCE_PREFIX) => {
+                let (value, value_end) = self.file_reference(value_start);
+                (value, value_end, false)
+            }
+            Some(_)
+                if self
+                    .collect(value_start, self.chars.len())
+                    .starts_with(NULL) =>
+            {
+                (NULL.to_string(), value_start + NULL.len(), true)
+            }
+            _ => {
+                return Err(self.error(
+                    value_start,
+                    format!(
+                        "the value should be surrounded by single or double quotes, or be {}",
+                        NULL
+                    ),
+                ))
+            }
+        };
+
+        let next = self.skip_whitespace(value_end);
+        let maybe_next_start = match self.chars.get(next) {
+            None => None,
+            Some(',') if can_be_followed => Some(self.skip_whitespace(next + 1)),
+            Some(char) if !can_be_followed => {
+                return Err(self.error(
+                    next,
+                    format!(
+                        "unexpected '{}' after the value; a single-quoted value runs to the last \
+                        ' in the string, so can't be followed by another arg",
+                        char
+                    ),
+                ))
+            }
+            Some(char) => {
+                return Err(self.error(
+                    next,
+                    format!(
+                        "unexpected '{}' after the value; args in the same string should be \
+                        separated by ','",
+                        char
+                    ),
+                ))
+            }
+        };
+        let arg = SimpleArg {
+            name,
+            cl_type,
+            value,
+            value_position: value_start + 1,
+        };
+        Ok((arg, maybe_next_start))
+    }
+
+    /// Returns the single-quoted value opening at `start` and the index after its closing quote,
+    /// which is the last quote in the input.
+    fn single_quoted(&self, start: usize) -> Result<(String, usize)> {
+        match (start + 1..self.chars.len()).rfind(|index| self.chars[*index] == '\'') {
+            Some(end) => Ok((self.collect(start, end + 1), end + 1)),
+            None => Err(self.error(
+                start,
+                "the opening ' of the value is never closed".to_string(),
+            )),
+        }
+    }
+
+    /// Returns the double-quoted value opening at `start`, as a single-quoted value, and the
+    /// index after its closing quote.
+    fn double_quoted(&self, start: usize) -> Result<(String, usize)> {
+        let mut value = String::new();
+        let mut index = start + 1;
+        while let Some(&char) = self.chars.get(index) {
+            match char {
+                '"' => return Ok((format!("'{}'", value), index + 1)),
+                '\\' => {
+                    let escaped = match self.chars.get(index + 1) {
+                        Some('"') => '"',
+                        Some('\'') => '\'',
+                        Some('\\') => '\\',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some(other) => {
+                            return Err(self.error(
+                                index,
+                                format!(
+                                    "unknown escape sequence '\\{}'; use '\\\\' for a backslash",
+                                    other
+                                ),
+                            ))
+                        }
+                        None => break,
+                    };
+                    value.push(escaped);
+                    index += 2;
+                }
+                _ => {
+                    value.push(char);
+                    index += 1;
+                }
+            }
+        }
+        Err(self.error(
+            start,
+            "the opening \" of the value is never closed".to_string(),
+        ))
+    }
+
+    /// Returns the file reference starting at `start`, which runs to the end of the input, and
+    /// the index after it.
+    fn file_reference(&self, start: usize) -> (String, usize) {
+        let end = self.chars.len();
+        (self.collect(start, end).trim_end().to_string(), end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::CLValue;
+
+    use super::*;
+
+    fn values(input: &str) -> Vec<(String, String)> {
+        tokenize(input)
+            .unwrap_or_else(|error| panic!("{} should tokenize: {}", input, error))
+            .into_iter()
+            .map(|arg| (arg.name, arg.value))
+            .collect()
+    }
+
+    fn error_position(input: &str) -> usize {
+        match tokenize(input) {
+            Err(Error::InvalidSimpleArg { position, .. }) => position,
+            other => panic!("{} should fail to tokenize, but got {:?}", input, other),
+        }
+    }
+
+    fn pair(name: &str, value: &str) -> (String, String) {
+        (name.to_string(), value.to_string())
+    }
+
+    #[test]
+    fn should_tokenize_existing_syntax() {
+        assert_eq!(values("x:u8='1'"), vec![pair("x", "'1'")]);
+        assert_eq!(values("x:opt_u8=null"), vec![pair("x", "null")]);
+        assert_eq!(values("x:string=''"), vec![pair("x", "''")]);
+        assert_eq!(values("x:string='it's'"), vec![pair("x", "'it's'")]);
+        assert_eq!(
+            values(r#"x:tuple2<u8,string>='(1, \"a, b\")'"#),
+            vec![pair("x", r#"'(1, \"a, b\")'"#)]
+        );
+        assert_eq!(
+            values("x:map<string,u8>='{a: 1, b: 2}'"),
+            vec![pair("x", "'{a: 1, b: 2}'")]
+        );
+        assert_eq!(
+            values("x:bytes=@path/to/file"),
+            vec![pair("x", "@path/to/file")]
+        );
+        assert_eq!(
+            tokenize("x:map<string,u8>='{}'").unwrap()[0].cl_type,
+            CLType::Map {
+                key: Box::new(CLType::String),
+                value: Box::new(CLType::U8)
+            }
+        );
+    }
+
+    #[test]
+    fn should_tokenize_quotes_and_escapes() {
+        assert_eq!(values(r#"x:string='it\'s'"#), vec![pair("x", r#"'it\'s'"#)]);
+        assert_eq!(values(r#"x:string='C:\'"#), vec![pair("x", r#"'C:\'"#)]);
+        assert_eq!(
+            values(r#"x:string="{\"a\": \"it's\"}""#),
+            vec![pair("x", r#"'{"a": "it's"}'"#)]
+        );
+        assert_eq!(values(r#"x:string="a\\b\n""#), vec![pair("x", "'a\\b\n'")]);
+        assert_eq!(values("x:string=''quoted''"), vec![pair("x", "''quoted''")]);
+    }
+
+    #[test]
+    fn should_tokenize_several_args() {
+        assert_eq!(
+            values(r#"a:u8="1", b:string="x, y", c:opt_u8=null,d:string='it', e'"#),
+            vec![
+                pair("a", "'1'"),
+                pair("b", "'x, y'"),
+                pair("c", "null"),
+                pair("d", "'it', e'"),
+            ]
+        );
+        assert_eq!(
+            values(r#"a:u8="1", f:bytes=@f, g:u8="2""#),
+            vec![pair("a", "'1'"), pair("f", r#"@f, g:u8="2""#)]
+        );
+        let args = tokenize(r#"a:u8="1", b:u8='2'"#).unwrap();
+        assert_eq!(args[1].value_position, 16);
+    }
+
+    #[test]
+    fn should_read_single_quoted_values_as_before() {
+        // Before double quotes were supported, everything after the type was the value.
+        let baseline_value =
+            |input: &str| input.splitn(3, &[':', '='][..]).nth(2).unwrap().to_string();
+        for input in [
+            "x:string='a', b:c'",
+            r#"x:string='a\'b'"#,
+            r#"x:string='C:\dir\', y:u8='1'"#,
+            "x:string='it's'",
+            "x:string=''quoted''",
+        ] {
+            assert_eq!(values(input), vec![pair("x", &baseline_value(input))]);
+        }
+
+        let arg = &tokenize(r#"x:string='a\'b'"#).unwrap()[0];
+        assert_eq!(
+            cl_type::parts_to_cl_value(arg.cl_type.clone(), &arg.value).unwrap(),
+            CLValue::from_t(r#"a\'b"#.to_string()).unwrap()
+        );
+    }
+
+    #[test]
+    fn should_point_at_invalid_character() {
+        assert_eq!(error_position("x"), 2);
+        assert_eq!(error_position("x=u8'1'"), 2);
+        assert_eq!(error_position(":u8='1'"), 1);
+        assert_eq!(error_position("x:u8"), 5);
+        assert_eq!(error_position("x:wrong='1'"), 3);
+        assert_eq!(error_position("x:u8=1"), 6);
+        assert_eq!(error_position("x:string='a"), 10);
+        assert_eq!(error_position(r#"x:string="a"#), 10);
+        assert_eq!(error_position(r#"x:string="a\qb""#), 12);
+        assert_eq!(error_position(r#"x:string="a"b"#), 13);
+        assert_eq!(error_position("x:opt_u8=nullify"), 14);
+        assert_eq!(error_position("x:string='a'b"), 13);
+        assert_eq!(error_position(r#"a:u8="1", 2"#), 12);
+        assert_eq!(error_position("a:u8='1', b:opt_u8=null"), 9);
+    }
+}
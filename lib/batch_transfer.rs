@@ -0,0 +1,547 @@
+//! Support for creating, sending and tracking many native transfers described by a CSV file.
+
+use std::{
+    collections::BTreeMap,
+    fs::{self, File, OpenOptions},
+    io,
+    path::Path,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use casper_execution_engine::core::engine_state::ExecutableDeployItem;
+use casper_node::types::{Deploy, TimeDiff};
+
+use crate::{
+    deploy::{DeployExt, DeployFormat},
+    error::{Error, Result},
+    rpc::RpcCall,
+    DeployStrParams, Signer,
+};
+
+/// The interval between successive checks of whether a sent transfer has been executed.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A single row of a transfers CSV file.
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct TransferRow {
+    target: String,
+    amount: String,
+    transfer_id: String,
+}
+
+/// The status of a single transfer, as recorded in the reconciliation report.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+enum TransferStatus {
+    /// The transfer `Deploy` could not be created, or could not be written in a dry run.
+    Invalid,
+    /// The signed transfer `Deploy` was written to the dry-run directory.
+    Written,
+    /// The transfer `Deploy` is about to be sent.  If this is a transfer's latest row, the run was
+    /// interrupted while sending it, so whether the node accepted it is unknown.
+    Sending,
+    /// The node rejected the transfer `Deploy`, or it couldn't be sent.
+    Rejected,
+    /// The transfer `Deploy` was sent, but no valid response was received, so whether the node
+    /// accepted it is unknown.
+    Unconfirmed,
+    /// The node accepted the transfer `Deploy`, but it was not executed before tracking stopped.
+    Pending,
+    /// The transfer `Deploy` was executed successfully.
+    Succeeded,
+    /// The transfer `Deploy` was executed, but failed.
+    Failed,
+}
+
+/// A single row of the reconciliation report.
+#[derive(Serialize, Deserialize, Debug)]
+struct ReportRow {
+    line: usize,
+    target: String,
+    amount: String,
+    transfer_id: String,
+    status: TransferStatus,
+    deploy_hash: Option<String>,
+    block_hash: Option<String>,
+    cost: Option<String>,
+    error: Option<String>,
+}
+
+impl ReportRow {
+    fn new(line: usize, row: TransferRow, status: TransferStatus) -> Self {
+        ReportRow {
+            line,
+            target: row.target,
+            amount: row.amount,
+            transfer_id: row.transfer_id,
+            status,
+            deploy_hash: None,
+            block_hash: None,
+            cost: None,
+            error: None,
+        }
+    }
+
+    /// Returns whether this row records the given transfer.
+    fn is_for(&self, row: &TransferRow) -> bool {
+        self.target == row.target
+            && self.amount == row.amount
+            && self.transfer_id == row.transfer_id
+    }
+}
+
+/// A summary of a [`batch_transfer()`](crate::batch_transfer) run.
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct BatchTransferSummary {
+    /// The number of transfers written to the dry-run directory.
+    pub written: usize,
+    /// The number of transfers executed successfully.
+    pub succeeded: usize,
+    /// The number of transfers which failed, either while being created, sent or executed.
+    pub failed: usize,
+    /// The number of transfers accepted by the node but not yet known to have been executed.
+    pub pending: usize,
+    /// The number of transfers skipped because a previous run had already written, sent or
+    /// executed them.
+    pub skipped: usize,
+    /// The number of transfers for which no valid response to sending their `Deploy` was
+    /// received, in this run or a previous one, so whether the node accepted them is unknown.
+    /// Their deploy hashes are recorded in the report, so can be checked with `get_deploy`.  They
+    /// are never sent again when resuming.
+    pub unconfirmed: usize,
+}
+
+fn csv_error(path: &str, error: impl ToString) -> Error {
+    Error::FailedToParseCsv {
+        context: path.to_string(),
+        error: error.to_string(),
+    }
+}
+
+/// Reads the rows of the transfers CSV file at `path`, returning each with its line number.
+fn read_transfers(path: &str) -> Result<Vec<(usize, TransferRow)>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_path(path)
+        .map_err(|error| csv_error(path, error))?;
+    reader
+        .deserialize()
+        .enumerate()
+        .map(|(index, maybe_row)| {
+            // Add 2 to the index to account for the header row and 1-based line numbers.
+            let line = index + 2;
+            maybe_row
+                .map(|row| (line, row))
+                .map_err(|error| csv_error(path, format!("line {}: {}", line, error)))
+        })
+        .collect()
+}
+
+/// Returns the latest row for each line of the transfers file recorded in an existing report.
+fn read_report(report_path: &str) -> Result<BTreeMap<usize, ReportRow>> {
+    if !Path::new(report_path).exists() {
+        return Ok(BTreeMap::new());
+    }
+    let mut reader =
+        csv::Reader::from_path(report_path).map_err(|error| csv_error(report_path, error))?;
+    let mut report_rows = BTreeMap::new();
+    for maybe_row in reader.deserialize() {
+        let report_row: ReportRow = maybe_row.map_err(|error| csv_error(report_path, error))?;
+        report_rows.insert(report_row.line, report_row);
+    }
+    Ok(report_rows)
+}
+
+/// Parses the completion timeout, where an empty string means sent transfers are not tracked.
+fn completion_timeout(value: &str) -> Result<Option<Duration>> {
+    if value.is_empty() {
+        return Ok(None);
+    }
+    value
+        .parse::<TimeDiff>()
+        .map(|time_diff| Some(Duration::from_millis(time_diff.millis())))
+        .map_err(|error| Error::FailedToParseTimeDiff {
+            context: "completion_timeout",
+            error,
+        })
+}
+
+/// Extracts the outcome from a `info_get_deploy` response if the `Deploy` has been executed.
+///
+/// Returns the block hash, cost and, for a failed execution, the error message.
+fn execution_outcome(response: &Value) -> Option<(String, String, Option<String>)> {
+    let execution_result = response.get("execution_results")?.as_array()?.first()?;
+    let block_hash = execution_result.get("block_hash")?.as_str()?.to_string();
+    let result = execution_result.get("result")?;
+    let (outcome, maybe_error) = match (result.get("Success"), result.get("Failure")) {
+        (Some(success), _) => (success, None),
+        (None, Some(failure)) => (
+            failure,
+            failure
+                .get("error_message")
+                .and_then(Value::as_str)
+                .map(ToString::to_string),
+        ),
+        (None, None) => return None,
+    };
+    let cost = outcome.get("cost")?.as_str()?.to_string();
+    Some((block_hash, cost, maybe_error))
+}
+
+/// Polls the node until the `Deploy` with the given hash has been executed or the timeout elapses,
+/// updating `report_row` with the outcome.
+async fn track(
+    node_address: &str,
+    verbosity_level: u64,
+    deploy_hash: &str,
+    timeout: Duration,
+    report_row: &mut ReportRow,
+) {
+    let deadline = Instant::now() + timeout;
+    loop {
+        // Failed requests are treated like unexecuted deploys, as the node may not yet know of the
+        // deploy or may be temporarily unavailable.
+        let maybe_outcome = RpcCall::new("", node_address, verbosity_level)
+            .get_deploy(deploy_hash)
+            .await
+            .ok()
+            .and_then(|response| response.get_result().and_then(execution_outcome));
+
+        if let Some((block_hash, cost, maybe_error)) = maybe_outcome {
+            report_row.status = if maybe_error.is_none() {
+                TransferStatus::Succeeded
+            } else {
+                TransferStatus::Failed
+            };
+            report_row.block_hash = Some(block_hash);
+            report_row.cost = Some(cost);
+            report_row.error = maybe_error;
+            return;
+        }
+
+        let now = Instant::now();
+        if now >= deadline {
+            return;
+        }
+        tokio::time::sleep(POLL_INTERVAL.min(deadline - now)).await;
+    }
+}
+
+/// Writes the signed `deploy` into `dir` in the given format, naming the file after the deploy
+/// hash.
+///
+/// An existing file of the same name is overwritten, as it can only hold the same `Deploy`.
+fn write_to_dir(dir: &str, format: DeployFormat, deploy: &Deploy) -> Result<()> {
+    let path = Path::new(dir).join(format!(
+        "{}.{}",
+        base16::encode_lower(deploy.id()),
+        format.file_extension()
+    ));
+    let file = File::create(&path).map_err(|error| Error::IoError {
+        context: format!("unable to create deploy file at '{}'", path.display()),
+        error,
+    })?;
+    deploy.write_deploy(file, format)
+}
+
+/// Creates, signs and either sends or writes a transfer `Deploy` for every row of the CSV file at
+/// `transfers_path`, writing a row of the reconciliation report to `report_path` as each completes.
+///
+/// See [`batch_transfer()`](crate::batch_transfer) for details of the parameters.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn batch_transfer(
+    node_address: &str,
+    verbosity_level: u64,
+    transfers_path: &str,
+    report_path: &str,
+    max_concurrency: usize,
+    completion_timeout: &str,
+    dry_run: Option<(&str, DeployFormat)>,
+    resume: bool,
+    force: bool,
+    deploy_params: DeployStrParams<'_>,
+    payment: ExecutableDeployItem,
+) -> Result<BatchTransferSummary> {
+    if max_concurrency == 0 {
+        return Err(Error::InvalidArgument {
+            context: "max_concurrency",
+            error: "must be greater than 0".to_string(),
+        });
+    }
+    let maybe_timeout = self::completion_timeout(completion_timeout)?;
+
+    let rows = read_transfers(transfers_path)?;
+    // The signer is constructed once, so e.g. an encrypted secret key is only decrypted once.
+    let signer: Arc<dyn Signer> = Arc::from(deploy_params.signer()?);
+
+    let previous_rows = if resume {
+        read_report(report_path)?
+    } else {
+        if Path::new(report_path).exists() && !force {
+            return Err(Error::FileAlreadyExists(report_path.into()));
+        }
+        BTreeMap::new()
+    };
+
+    let mut summary = BatchTransferSummary::default();
+    let mut remaining = vec![];
+    for (line, row) in rows {
+        let previous_row = match previous_rows.get(&line) {
+            Some(previous_row) => previous_row,
+            None => {
+                remaining.push((line, row));
+                continue;
+            }
+        };
+        if !previous_row.is_for(&row) {
+            return Err(Error::InvalidArgument {
+                context: "resume",
+                error: format!(
+                    "line {} of '{}' differs from the transfer recorded for it in '{}'",
+                    line, transfers_path, report_path
+                ),
+            });
+        }
+        match previous_row.status {
+            TransferStatus::Written | TransferStatus::Pending | TransferStatus::Succeeded => {
+                summary.skipped += 1
+            }
+            TransferStatus::Sending | TransferStatus::Unconfirmed => summary.unconfirmed += 1,
+            TransferStatus::Invalid | TransferStatus::Rejected | TransferStatus::Failed => {
+                remaining.push((line, row))
+            }
+        }
+    }
+
+    if let Some((dir, _)) = dry_run {
+        fs::create_dir_all(dir).map_err(|error| Error::IoError {
+            context: format!("unable to create directory '{}'", dir),
+            error,
+        })?;
+    }
+
+    // When appending to a non-empty report, its header row has already been written.
+    let has_headers = !resume
+        || fs::metadata(report_path)
+            .map(|metadata| metadata.len() == 0)
+            .unwrap_or(true);
+    let report_file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(resume)
+        .truncate(!resume)
+        .open(report_path)
+        .map_err(|error| Error::IoError {
+            context: format!("unable to open report file at '{}'", report_path),
+            error,
+        })?;
+    let writer = Mutex::new(
+        csv::WriterBuilder::new()
+            .has_headers(has_headers)
+            .from_writer(report_file),
+    );
+    let write_row = |report_row: &ReportRow| -> Result<()> {
+        let mut writer = writer.lock().expect("should lock report file");
+        writer
+            .serialize(report_row)
+            .map_err(io::Error::from)
+            .and_then(|_| writer.flush())
+            .map_err(|error| Error::IoError {
+                context: format!("unable to write report file at '{}'", report_path),
+                error,
+            })
+    };
+
+    let transfers = remaining.into_iter().map(|(line, row)| {
+        transfer(
+            node_address,
+            verbosity_level,
+            line,
+            row,
+            deploy_params.clone(),
+            &signer,
+            payment.clone(),
+            maybe_timeout,
+            dry_run,
+            &write_row,
+        )
+    });
+
+    let mut report_rows = stream::iter(transfers).buffer_unordered(max_concurrency);
+    while let Some(maybe_report_row) = report_rows.next().await {
+        let report_row = maybe_report_row?;
+        match report_row.status {
+            TransferStatus::Written => summary.written += 1,
+            TransferStatus::Succeeded => summary.succeeded += 1,
+            TransferStatus::Sending | TransferStatus::Pending => summary.pending += 1,
+            TransferStatus::Unconfirmed => summary.unconfirmed += 1,
+            TransferStatus::Invalid | TransferStatus::Rejected | TransferStatus::Failed => {
+                summary.failed += 1
+            }
+        }
+        write_row(&report_row)?;
+    }
+
+    Ok(summary)
+}
+
+/// Creates and signs the transfer `Deploy` for `row`, then either writes it to the dry-run
+/// directory or sends and tracks it, first recording its deploy hash via `write_row`.
+#[allow(clippy::too_many_arguments)]
+async fn transfer(
+    node_address: &str,
+    verbosity_level: u64,
+    line: usize,
+    row: TransferRow,
+    deploy_params: DeployStrParams<'_>,
+    signer: &Arc<dyn Signer>,
+    payment: ExecutableDeployItem,
+    maybe_timeout: Option<Duration>,
+    dry_run: Option<(&str, DeployFormat)>,
+    write_row: &impl Fn(&ReportRow) -> Result<()>,
+) -> Result<ReportRow> {
+    let signer = Box::new(Arc::clone(signer));
+    let maybe_deploy = deploy_params.with_signer(signer).and_then(|deploy_params| {
+        Deploy::new_transfer(
+            &row.amount,
+            None,
+            &row.target,
+            &row.transfer_id,
+            deploy_params,
+            payment,
+        )
+    });
+    let deploy = match maybe_deploy {
+        Ok(deploy) => deploy,
+        Err(error) => {
+            let mut report_row = ReportRow::new(line, row, TransferStatus::Invalid);
+            report_row.error = Some(error.to_string());
+            return Ok(report_row);
+        }
+    };
+
+    let deploy_hash = base16::encode_lower(deploy.id());
+    let mut report_row = ReportRow::new(line, row, TransferStatus::Sending);
+    report_row.deploy_hash = Some(deploy_hash.clone());
+
+    if let Some((dir, format)) = dry_run {
+        match write_to_dir(dir, format, &deploy) {
+            Ok(()) => report_row.status = TransferStatus::Written,
+            Err(error) => {
+                report_row.status = TransferStatus::Invalid;
+                report_row.error = Some(error.to_string());
+            }
+        }
+        return Ok(report_row);
+    }
+
+    write_row(&report_row)?;
+    if let Err(error) = RpcCall::new("", node_address, verbosity_level)
+        .put_deploy(deploy)
+        .await
+    {
+        report_row.status = if error.is_definite_rejection() {
+            TransferStatus::Rejected
+        } else {
+            TransferStatus::Unconfirmed
+        };
+        report_row.error = Some(error.to_string());
+        return Ok(report_row);
+    }
+
+    report_row.status = TransferStatus::Pending;
+    if let Some(timeout) = maybe_timeout {
+        track(
+            node_address,
+            verbosity_level,
+            &deploy_hash,
+            timeout,
+            &mut report_row,
+        )
+        .await;
+    }
+    Ok(report_row)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn should_read_transfers() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("transfers.csv");
+        fs::write(
+            &path,
+            "target,amount,transfer_id\n\
+            01522ef6c89038019cb7af05c340623804392dd2bb1f4dab5e4a9c3ab752fc0179, 2500000000, 1\n",
+        )
+        .unwrap();
+        let rows = read_transfers(path.to_str().unwrap()).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].0, 2);
+        assert_eq!(rows[0].1.amount, "2500000000");
+        assert_eq!(rows[0].1.transfer_id, "1");
+    }
+
+    #[test]
+    fn should_fail_to_read_transfers_with_missing_column() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("transfers.csv");
+        fs::write(&path, "target,amount\nabc,1\n").unwrap();
+        assert!(matches!(
+            read_transfers(path.to_str().unwrap()),
+            Err(Error::FailedToParseCsv { error, .. }) if error.contains("line 2")
+        ));
+    }
+
+    #[test]
+    fn should_parse_execution_outcome() {
+        assert!(execution_outcome(&json!({ "execution_results": [] })).is_none());
+
+        let success = json!({
+            "execution_results": [{
+                "block_hash": "aa",
+                "result": { "Success": { "cost": "100000000" } }
+            }]
+        });
+        assert_eq!(
+            execution_outcome(&success),
+            Some(("aa".to_string(), "100000000".to_string(), None))
+        );
+
+        let failure = json!({
+            "execution_results": [{
+                "block_hash": "bb",
+                "result": { "Failure": { "cost": "1", "error_message": "Insufficient funds" } }
+            }]
+        });
+        assert_eq!(
+            execution_outcome(&failure),
+            Some((
+                "bb".to_string(),
+                "1".to_string(),
+                Some("Insufficient funds".to_string())
+            ))
+        );
+    }
+
+    #[test]
+    fn should_parse_completion_timeout() {
+        assert_eq!(completion_timeout("").unwrap(), None);
+        assert_eq!(
+            completion_timeout("2min").unwrap(),
+            Some(Duration::from_secs(120))
+        );
+        assert!(completion_timeout("soon").is_err());
+    }
+}
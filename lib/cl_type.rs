@@ -1,53 +1,111 @@
 //! Supported `CLType` and `CLValue` parsing and validation.
 
-use std::{result::Result as StdResult, str::FromStr};
+use std::{collections::HashSet, fs, path::Path, result::Result as StdResult, str::FromStr};
 
 use casper_types::{
-    account::AccountHash, bytesrepr::ToBytes, AsymmetricType, CLType, CLTyped, CLValue, Key,
-    PublicKey, URef, U128, U256, U512,
+    account::AccountHash,
+    bytesrepr::{
+        self, Bytes, FromBytes, ToBytes, OPTION_NONE_TAG, OPTION_SOME_TAG, RESULT_ERR_TAG,
+        RESULT_OK_TAG, U32_SERIALIZED_LENGTH,
+    },
+    AsymmetricType, CLType, CLTyped, CLValue, Key, PublicKey, URef, U128, U256, U512,
 };
+use serde_json::Value;
 
-use crate::error::{Error, Result};
+use crate::{
+    error::{Error, Result},
+    global_state_key,
+};
+
+/// The prefix of the name of a `ByteArray` type, followed by its length, e.g. "byte_array_32".
+const BYTE_ARRAY_PREFIX: &str = "byte_array_";
+
+/// The prefix of a `bytes` or `byte_array_N` value naming the file holding the bytes, e.g.
+/// "@path/to/file".
+pub(crate) const FILE_REFERENCE_PREFIX: char = '@';
+
+/// The names and parameter counts of the supported compound types, e.g. "map<string,key>".
+const COMPOUND_TYPES: [&str; 7] = [
+    "option<T>",
+    "list<T>",
+    "map<K,V>",
+    "tuple1<T1>",
+    "tuple2<T1,T2>",
+    "tuple3<T1,T2,T3>",
+    "result<T,E>",
+];
 
 /// Parse a `CLType` from `&str`.
+///
+/// As well as the names returned by [`supported_cl_types`], this accepts `byte_array_N` and the
+/// [`COMPOUND_TYPES`], whose parameters can be any of these types, nested to any depth.
 pub(crate) fn parse(strval: &str) -> StdResult<CLType, ()> {
-    let supported_types = supported_cl_types();
-    let cl_type = match strval.to_lowercase() {
-        t if t == supported_types[0].0 => supported_types[0].1.clone(),
-        t if t == supported_types[1].0 => supported_types[1].1.clone(),
-        t if t == supported_types[2].0 => supported_types[2].1.clone(),
-        t if t == supported_types[3].0 => supported_types[3].1.clone(),
-        t if t == supported_types[4].0 => supported_types[4].1.clone(),
-        t if t == supported_types[5].0 => supported_types[5].1.clone(),
-        t if t == supported_types[6].0 => supported_types[6].1.clone(),
-        t if t == supported_types[7].0 => supported_types[7].1.clone(),
-        t if t == supported_types[8].0 => supported_types[8].1.clone(),
-        t if t == supported_types[9].0 => supported_types[9].1.clone(),
-        t if t == supported_types[10].0 => supported_types[10].1.clone(),
-        t if t == supported_types[11].0 => supported_types[11].1.clone(),
-        t if t == supported_types[12].0 => supported_types[12].1.clone(),
-        t if t == supported_types[13].0 => supported_types[13].1.clone(),
-        t if t == supported_types[14].0 => supported_types[14].1.clone(),
-        t if t == supported_types[15].0 => supported_types[15].1.clone(),
-        t if t == supported_types[16].0 => supported_types[16].1.clone(),
-        t if t == supported_types[17].0 => supported_types[17].1.clone(),
-        t if t == supported_types[18].0 => supported_types[18].1.clone(),
-        t if t == supported_types[19].0 => supported_types[19].1.clone(),
-        t if t == supported_types[20].0 => supported_types[20].1.clone(),
-        t if t == supported_types[21].0 => supported_types[21].1.clone(),
-        t if t == supported_types[22].0 => supported_types[22].1.clone(),
-        t if t == supported_types[23].0 => supported_types[23].1.clone(),
-        t if t == supported_types[24].0 => supported_types[24].1.clone(),
-        t if t == supported_types[25].0 => supported_types[25].1.clone(),
-        t if t == supported_types[26].0 => supported_types[26].1.clone(),
-        t if t == supported_types[27].0 => supported_types[27].1.clone(),
-        t if t == supported_types[28].0 => supported_types[28].1.clone(),
-        t if t == supported_types[29].0 => supported_types[29].1.clone(),
+    let lowercase = strval.trim().to_lowercase();
+    if let Some((_, cl_type)) = supported_cl_types()
+        .into_iter()
+        .find(|(name, _)| *name == lowercase)
+    {
+        return Ok(cl_type);
+    }
+    if let Some(length) = lowercase.strip_prefix(BYTE_ARRAY_PREFIX) {
+        return length.parse().map(CLType::ByteArray).map_err(|_| ());
+    }
+
+    let (name, params) = lowercase
+        .strip_suffix('>')
+        .and_then(|type_and_params| type_and_params.split_once('<'))
+        .ok_or(())?;
+    let params = split_top_level(params, ',')
+        .into_iter()
+        .map(|param| parse(param).map(Box::new))
+        .collect::<StdResult<Vec<_>, ()>>()?;
+    let cl_type = match (name.trim(), params.as_slice()) {
+        ("option", [inner]) => CLType::Option(inner.clone()),
+        ("list", [inner]) => CLType::List(inner.clone()),
+        ("map", [key, value]) => CLType::Map {
+            key: key.clone(),
+            value: value.clone(),
+        },
+        ("tuple1", [t1]) => CLType::Tuple1([t1.clone()]),
+        ("tuple2", [t1, t2]) => CLType::Tuple2([t1.clone(), t2.clone()]),
+        ("tuple3", [t1, t2, t3]) => CLType::Tuple3([t1.clone(), t2.clone(), t3.clone()]),
+        ("result", [ok, err]) => CLType::Result {
+            ok: ok.clone(),
+            err: err.clone(),
+        },
         _ => return Err(()),
     };
     Ok(cl_type)
 }
 
+/// Splits `value` at each `separator` which isn't nested inside brackets or a double-quoted string.
+fn split_top_level(value: &str, separator: char) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut depth = 0_i32;
+    let mut in_quotes = false;
+    let mut escaped = false;
+    let mut part_start = 0;
+    for (index, char) in value.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match char {
+            '\\' if in_quotes => escaped = true,
+            '"' => in_quotes = !in_quotes,
+            '<' | '[' | '{' | '(' if !in_quotes => depth += 1,
+            '>' | ']' | '}' | ')' if !in_quotes => depth -= 1,
+            _ if char == separator && depth == 0 && !in_quotes => {
+                parts.push(&value[part_start..index]);
+                part_start = index + char.len_utf8();
+            }
+            _ => (),
+        }
+    }
+    parts.push(&value[part_start..]);
+    parts
+}
+
 pub(crate) fn supported_cl_types() -> Vec<(&'static str, CLType)> {
     vec![
         ("bool", CLType::Bool),
@@ -61,6 +119,7 @@ pub(crate) fn supported_cl_types() -> Vec<(&'static str, CLType)> {
         ("u512", CLType::U512),
         ("unit", CLType::Unit),
         ("string", CLType::String),
+        ("bytes", CLType::List(Box::new(CLType::U8))),
         ("key", CLType::Key),
         ("account_hash", AccountHash::cl_type()),
         ("uref", CLType::URef),
@@ -76,6 +135,10 @@ pub(crate) fn supported_cl_types() -> Vec<(&'static str, CLType)> {
         ("opt_u512", CLType::Option(Box::new(CLType::U512))),
         ("opt_unit", CLType::Option(Box::new(CLType::Unit))),
         ("opt_string", CLType::Option(Box::new(CLType::String))),
+        (
+            "opt_bytes",
+            CLType::Option(Box::new(CLType::List(Box::new(CLType::U8)))),
+        ),
         ("opt_key", CLType::Option(Box::new(CLType::Key))),
         (
             "opt_account_hash",
@@ -93,7 +156,10 @@ pub(crate) fn supported_cl_types() -> Vec<(&'static str, CLType)> {
 pub mod help {
     use std::convert::TryFrom;
 
-    use casper_types::{account::AccountHash, AccessRights, AsymmetricType, Key, PublicKey, URef};
+    use casper_types::{
+        account::AccountHash, AccessRights, AsymmetricType, DeployHash, EraId, Key, PublicKey,
+        TransferAddr, URef,
+    };
 
     /// Returns a list of `CLType`s able to be passed as a string for use as payment code or session
     /// code args.
@@ -106,6 +172,11 @@ pub mod help {
                 msg.push_str(", ")
             }
         }
+        msg.push_str(&format!(
+            ", {}N, {}",
+            super::BYTE_ARRAY_PREFIX,
+            super::COMPOUND_TYPES.join(", ")
+        ));
         msg
     }
 
@@ -127,9 +198,19 @@ pub mod help {
 "name_09:u512='8'"
 "name_10:unit=''"
 "name_11:string='a value'"
+"name_12:bytes='0a0b0c0d'"
+"name_13:bytes=@path/to/file"
 "key_account_name:key='{}'"
 "key_hash_name:key='{}'"
 "key_uref_name:key='{}'"
+"key_transfer_name:key='{}'"
+"key_deploy_name:key='{}'"
+"key_era_name:key='{}'"
+"key_balance_name:key='{}'"
+"key_bid_name:key='{}'"
+"key_withdraw_name:key='{}'"
+"key_dictionary_name:key='{}'"
+"key_system_contract_registry_name:key='{}'"
 "account_hash_name:account_hash='{}'"
 "uref_name:uref='{}'"
 "public_key_name:public_key='{}'"
@@ -146,10 +227,34 @@ Prefix the type with "opt_" and use the term "null" without quotes to specify a
 "name_08:opt_string='a value'"  # Some("a value".to_string())
 "name_09:opt_string='null'"     # Some("null".to_string())
 "name_10:opt_string=null"       # None
+
+Compound types can be nested to any depth. Lists are written as [...], maps as {{KEY: VALUE, ...}},
+tuples as (...) and results as ok(...) or err(...), with elements separated by commas. Bytes and
+byte arrays are written in hex, or as @ followed by the path of a file holding them, which needs no
+single quotes. Strings inside a compound value which contain any of ,:()[]{{}}<>" must be
+double-quoted, with " and \ escaped by a backslash (and the double quotes escaped for the shell):
+"name_01:list<u64>='[1, 2, 3]'"
+"name_02:map<string,key>='{{alice: {}, bob: {}}}'"
+"name_03:byte_array_4='0a0b0c0d'"
+"name_04:tuple2<u8,string>='(1, \"a, b\")'"
+"name_05:option<list<u256>>='[1, 2]'"     # Some(vec![1, 2])
+"name_06:option<list<u256>>=null"         # None
+"name_07:list<option<u8>>='[1, null]'"    # vec![Some(1), None]
+"name_08:result<u64,string>='ok(5)'"      # Ok(5)
+"name_09:result<u64,string>='err(failed)'" # Err("failed".to_string())
+"name_10:byte_array_32=@path/to/file"
 "#,
             Key::Account(AccountHash::new(array)).to_formatted_string(),
             Key::Hash(array).to_formatted_string(),
             Key::URef(URef::new(array, AccessRights::NONE)).to_formatted_string(),
+            Key::Transfer(TransferAddr::new(array)).to_formatted_string(),
+            Key::DeployInfo(DeployHash::new(array)).to_formatted_string(),
+            Key::EraInfo(EraId::new(1)).to_formatted_string(),
+            Key::Balance(array).to_formatted_string(),
+            Key::Bid(AccountHash::new(array)).to_formatted_string(),
+            Key::Withdraw(AccountHash::new(array)).to_formatted_string(),
+            Key::Dictionary(array).to_formatted_string(),
+            Key::SystemContractRegistry.to_formatted_string(),
             AccountHash::new(array).to_formatted_string(),
             URef::new(array, AccessRights::READ_ADD_WRITE).to_formatted_string(),
             PublicKey::from_hex(
@@ -157,6 +262,65 @@ Prefix the type with "opt_" and use the term "null" without quotes to specify a
             )
             .unwrap()
             .to_hex(),
+            Key::Account(AccountHash::new(array)).to_formatted_string(),
+            Key::Hash(array).to_formatted_string(),
+        )
+    }
+
+    /// Returns a string giving an example of the JSON file of args passed as payment code or
+    /// session code complex args.
+    pub fn complex_arg_examples() -> String {
+        let bytes = (1..33).collect::<Vec<_>>();
+        let array = <[u8; 32]>::try_from(bytes.as_ref()).unwrap();
+
+        format!(
+            r#"[
+  {{"name": "amount", "type": "U512", "value": "1000000"}},
+  {{"name": "recipients", "type": "List<Key>", "value": ["{}", {{"Hash": "{}"}}]}},
+  {{"name": "weights", "type": {{"Map": {{"key": "String", "value": "U64"}}}}, "value": [{{"key": "a", "value": 1}}]}},
+  {{"name": "names", "type": "map<string,u8>", "value": {{"a": 1, "b": 2}}}},
+  {{"name": "checksum", "type": "byte_array_4", "value": "0a0b0c0d"}},
+  {{"name": "metadata", "type": "Bytes", "value": "@path/to/file"}},
+  {{"name": "pair", "type": "Tuple2<U8,String>", "value": [1, "a value"]}},
+  {{"name": "maybe", "type": "Option<U64>", "value": null}},
+  {{"name": "outcome", "type": "Result<U64,String>", "value": {{"Err": "failed"}}}},
+  {{"name": "unit", "type": "Unit", "value": null}},
+  {{"name": "raw", "value": {{"raw_bytes": "040000000102030404"}}}}
+]
+
+Each arg's "type" is written as for --session-arg, case-insensitively, or as the "cl_type" of a
+CLValue in a deploy's JSON. Its "value" is written as the "parsed" field of such a CLValue, except
+that numbers may be JSON numbers or strings, maps with string keys may be JSON objects, and bytes
+and byte arrays may be "@" followed by the path of a file holding them. Such files are read from
+disk and included in the deploy, with a relative path resolved against the directory of the args
+file. An arg without a "type" must have a value of the form {{"raw_bytes": "<hex>"}}, holding the
+bytesrepr encoding of the CLValue.
+
+The same list may be written in a TOML or YAML file, either at the top level or under an "args" key.
+As TOML has no null, a "value" which would be null is omitted instead:
+
+[[args]]
+name = "amount"
+type = "U512"
+value = "1000000"
+
+[[args]]
+name = "maybe"
+type = "Option<U64>"
+
+or in YAML:
+
+- name: amount
+  type: U512
+  value: "1000000"
+- name: maybe
+  type: Option<U64>
+  value: null
+
+The file format is detected from a ".json", ".toml", ".yaml" or ".yml" extension, and a path of "-"
+reads the args from stdin."#,
+            Key::Account(AccountHash::new(array)).to_formatted_string(),
+            Key::Hash(array).to_formatted_string(),
         )
     }
 }
@@ -190,28 +354,30 @@ where
 
 /// Returns a value built from a single arg which has been split into its constituent parts.
 pub fn parts_to_cl_value(cl_type: CLType, value: &str) -> Result<CLValue> {
-    let (cl_type_to_parse, optional_status, trimmed_value) = match cl_type {
-        CLType::Option(inner_type) => {
-            if value == "null" {
-                (*inner_type, OptionalStatus::None, "")
-            } else {
-                (*inner_type, OptionalStatus::Some, value.trim_matches('\''))
-            }
-        }
-        _ => (
-            cl_type,
-            OptionalStatus::NotOptional,
-            value.trim_matches('\''),
-        ),
+    let (cl_type_to_parse, optional_status) = match cl_type {
+        CLType::Option(inner_type) if value == "null" => (*inner_type, OptionalStatus::None),
+        CLType::Option(inner_type) => (*inner_type, OptionalStatus::Some),
+        _ => (cl_type, OptionalStatus::NotOptional),
     };
 
-    if value == trimmed_value {
-        return Err(Error::InvalidCLValue(format!(
-            "value in simple arg should be surrounded by single quotes unless it's a null \
+    // Only the outermost pair of quotes is removed, so the value itself may start or end with one.
+    let is_file_reference =
+        is_bytes_type(&cl_type_to_parse) && value.starts_with(FILE_REFERENCE_PREFIX);
+    let trimmed_value = match value
+        .strip_prefix('\'')
+        .and_then(|value| value.strip_suffix('\''))
+    {
+        Some(trimmed_value) => trimmed_value,
+        None if optional_status == OptionalStatus::None => "",
+        None if is_file_reference => value,
+        None => {
+            return Err(Error::InvalidCLValue(format!(
+                "value in simple arg should be surrounded by single quotes unless it's a null \
                    optional value (value passed: {})",
-            value
-        )));
-    }
+                value
+            )))
+        }
+    };
 
     match cl_type_to_parse {
         CLType::Bool => {
@@ -325,7 +491,7 @@ pub fn parts_to_cl_value(cl_type: CLType, value: &str) -> Result<CLValue> {
         }
         CLType::Key => {
             let parse = || {
-                Key::from_formatted_str(trimmed_value).map_err(|error| {
+                global_state_key::from_formatted_str(trimmed_value).map_err(|error| {
                     Error::InvalidCLValue(format!(
                         "can't parse {} as Key: {}",
                         trimmed_value, error
@@ -334,18 +500,6 @@ pub fn parts_to_cl_value(cl_type: CLType, value: &str) -> Result<CLValue> {
             };
             parse_to_cl_value(optional_status, parse)
         }
-        CLType::ByteArray(32) => {
-            let parse = || {
-                AccountHash::from_formatted_str(trimmed_value).map_err(|error| {
-                    Error::InvalidCLValue(format!(
-                        "can't parse {} as AccountHash: {:?}.\
-                        AccountHash type values should start with 'account-hash-' prefix.",
-                        trimmed_value, error
-                    ))
-                })
-            };
-            parse_to_cl_value(optional_status, parse)
-        }
         CLType::URef => {
             let parse = || {
                 URef::from_formatted_str(trimmed_value).map_err(|error| {
@@ -370,6 +524,705 @@ pub fn parts_to_cl_value(cl_type: CLType, value: &str) -> Result<CLValue> {
             };
             parse_to_cl_value(optional_status, parse)
         }
-        _ => unreachable!(),
+        cl_type => {
+            // Files named in simple args are read relative to the current directory.
+            let base_dir = Path::new("");
+            let (cl_type, bytes) = match optional_status {
+                OptionalStatus::Some => {
+                    let mut bytes = vec![OPTION_SOME_TAG];
+                    bytes.extend(value_to_bytes(&cl_type, trimmed_value, base_dir)?);
+                    (CLType::Option(Box::new(cl_type)), bytes)
+                }
+                OptionalStatus::None => (CLType::Option(Box::new(cl_type)), vec![OPTION_NONE_TAG]),
+                OptionalStatus::NotOptional => {
+                    let bytes = value_to_bytes(&cl_type, trimmed_value, base_dir)?;
+                    (cl_type, bytes)
+                }
+            };
+            Ok(CLValue::from_components(cl_type, bytes))
+        }
+    }
+}
+
+/// Returns the serialized form of `value` parsed as the given type, which may be a compound type.
+///
+/// Scalar values are written as in a simple arg, but without the surrounding single quotes.
+fn value_to_bytes(cl_type: &CLType, value: &str, base_dir: &Path) -> Result<Vec<u8>> {
+    let value = value.trim();
+    let invalid = |expected: &str| {
+        Error::InvalidCLValue(format!(
+            "can't parse {} as {:?}: expected {}",
+            value, cl_type, expected
+        ))
+    };
+    match cl_type {
+        CLType::Bool
+        | CLType::I32
+        | CLType::I64
+        | CLType::U8
+        | CLType::U32
+        | CLType::U64
+        | CLType::U128
+        | CLType::U256
+        | CLType::U512
+        | CLType::String
+        | CLType::Key
+        | CLType::URef
+        | CLType::PublicKey => {
+            let cl_value = parts_to_cl_value(cl_type.clone(), &format!("'{}'", unquote(value)))?;
+            Ok(cl_value.inner_bytes().clone())
+        }
+        CLType::Unit => match value {
+            "" | "()" => Ok(vec![]),
+            _ => Err(invalid("()")),
+        },
+        CLType::Option(inner_type) => {
+            if value == "null" {
+                return Ok(vec![OPTION_NONE_TAG]);
+            }
+            let mut bytes = vec![OPTION_SOME_TAG];
+            bytes.extend(value_to_bytes(inner_type, value, base_dir)?);
+            Ok(bytes)
+        }
+        CLType::List(_) if is_bytes_type(cl_type) && !value.starts_with('[') => {
+            let bytes = raw_bytes(value, base_dir)?;
+            let mut serialized = (bytes.len() as u32).to_bytes()?;
+            serialized.extend(bytes);
+            Ok(serialized)
+        }
+        CLType::List(element_type) => {
+            let elements = delimited(value, '[', ']').ok_or_else(|| invalid("[...]"))?;
+            let mut bytes = (elements.len() as u32).to_bytes()?;
+            for element in elements {
+                bytes.extend(value_to_bytes(element_type, element, base_dir)?);
+            }
+            Ok(bytes)
+        }
+        CLType::Map {
+            key: key_type,
+            value: value_type,
+        } => {
+            let entries = delimited(value, '{', '}').ok_or_else(|| invalid("{KEY: VALUE, ...}"))?;
+            let mut serialized_entries = vec![];
+            let mut keys = HashSet::new();
+            for entry in entries {
+                let (key, value) = match split_top_level(entry, ':').as_slice() {
+                    [key, value] => (*key, *value),
+                    _ => return Err(invalid("{KEY: VALUE, ...}")),
+                };
+                let key_bytes = value_to_bytes(key_type, key, base_dir)?;
+                if !keys.insert(key_bytes.clone()) {
+                    return Err(Error::InvalidCLValue(format!(
+                        "map {} has duplicate key {}",
+                        value,
+                        key.trim()
+                    )));
+                }
+                serialized_entries.push((key_bytes, value_to_bytes(value_type, value, base_dir)?));
+            }
+            map_to_bytes(key_type, serialized_entries)
+        }
+        CLType::Tuple1(types) => {
+            tuple_to_bytes(types, value, base_dir).ok_or_else(|| invalid("(T1)"))?
+        }
+        CLType::Tuple2(types) => {
+            tuple_to_bytes(types, value, base_dir).ok_or_else(|| invalid("(T1, T2)"))?
+        }
+        CLType::Tuple3(types) => {
+            tuple_to_bytes(types, value, base_dir).ok_or_else(|| invalid("(T1, T2, T3)"))?
+        }
+        CLType::Result { ok, err } => {
+            let (tag, inner_type, inner_value) = if let Some(inner_value) = call_arg(value, "ok") {
+                (RESULT_OK_TAG, ok, inner_value)
+            } else if let Some(inner_value) = call_arg(value, "err") {
+                (RESULT_ERR_TAG, err, inner_value)
+            } else {
+                return Err(invalid("ok(...) or err(...)"));
+            };
+            let mut bytes = vec![tag];
+            bytes.extend(value_to_bytes(inner_type, inner_value, base_dir)?);
+            Ok(bytes)
+        }
+        CLType::ByteArray(length) => {
+            if *length == 32 && value.starts_with("account-hash-") {
+                return AccountHash::from_formatted_str(value)
+                    .map(|account_hash| account_hash.value().to_vec())
+                    .map_err(|error| {
+                        Error::InvalidCLValue(format!(
+                            "can't parse {} as AccountHash: {:?}",
+                            value, error
+                        ))
+                    });
+            }
+            let bytes = raw_bytes(value, base_dir)?;
+            if bytes.len() != *length as usize {
+                return Err(Error::InvalidCLValue(format!(
+                    "byte array {} has {} bytes, expected {}",
+                    value,
+                    bytes.len(),
+                    length
+                )));
+            }
+            Ok(bytes)
+        }
+        CLType::Any => Err(Error::InvalidCLValue(format!(
+            "can't parse {} as {:?}: unsupported type",
+            value, cl_type
+        ))),
+    }
+}
+
+/// Returns a value of the given type built from its JSON representation.
+///
+/// The JSON representation matches the `parsed` field of a `CLValue` in a deploy's JSON: numbers
+/// may be JSON numbers or strings, `Unit` is `null`, `Key` may be a formatted string such as
+/// `"hash-..."` or an object such as `{"Hash": "hash-..."}`, byte arrays are hex strings, lists
+/// and tuples are arrays, maps are arrays of `{"key": KEY, "value": VALUE}` objects (or, for maps
+/// with string keys, JSON objects), options are `null` or their inner value and results are
+/// `{"Ok": VALUE}` or `{"Err": VALUE}`.
+pub(crate) fn json_to_cl_value(cl_type: CLType, value: &Value, base_dir: &Path) -> Result<CLValue> {
+    let bytes = json_to_bytes(&cl_type, value, base_dir)?;
+    Ok(CLValue::from_components(cl_type, bytes))
+}
+
+/// Returns the serialized form of the JSON `value` parsed as the given type.
+fn json_to_bytes(cl_type: &CLType, value: &Value, base_dir: &Path) -> Result<Vec<u8>> {
+    let invalid = |expected: &str| {
+        Error::InvalidCLValue(format!(
+            "can't parse {} as {:?}: expected {}",
+            value, cl_type, expected
+        ))
+    };
+    match cl_type {
+        CLType::Bool => value
+            .as_bool()
+            .ok_or_else(|| invalid("true or false"))?
+            .to_bytes()
+            .map_err(Error::from),
+        CLType::I32
+        | CLType::I64
+        | CLType::U8
+        | CLType::U32
+        | CLType::U64
+        | CLType::U128
+        | CLType::U256
+        | CLType::U512 => match value {
+            Value::Number(number) => value_to_bytes(cl_type, &number.to_string(), base_dir),
+            Value::String(number) => value_to_bytes(cl_type, number, base_dir),
+            _ => Err(invalid("a number")),
+        },
+        CLType::Unit => match value {
+            Value::Null => Ok(vec![]),
+            _ => Err(invalid("null")),
+        },
+        CLType::String => value
+            .as_str()
+            .ok_or_else(|| invalid("a string"))?
+            .to_bytes()
+            .map_err(Error::from),
+        CLType::Key if value.is_object() => serde_json::from_value::<Key>(value.clone())
+            .map_err(|error| invalid(&error.to_string()))?
+            .to_bytes()
+            .map_err(Error::from),
+        CLType::Key | CLType::URef | CLType::PublicKey | CLType::ByteArray(_) => value_to_bytes(
+            cl_type,
+            value.as_str().ok_or_else(|| invalid("a string"))?,
+            base_dir,
+        ),
+        CLType::Option(inner_type) => {
+            if value.is_null() {
+                return Ok(vec![OPTION_NONE_TAG]);
+            }
+            let mut bytes = vec![OPTION_SOME_TAG];
+            bytes.extend(json_to_bytes(inner_type, value, base_dir)?);
+            Ok(bytes)
+        }
+        CLType::List(_) if is_bytes_type(cl_type) && value.is_string() => value_to_bytes(
+            cl_type,
+            value.as_str().expect("should be a string"),
+            base_dir,
+        ),
+        CLType::List(element_type) => {
+            let elements = value.as_array().ok_or_else(|| invalid("an array"))?;
+            let mut bytes = (elements.len() as u32).to_bytes()?;
+            for element in elements {
+                bytes.extend(json_to_bytes(element_type, element, base_dir)?);
+            }
+            Ok(bytes)
+        }
+        CLType::Map {
+            key: key_type,
+            value: value_type,
+        } => {
+            let expected = "an array of {\"key\": KEY, \"value\": VALUE} objects";
+            let entries: Vec<(Value, &Value)> = match value {
+                Value::Array(entries) => entries
+                    .iter()
+                    .map(|entry| match (entry.get("key"), entry.get("value")) {
+                        (Some(key), Some(value)) => Ok((key.clone(), value)),
+                        _ => Err(invalid(expected)),
+                    })
+                    .collect::<Result<_>>()?,
+                Value::Object(map) => map
+                    .iter()
+                    .map(|(key, value)| (Value::String(key.clone()), value))
+                    .collect(),
+                _ => return Err(invalid(expected)),
+            };
+            let mut serialized_entries = vec![];
+            let mut keys = HashSet::new();
+            for (key, value) in entries {
+                let key_bytes = json_to_bytes(key_type, &key, base_dir)?;
+                if !keys.insert(key_bytes.clone()) {
+                    return Err(invalid(&format!("no duplicate keys, but found {}", key)));
+                }
+                serialized_entries.push((key_bytes, json_to_bytes(value_type, value, base_dir)?));
+            }
+            map_to_bytes(key_type, serialized_entries)
+        }
+        CLType::Tuple1(types) => {
+            json_tuple_to_bytes(types, value, base_dir).ok_or_else(|| invalid("[T1]"))?
+        }
+        CLType::Tuple2(types) => {
+            json_tuple_to_bytes(types, value, base_dir).ok_or_else(|| invalid("[T1, T2]"))?
+        }
+        CLType::Tuple3(types) => {
+            json_tuple_to_bytes(types, value, base_dir).ok_or_else(|| invalid("[T1, T2, T3]"))?
+        }
+        CLType::Result { ok, err } => {
+            let expected = "{\"Ok\": VALUE} or {\"Err\": VALUE}";
+            let (variant, inner_value) = match value.as_object() {
+                Some(map) if map.len() == 1 => map.iter().next().expect("should have an entry"),
+                _ => return Err(invalid(expected)),
+            };
+            let (tag, inner_type) = match variant.to_lowercase().as_str() {
+                "ok" => (RESULT_OK_TAG, ok),
+                "err" => (RESULT_ERR_TAG, err),
+                _ => return Err(invalid(expected)),
+            };
+            let mut bytes = vec![tag];
+            bytes.extend(json_to_bytes(inner_type, inner_value, base_dir)?);
+            Ok(bytes)
+        }
+        CLType::Any => Err(invalid("a supported type")),
+    }
+}
+
+/// Returns the serialized form of the JSON tuple `value`, or `None` if it isn't an array with an
+/// element per type.
+fn json_tuple_to_bytes(
+    types: &[Box<CLType>],
+    value: &Value,
+    base_dir: &Path,
+) -> Option<Result<Vec<u8>>> {
+    let elements = value
+        .as_array()
+        .filter(|array| array.len() == types.len())?;
+    let mut bytes = vec![];
+    for (element_type, element) in types.iter().zip(elements) {
+        match json_to_bytes(element_type, element, base_dir) {
+            Ok(element_bytes) => bytes.extend(element_bytes),
+            Err(error) => return Some(Err(error)),
+        }
+    }
+    Some(Ok(bytes))
+}
+
+/// Parses `cl_type` as a type name, returning an error listing the supported types if it is
+/// invalid.
+fn parse_named(cl_type: &str) -> Result<CLType> {
+    parse(cl_type).map_err(|_| Error::InvalidArgument {
+        context: "cl_type",
+        error: format!(
+            "unknown CLType '{}', expected one of {}",
+            cl_type,
+            help::supported_cl_type_list()
+        ),
+    })
+}
+
+/// Returns `value` parsed as the type named by `cl_type`, both written as for a simple arg, but
+/// with the value not surrounded by single quotes.
+pub(crate) fn encode(cl_type: &str, value: &str) -> Result<CLValue> {
+    let cl_type = parse_named(cl_type)?;
+    if value == "null" {
+        return parts_to_cl_value(cl_type, value);
+    }
+    parts_to_cl_value(cl_type, &format!("'{}'", value))
+}
+
+/// Decodes `bytes` as the serialized value of the type named by `cl_type`, or as a serialized
+/// `CLValue` if `cl_type` is empty.
+pub(crate) fn decode(cl_type: &str, bytes: &[u8]) -> Result<CLValue> {
+    let failed = |cl_type: Option<&CLType>, offset, error| {
+        Error::InvalidCLValue(format!(
+            "failed to decode {}: {} at byte offset {}",
+            cl_type.map_or_else(|| "CLValue".to_string(), |cl_type| format!("{:?}", cl_type)),
+            error,
+            offset
+        ))
+    };
+    if cl_type.is_empty() {
+        return decode_cl_value(bytes)
+            .map_err(|error| failed(error.cl_type.as_ref(), error.offset, error.error));
+    }
+    let cl_type = parse_named(cl_type)?;
+    check_bytes(&cl_type, bytes)
+        .map_err(|(offset, error)| failed(Some(&cl_type), offset, error))?;
+    Ok(CLValue::from_components(cl_type, bytes.to_vec()))
+}
+
+/// The failure to decode a serialized `CLValue`.
+pub(crate) struct DecodeError {
+    /// The type of the value, if it was decoded.
+    pub(crate) cl_type: Option<CLType>,
+    /// The offset into the serialized `CLValue` at which decoding failed.
+    pub(crate) offset: usize,
+    pub(crate) error: bytesrepr::Error,
+}
+
+/// Decodes `bytes` as a serialized `CLValue`, checking that its value is exactly the serialized
+/// form of a value of its type.
+pub(crate) fn decode_cl_value(bytes: &[u8]) -> StdResult<CLValue, DecodeError> {
+    let decode_error = |cl_type: Option<&CLType>, offset, error| DecodeError {
+        cl_type: cl_type.cloned(),
+        offset,
+        error,
+    };
+    let (inner_bytes, remainder) =
+        Bytes::from_bytes(bytes).map_err(|error| decode_error(None, 0, error))?;
+    let type_offset = bytes.len() - remainder.len();
+    let (cl_type, remainder) =
+        CLType::from_bytes(remainder).map_err(|error| decode_error(None, type_offset, error))?;
+    if !remainder.is_empty() {
+        return Err(decode_error(
+            Some(&cl_type),
+            bytes.len() - remainder.len(),
+            bytesrepr::Error::LeftOverBytes,
+        ));
+    }
+    check_bytes(&cl_type, &inner_bytes).map_err(|(offset, error)| {
+        decode_error(Some(&cl_type), U32_SERIALIZED_LENGTH + offset, error)
+    })?;
+    Ok(CLValue::from_components(cl_type, inner_bytes.into()))
+}
+
+/// Checks that `bytes` is exactly the serialized form of a value of the given type, returning the
+/// offset into `bytes` at which decoding failed if not.
+pub(crate) fn check_bytes(
+    cl_type: &CLType,
+    bytes: &[u8],
+) -> StdResult<(), (usize, bytesrepr::Error)> {
+    let length = decoded_length(cl_type, bytes)?;
+    if length < bytes.len() {
+        return Err((length, bytesrepr::Error::LeftOverBytes));
+    }
+    Ok(())
+}
+
+/// Returns the length of the serialized value of the given type at the start of `bytes`, or the
+/// offset into `bytes` at which decoding failed.
+fn decoded_length(cl_type: &CLType, bytes: &[u8]) -> StdResult<usize, (usize, bytesrepr::Error)> {
+    fn scalar<T: FromBytes>(bytes: &[u8]) -> StdResult<usize, (usize, bytesrepr::Error)> {
+        T::from_bytes(bytes)
+            .map(|(_, remainder)| bytes.len() - remainder.len())
+            .map_err(|error| (0, error))
+    }
+
+    /// Returns the length of the serialized value of the given type at `offset` in `bytes`.
+    fn nested(
+        cl_type: &CLType,
+        bytes: &[u8],
+        offset: usize,
+    ) -> StdResult<usize, (usize, bytesrepr::Error)> {
+        decoded_length(cl_type, &bytes[offset..])
+            .map_err(|(inner_offset, error)| (offset + inner_offset, error))
+    }
+
+    /// Returns the length of the serialized values of the given types in sequence at `offset`.
+    fn sequence<'a>(
+        cl_types: impl IntoIterator<Item = &'a CLType>,
+        bytes: &[u8],
+        mut offset: usize,
+    ) -> StdResult<usize, (usize, bytesrepr::Error)> {
+        for cl_type in cl_types {
+            offset += nested(cl_type, bytes, offset)?;
+        }
+        Ok(offset)
+    }
+
+    match cl_type {
+        CLType::Bool => scalar::<bool>(bytes),
+        CLType::I32 => scalar::<i32>(bytes),
+        CLType::I64 => scalar::<i64>(bytes),
+        CLType::U8 => scalar::<u8>(bytes),
+        CLType::U32 => scalar::<u32>(bytes),
+        CLType::U64 => scalar::<u64>(bytes),
+        CLType::U128 => scalar::<U128>(bytes),
+        CLType::U256 => scalar::<U256>(bytes),
+        CLType::U512 => scalar::<U512>(bytes),
+        CLType::Unit => scalar::<()>(bytes),
+        CLType::String => scalar::<String>(bytes),
+        CLType::Key => scalar::<Key>(bytes),
+        CLType::URef => scalar::<URef>(bytes),
+        CLType::PublicKey => scalar::<PublicKey>(bytes),
+        CLType::Option(inner_type) => match u8::from_bytes(bytes).map_err(|error| (0, error))? {
+            (OPTION_NONE_TAG, _) => Ok(1),
+            (OPTION_SOME_TAG, _) => sequence([inner_type.as_ref()], bytes, 1),
+            _ => Err((0, bytesrepr::Error::Formatting)),
+        },
+        CLType::List(element_type) => {
+            let (count, _) = u32::from_bytes(bytes).map_err(|error| (0, error))?;
+            sequence(
+                (0..count).map(|_| element_type.as_ref()),
+                bytes,
+                U32_SERIALIZED_LENGTH,
+            )
+        }
+        CLType::ByteArray(length) => {
+            if bytes.len() < *length as usize {
+                return Err((0, bytesrepr::Error::EarlyEndOfStream));
+            }
+            Ok(*length as usize)
+        }
+        CLType::Result { ok, err } => match u8::from_bytes(bytes).map_err(|error| (0, error))? {
+            (RESULT_OK_TAG, _) => sequence([ok.as_ref()], bytes, 1),
+            (RESULT_ERR_TAG, _) => sequence([err.as_ref()], bytes, 1),
+            _ => Err((0, bytesrepr::Error::Formatting)),
+        },
+        CLType::Map { key, value } => {
+            let (count, _) = u32::from_bytes(bytes).map_err(|error| (0, error))?;
+            sequence(
+                (0..count).flat_map(|_| [key.as_ref(), value.as_ref()]),
+                bytes,
+                U32_SERIALIZED_LENGTH,
+            )
+        }
+        CLType::Tuple1(types) => sequence(types.iter().map(Box::as_ref), bytes, 0),
+        CLType::Tuple2(types) => sequence(types.iter().map(Box::as_ref), bytes, 0),
+        CLType::Tuple3(types) => sequence(types.iter().map(Box::as_ref), bytes, 0),
+        CLType::Any => Err((0, bytesrepr::Error::Formatting)),
+    }
+}
+
+/// A deserialized value of any supported `CLType`, ordered as the corresponding Rust type is.
+///
+/// Values of the same `CLType` always have the same variant, so compare as their Rust types do,
+/// e.g. a `List` compares lexicographically like a `Vec`, and a `Map` like a `BTreeMap`.
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum SortKey {
+    Bool(bool),
+    I32(i32),
+    I64(i64),
+    U8(u8),
+    U32(u32),
+    U64(u64),
+    U128(U128),
+    U256(U256),
+    U512(U512),
+    Unit,
+    String(String),
+    Key(Key),
+    URef(URef),
+    PublicKey(PublicKey),
+    Option(Option<Box<SortKey>>),
+    Sequence(Vec<SortKey>),
+    Bytes(Vec<u8>),
+    Result(StdResult<Box<SortKey>, Box<SortKey>>),
+}
+
+impl SortKey {
+    /// Deserializes a value of `cl_type` from the start of `bytes`, returning it and the remainder.
+    fn from_bytes<'a>(
+        cl_type: &CLType,
+        bytes: &'a [u8],
+    ) -> StdResult<(SortKey, &'a [u8]), bytesrepr::Error> {
+        fn scalar<T: FromBytes>(
+            bytes: &[u8],
+            variant: fn(T) -> SortKey,
+        ) -> StdResult<(SortKey, &[u8]), bytesrepr::Error> {
+            T::from_bytes(bytes).map(|(value, remainder)| (variant(value), remainder))
+        }
+
+        fn sequence<'a, 'b>(
+            cl_types: impl IntoIterator<Item = &'b CLType>,
+            mut bytes: &'a [u8],
+        ) -> StdResult<(SortKey, &'a [u8]), bytesrepr::Error> {
+            let mut elements = vec![];
+            for cl_type in cl_types {
+                let (element, remainder) = SortKey::from_bytes(cl_type, bytes)?;
+                elements.push(element);
+                bytes = remainder;
+            }
+            Ok((SortKey::Sequence(elements), bytes))
+        }
+
+        match cl_type {
+            CLType::Bool => scalar(bytes, SortKey::Bool),
+            CLType::I32 => scalar(bytes, SortKey::I32),
+            CLType::I64 => scalar(bytes, SortKey::I64),
+            CLType::U8 => scalar(bytes, SortKey::U8),
+            CLType::U32 => scalar(bytes, SortKey::U32),
+            CLType::U64 => scalar(bytes, SortKey::U64),
+            CLType::U128 => scalar(bytes, SortKey::U128),
+            CLType::U256 => scalar(bytes, SortKey::U256),
+            CLType::U512 => scalar(bytes, SortKey::U512),
+            CLType::Unit => scalar(bytes, |()| SortKey::Unit),
+            CLType::String => scalar(bytes, SortKey::String),
+            CLType::Key => scalar(bytes, SortKey::Key),
+            CLType::URef => scalar(bytes, SortKey::URef),
+            CLType::PublicKey => scalar(bytes, SortKey::PublicKey),
+            CLType::Option(inner_type) => match u8::from_bytes(bytes)? {
+                (OPTION_NONE_TAG, remainder) => Ok((SortKey::Option(None), remainder)),
+                (OPTION_SOME_TAG, remainder) => {
+                    let (inner, remainder) = SortKey::from_bytes(inner_type, remainder)?;
+                    Ok((SortKey::Option(Some(Box::new(inner))), remainder))
+                }
+                _ => Err(bytesrepr::Error::Formatting),
+            },
+            CLType::List(element_type) => {
+                let (count, remainder) = u32::from_bytes(bytes)?;
+                sequence((0..count).map(|_| element_type.as_ref()), remainder)
+            }
+            CLType::ByteArray(length) => {
+                if bytes.len() < *length as usize {
+                    return Err(bytesrepr::Error::EarlyEndOfStream);
+                }
+                let (array, remainder) = bytes.split_at(*length as usize);
+                Ok((SortKey::Bytes(array.to_vec()), remainder))
+            }
+            CLType::Result { ok, err } => {
+                let (tag, remainder) = u8::from_bytes(bytes)?;
+                let (variant, inner_type): (fn(_) -> _, _) = match tag {
+                    RESULT_OK_TAG => (Ok, ok),
+                    RESULT_ERR_TAG => (Err, err),
+                    _ => return Err(bytesrepr::Error::Formatting),
+                };
+                let (inner, remainder) = SortKey::from_bytes(inner_type, remainder)?;
+                Ok((SortKey::Result(variant(Box::new(inner))), remainder))
+            }
+            // A map's entries are serialized in order, so it compares as the sequence of its keys
+            // and values.
+            CLType::Map { key, value } => {
+                let (count, remainder) = u32::from_bytes(bytes)?;
+                sequence(
+                    (0..count).flat_map(|_| [key.as_ref(), value.as_ref()]),
+                    remainder,
+                )
+            }
+            CLType::Tuple1(types) => sequence(types.iter().map(Box::as_ref), bytes),
+            CLType::Tuple2(types) => sequence(types.iter().map(Box::as_ref), bytes),
+            CLType::Tuple3(types) => sequence(types.iter().map(Box::as_ref), bytes),
+            CLType::Any => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+/// Returns the serialized form of a map with the given serialized keys and values.
+///
+/// The entries are sorted by their deserialized keys, so the result is the same as that of
+/// `BTreeMap::to_bytes` regardless of the order in which the entries were given.
+fn map_to_bytes(key_type: &CLType, entries: Vec<(Vec<u8>, Vec<u8>)>) -> Result<Vec<u8>> {
+    let mut sorted_entries = entries
+        .into_iter()
+        .map(|(key_bytes, value_bytes)| {
+            let (sort_key, _) = SortKey::from_bytes(key_type, &key_bytes)?;
+            Ok((sort_key, key_bytes, value_bytes))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    sorted_entries.sort_by(|(lhs, ..), (rhs, ..)| lhs.cmp(rhs));
+
+    let mut bytes = (sorted_entries.len() as u32).to_bytes()?;
+    for (_, key_bytes, value_bytes) in sorted_entries {
+        bytes.extend(key_bytes);
+        bytes.extend(value_bytes);
+    }
+    Ok(bytes)
+}
+
+/// Returns the serialized form of the tuple `value`, or `None` if it isn't of the form
+/// `(ELEMENT, ...)` with an element per type.
+fn tuple_to_bytes(types: &[Box<CLType>], value: &str, base_dir: &Path) -> Option<Result<Vec<u8>>> {
+    let elements = delimited(value, '(', ')')?;
+    if elements.len() != types.len() {
+        return None;
+    }
+    let mut bytes = vec![];
+    for (element_type, element) in types.iter().zip(elements) {
+        match value_to_bytes(element_type, element, base_dir) {
+            Ok(element_bytes) => bytes.extend(element_bytes),
+            Err(error) => return Some(Err(error)),
+        }
+    }
+    Some(Ok(bytes))
+}
+
+/// Returns true if values of `cl_type` are raw bytes, i.e. it's `bytes` or `byte_array_N`.
+fn is_bytes_type(cl_type: &CLType) -> bool {
+    match cl_type {
+        CLType::ByteArray(_) => true,
+        CLType::List(element_type) => **element_type == CLType::U8,
+        _ => false,
+    }
+}
+
+/// Returns the bytes given by `value`: the contents of the file it names if it's of the form
+/// "@path/to/file", or else `value` decoded from hex.
+///
+/// A relative path is resolved against `base_dir`, e.g. the directory of the args file which
+/// holds the value.
+fn raw_bytes(value: &str, base_dir: &Path) -> Result<Vec<u8>> {
+    if let Some(path) = value.strip_prefix(FILE_REFERENCE_PREFIX) {
+        let path = base_dir.join(unquote(path.trim()));
+        return fs::read(&path).map_err(|error| Error::IoError {
+            context: format!("failed to read bytes from '{}'", path.display()),
+            error,
+        });
+    }
+    base16::decode(value)
+        .map_err(|error| Error::InvalidCLValue(format!("can't parse {} as hex: {}", value, error)))
+}
+
+/// Returns the comma-separated elements of `value` enclosed by `open` and `close`, or `None` if
+/// `value` isn't enclosed by them.
+fn delimited(value: &str, open: char, close: char) -> Option<Vec<&str>> {
+    let inner = value.strip_prefix(open)?.strip_suffix(close)?;
+    if inner.trim().is_empty() {
+        return Some(vec![]);
+    }
+    Some(split_top_level(inner, ','))
+}
+
+/// Returns the argument of `value` if it's of the form `name(ARG)`, ignoring case.
+fn call_arg<'a>(value: &'a str, name: &str) -> Option<&'a str> {
+    let prefix = value.get(..name.len())?;
+    if !prefix.eq_ignore_ascii_case(name) {
+        return None;
+    }
+    value[name.len()..]
+        .trim_start()
+        .strip_prefix('(')?
+        .strip_suffix(')')
+}
+
+/// Returns `value` without surrounding double quotes, unescaping `\"` and `\\` within them.
+fn unquote(value: &str) -> String {
+    match value
+        .strip_prefix('"')
+        .and_then(|quoted| quoted.strip_suffix('"'))
+    {
+        Some(quoted) => {
+            let mut unquoted = String::with_capacity(quoted.len());
+            let mut chars = quoted.chars();
+            while let Some(char) = chars.next() {
+                if char == '\\' {
+                    unquoted.extend(chars.next());
+                } else {
+                    unquoted.push(char);
+                }
+            }
+            unquoted
+        }
+        None => value.to_string(),
     }
 }
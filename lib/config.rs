@@ -0,0 +1,251 @@
+//! The client's config file, holding named profiles of default values for commonly-used args.
+//!
+//! The config file is TOML, e.g.
+//!
+//! ```toml
+//! default_profile = "local"
+//!
+//! [profiles.testnet]
+//! node_address = "http://testnet-node.example.com:7777"
+//! chain_name = "casper-test"
+//! secret_key = "keys/testnet/secret_key.pem"
+//! payment_amount = "2500000000"
+//! ttl = "1h"
+//!
+//! [profiles.local]
+//! chain_name = "casper-net-1"
+//! ```
+//!
+//! Relative `secret_key` paths are relative to the directory holding the config file.
+
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+
+/// Name of the environment variable which, if set, holds the path to the config file.
+pub const CONFIG_ENV_VAR: &str = "CASPER_CLIENT_CONFIG";
+/// Name of the environment variable which, if set, holds the name of the profile to use.
+pub const PROFILE_ENV_VAR: &str = "CASPER_CLIENT_PROFILE";
+/// The path of the config file relative to the user's config directory.
+const CONFIG_FILE: &str = "casper-client/config.toml";
+
+/// The default values for args set by a profile.  Unset values are `None`.
+#[derive(Serialize, Deserialize, Default, Clone, PartialEq, Eq, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    /// Hostname or IP and port of the node on which the HTTP service is running.
+    pub node_address: Option<String>,
+    /// Name of the chain for which deploys are created.
+    pub chain_name: Option<String>,
+    /// Path to the secret key file with which deploys and messages are signed.
+    pub secret_key: Option<String>,
+    /// The amount of standard payment for deploys.
+    pub payment_amount: Option<String>,
+    /// Time that deploys remain valid for, e.g. "30min".
+    pub ttl: Option<String>,
+}
+
+#[derive(Deserialize, Default, Debug)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    default_profile: Option<String>,
+    #[serde(default)]
+    profiles: BTreeMap<String, Profile>,
+}
+
+/// The settings resolved from the config file and the selected profile.
+#[derive(Serialize, Default, Clone, PartialEq, Eq, Debug)]
+pub struct Config {
+    /// The path of the config file read, if any.
+    pub config_file: Option<PathBuf>,
+    /// The name of the selected profile, if any.
+    pub profile: Option<String>,
+    /// The settings of the selected profile, or no settings if no profile is selected.
+    #[serde(flatten)]
+    pub settings: Profile,
+}
+
+/// Returns the default path of the config file, "casper-client/config.toml" in the user's config
+/// directory: `$XDG_CONFIG_HOME`, or else `$HOME/.config`, or on Windows `%APPDATA%`.
+pub fn default_path() -> Option<PathBuf> {
+    let non_empty = |name: &str| env::var_os(name).filter(|value| !value.is_empty());
+    let config_dir = non_empty("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| non_empty("HOME").map(|home| Path::new(&home).join(".config")))
+        .or_else(|| non_empty("APPDATA").map(PathBuf::from))?;
+    Some(config_dir.join(CONFIG_FILE))
+}
+
+/// Reads the config file and resolves the settings of the selected profile.
+///
+/// * `config_path` is the path of the config file.  If empty, the path in [`CONFIG_ENV_VAR`] is
+///   used, or else [`default_path`].  A missing file is only an error if its path was given
+///   explicitly, or if a profile is selected.
+/// * `profile` is the name of the profile to use.  If empty, the name in [`PROFILE_ENV_VAR`] is
+///   used, or else the config file's `default_profile`.  If no profile is selected, no settings
+///   are returned.
+pub fn load(config_path: &str, profile: &str) -> Result<Config> {
+    let from_env = |name: &str| env::var(name).ok().filter(|value| !value.is_empty());
+    let explicit_path = Some(config_path.to_string())
+        .filter(|path| !path.is_empty())
+        .or_else(|| from_env(CONFIG_ENV_VAR))
+        .map(PathBuf::from);
+    let explicit_profile = Some(profile.to_string())
+        .filter(|profile| !profile.is_empty())
+        .or_else(|| from_env(PROFILE_ENV_VAR));
+
+    let path = match explicit_path.clone().or_else(default_path) {
+        Some(path) if explicit_path.is_some() || path.exists() => path,
+        _ => {
+            return match explicit_profile {
+                Some(profile) => Err(Error::InvalidArgument {
+                    context: "profile",
+                    error: format!("no config file exists to define profile '{}'", profile),
+                }),
+                None => Ok(Config::default()),
+            }
+        }
+    };
+
+    let contents = fs::read_to_string(&path).map_err(|error| Error::IoError {
+        context: format!("unable to read config file at '{}'", path.display()),
+        error,
+    })?;
+    let config_file: ConfigFile =
+        toml::from_str(&contents).map_err(|error| Error::InvalidArgument {
+            context: "config_file",
+            error: format!("'{}': {}", path.display(), error),
+        })?;
+
+    let profile = match explicit_profile.or_else(|| config_file.default_profile.clone()) {
+        Some(profile) => profile,
+        None => {
+            return Ok(Config {
+                config_file: Some(path),
+                ..Config::default()
+            })
+        }
+    };
+    let mut settings =
+        config_file
+            .profiles
+            .get(&profile)
+            .cloned()
+            .ok_or_else(|| Error::InvalidArgument {
+                context: "profile",
+                error: format!(
+                    "'{}' is not defined in '{}'; available profiles: {:?}",
+                    profile,
+                    path.display(),
+                    config_file.profiles.keys().collect::<Vec<_>>()
+                ),
+            })?;
+    if let (Some(secret_key), Some(config_dir)) = (&settings.secret_key, path.parent()) {
+        settings.secret_key = Some(config_dir.join(secret_key).display().to_string());
+    }
+
+    Ok(Config {
+        config_file: Some(path),
+        profile: Some(profile),
+        settings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    const CONFIG: &str = r#"
+default_profile = "local"
+
+[profiles.testnet]
+node_address = "http://1.2.3.4:7777"
+chain_name = "casper-test"
+secret_key = "keys/secret_key.pem"
+payment_amount = "2500000000"
+ttl = "1h"
+
+[profiles.local]
+chain_name = "casper-net-1"
+"#;
+
+    fn write_config(contents: &str) -> (TempDir, String) {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.toml");
+        fs::write(&path, contents).unwrap();
+        (temp_dir, path.display().to_string())
+    }
+
+    #[test]
+    fn should_load_selected_profile() {
+        let (temp_dir, path) = write_config(CONFIG);
+
+        let config = load(&path, "testnet").unwrap();
+        assert_eq!(config.config_file, Some(PathBuf::from(&path)));
+        assert_eq!(config.profile.as_deref(), Some("testnet"));
+        assert_eq!(
+            config.settings,
+            Profile {
+                node_address: Some("http://1.2.3.4:7777".to_string()),
+                chain_name: Some("casper-test".to_string()),
+                secret_key: Some(
+                    temp_dir
+                        .path()
+                        .join("keys/secret_key.pem")
+                        .display()
+                        .to_string()
+                ),
+                payment_amount: Some("2500000000".to_string()),
+                ttl: Some("1h".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn should_load_default_profile() {
+        let (_temp_dir, path) = write_config(CONFIG);
+        let config = load(&path, "").unwrap();
+        assert_eq!(config.profile.as_deref(), Some("local"));
+        assert_eq!(config.settings.chain_name.as_deref(), Some("casper-net-1"));
+        assert_eq!(config.settings.node_address, None);
+
+        let (_temp_dir, path) = write_config("[profiles.local]\n");
+        let config = load(&path, "").unwrap();
+        assert_eq!(config.profile, None);
+        assert_eq!(config.settings, Profile::default());
+    }
+
+    #[test]
+    fn should_fail_to_load_invalid_config() {
+        let (_temp_dir, path) = write_config(CONFIG);
+        assert!(matches!(
+            load(&path, "mainnet"),
+            Err(Error::InvalidArgument {
+                context: "profile",
+                ..
+            })
+        ));
+
+        let (_temp_dir, path) = write_config("[profiles.local]\nnode = \"x\"\n");
+        assert!(matches!(
+            load(&path, "local"),
+            Err(Error::InvalidArgument {
+                context: "config_file",
+                ..
+            })
+        ));
+
+        assert!(matches!(
+            load("missing.toml", ""),
+            Err(Error::IoError { .. })
+        ));
+    }
+}
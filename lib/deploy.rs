@@ -1,9 +1,10 @@
 use rand::{self, distributions::Alphanumeric, Rng};
 use serde::{Deserialize, Serialize};
 use std::{
-    fs::{self, File},
+    fs::{self, File, OpenOptions},
     io::{self, BufReader, Read, Write},
     path::{Path, PathBuf},
+    str,
 };
 
 use casper_execution_engine::core::engine_state::ExecutableDeployItem;
@@ -12,22 +13,20 @@ use casper_node::{
     types::{Deploy, DeployHash, TimeDiff, Timestamp},
 };
 use casper_types::{
-    account::AccountHash, AsymmetricType, ProtocolVersion, PublicKey, RuntimeArgs, SecretKey,
-    UIntParseError, URef, U512,
+    account::AccountHash,
+    bytesrepr::{self, ToBytes},
+    AsymmetricType, ProtocolVersion, PublicKey, RuntimeArgs, URef,
 };
 
 use crate::{
+    deploy_limits::DeployLimits,
     error::{Error, Result},
     parsing,
     rpc::RpcClient,
+    signer::{self, Signer},
+    units,
 };
 
-/// The maximum permissible size in bytes of a Deploy when serialized via `ToBytes`.
-///
-/// Note: this should be kept in sync with the value of `[deploys.max_deploy_size]` in the
-/// production chainspec.
-const MAX_SERIALIZED_SIZE: u32 = 1_024 * 1_024;
-
 /// SendDeploy allows sending a deploy to the node.
 pub(crate) struct SendDeploy;
 
@@ -88,6 +87,8 @@ pub(super) enum OutputKind<'a> {
         tmp_path: PathBuf,
         /// If `overwrite_if_exists` is `true`, then the file at `path` will be overwritten.
         overwrite_if_exists: bool,
+        /// If `private` is `true`, then only the owner may read or write the file.
+        private: bool,
     },
     Stdout,
 }
@@ -96,6 +97,10 @@ impl<'a> OutputKind<'a> {
     /// This is a convenience method that acts as a constructor for a new `OutputKind::File` enum
     /// variant.
     pub(super) fn file(path: &'a str, overwrite_if_exists: bool) -> Self {
+        Self::new_file(path, overwrite_if_exists, false)
+    }
+
+    fn new_file(path: &'a str, overwrite_if_exists: bool, private: bool) -> Self {
         let collision_resistant_string = rand::thread_rng()
             .sample_iter(&Alphanumeric)
             .take(5)
@@ -107,6 +112,7 @@ impl<'a> OutputKind<'a> {
             path,
             tmp_path,
             overwrite_if_exists,
+            private,
         }
     }
 
@@ -117,13 +123,13 @@ impl<'a> OutputKind<'a> {
                 path,
                 tmp_path,
                 overwrite_if_exists,
-                ..
+                private,
             } => {
                 let path = PathBuf::from(path);
                 if path.exists() && !overwrite_if_exists {
                     return Err(Error::FileAlreadyExists(path));
                 }
-                let file = File::create(&tmp_path).map_err(|error| Error::IoError {
+                let file = create_file(tmp_path, *private).map_err(|error| Error::IoError {
                     context: format!("failed to create {}", tmp_path.display()),
                     error,
                 })?;
@@ -153,13 +159,127 @@ impl<'a> OutputKind<'a> {
             OutputKind::Stdout => Ok(()),
         }
     }
+
+    /// Writes `contents` to the file at `maybe_output_path`, or to `stdout` if empty.
+    pub(super) fn write_all(
+        maybe_output_path: &'a str,
+        contents: &[u8],
+        force: bool,
+    ) -> Result<()> {
+        Self::write_all_to(maybe_output_path, contents, force, false)
+    }
+
+    /// As [`write_all`](Self::write_all), but only the owner may read or write the file, as is
+    /// required for secret keys.
+    pub(super) fn write_all_private(
+        maybe_output_path: &'a str,
+        contents: &[u8],
+        force: bool,
+    ) -> Result<()> {
+        Self::write_all_to(maybe_output_path, contents, force, true)
+    }
+
+    fn write_all_to(
+        maybe_output_path: &'a str,
+        contents: &[u8],
+        force: bool,
+        private: bool,
+    ) -> Result<()> {
+        let output = if maybe_output_path.is_empty() {
+            OutputKind::Stdout
+        } else {
+            OutputKind::new_file(maybe_output_path, force, private)
+        };
+        output
+            .get()?
+            .write_all(contents)
+            .map_err(|error| Error::IoError {
+                context: "unable to write output".to_string(),
+                error,
+            })?;
+        output.commit()
+    }
+}
+
+/// Creates the file at `path` for writing, truncating it if it exists.
+///
+/// If `private` is true, only the owner may read or write the file, including when it already
+/// existed with wider permissions.  This has no effect on platforms other than Unix.
+fn create_file(path: &Path, private: bool) -> io::Result<File> {
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    if private {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let file = options.open(path)?;
+    #[cfg(unix)]
+    if private {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+    Ok(file)
+}
+
+/// Writes `contents` to the file at `path`, ensuring only the owner can read or write it.
+pub(crate) fn write_private_file(path: &Path, contents: &[u8]) -> io::Result<()> {
+    create_file(path, true)?.write_all(contents)
+}
+
+/// The encoding used when writing a `Deploy` to a file or stdout.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum DeployFormat {
+    /// Pretty-printed JSON.
+    Json,
+    /// The canonical `ToBytes` encoding, as used by the node and other Casper SDKs.
+    Binary,
+    /// The canonical `ToBytes` encoding, base64-encoded.
+    Base64,
+}
+
+impl DeployFormat {
+    pub(crate) const JSON: &'static str = "json";
+    pub(crate) const BINARY: &'static str = "binary";
+    pub(crate) const BASE64: &'static str = "base64";
+
+    /// Returns the extension used for files holding a `Deploy` in this format.
+    pub(crate) fn file_extension(self) -> &'static str {
+        match self {
+            DeployFormat::Json => "json",
+            DeployFormat::Binary => "bin",
+            DeployFormat::Base64 => "b64",
+        }
+    }
+
+    /// Infers the format of a serialized `Deploy`.
+    ///
+    /// JSON is assumed if the first non-whitespace byte is `{` and the input is valid UTF-8, and
+    /// base64 if every byte is part of the base64 alphabet or whitespace.  Anything else is treated
+    /// as binary.
+    ///
+    /// Binary input isn't assumed to be JSON just because it starts with `{`, since it is
+    /// practically never valid UTF-8.
+    pub(crate) fn detect(bytes: &[u8]) -> Self {
+        let is_base64_byte = |byte: &u8| {
+            byte.is_ascii_alphanumeric()
+                || byte.is_ascii_whitespace()
+                || matches!(byte, b'+' | b'/' | b'=')
+        };
+        match bytes.iter().find(|byte| !byte.is_ascii_whitespace()) {
+            None => DeployFormat::Json,
+            Some(b'{') if str::from_utf8(bytes).is_ok() => DeployFormat::Json,
+            Some(_) if bytes.iter().all(is_base64_byte) => DeployFormat::Base64,
+            Some(_) => DeployFormat::Binary,
+        }
+    }
 }
 
 /// `DeployParams` are used as a helper to construct a `Deploy` with
 /// `DeployExt::with_payment_and_session`.
 pub struct DeployParams {
-    /// The secret key for this `Deploy`.
-    pub secret_key: SecretKey,
+    /// The signer of this `Deploy`.
+    pub signer: Box<dyn Signer>,
 
     /// The creation timestamp of this `Deploy`.
     pub timestamp: Timestamp,
@@ -178,6 +298,9 @@ pub struct DeployParams {
 
     /// Optional public key of the account creating the Deploy.
     pub session_account: Option<PublicKey>,
+
+    /// The limits of the network this `Deploy` is intended for.
+    pub deploy_limits: DeployLimits,
 }
 
 /// An extension trait that adds some client-specific functionality to `Deploy`.
@@ -199,18 +322,26 @@ pub(super) trait DeployExt {
         payment: ExecutableDeployItem,
     ) -> Result<Deploy>;
 
-    /// Writes the `Deploy` to `output`.
-    fn write_deploy<W>(&self, output: W) -> Result<()>
+    /// Writes the `Deploy` to `output` using the given `format`.
+    fn write_deploy<W>(&self, output: W, format: DeployFormat) -> Result<()>
     where
         W: Write;
 
-    /// Reads a `Deploy` from the `input`.
-    fn read_deploy<R>(input: R) -> Result<Deploy>
+    /// Reads a `Deploy` from the `input`, detecting whether it is encoded as JSON, binary or
+    /// base64, and checks it against `deploy_limits`.
+    fn read_deploy<R>(input: R, deploy_limits: &DeployLimits) -> Result<Deploy>
     where
         R: Read;
 
-    /// Reads a `Deploy` from the reader at `input`, signs it, then writes it back to `output`.
-    fn sign_and_write_deploy<R, W>(input: R, secret_key: SecretKey, output: W) -> Result<()>
+    /// Reads a `Deploy` from the reader at `input`, signs it using `signer`, then writes it back to
+    /// `output` using the given `format`.
+    fn sign_and_write_deploy<R, W>(
+        input: R,
+        signer: &dyn Signer,
+        output: W,
+        format: DeployFormat,
+        deploy_limits: &DeployLimits,
+    ) -> Result<()>
     where
         R: Read,
         W: Write;
@@ -228,22 +359,30 @@ impl DeployExt for Deploy {
             gas_price,
             dependencies,
             chain_name,
-            secret_key,
+            signer,
             session_account,
+            deploy_limits,
         } = params;
 
-        let deploy = Deploy::new(
-            timestamp,
-            ttl,
-            gas_price,
-            dependencies,
-            chain_name,
-            payment,
-            session,
-            &secret_key,
-            session_account,
-        );
-        deploy.is_valid_size(MAX_SERIALIZED_SIZE)?;
+        let account = match session_account {
+            Some(account) => account,
+            None => signer.public_key()?,
+        };
+        let deploy = signer::unsigned(|ephemeral_key| {
+            Deploy::new(
+                timestamp,
+                ttl,
+                gas_price,
+                dependencies,
+                chain_name,
+                payment,
+                session,
+                ephemeral_key,
+                Some(account),
+            )
+        })?;
+        let deploy = signer::sign(&deploy, signer.as_ref())?;
+        deploy_limits.validate(&deploy)?;
         Ok(deploy)
     }
 
@@ -260,10 +399,7 @@ impl DeployExt for Deploy {
         const TRANSFER_ARG_TARGET: &str = "target";
         const TRANSFER_ARG_ID: &str = "id";
 
-        let amount = U512::from_dec_str(amount).map_err(|err| Error::FailedToParseUint {
-            context: TRANSFER_ARG_AMOUNT,
-            error: UIntParseError::FromDecStr(err),
-        })?;
+        let amount = units::parse_motes(TRANSFER_ARG_AMOUNT, amount)?;
 
         let mut transfer_args = RuntimeArgs::new();
         transfer_args.insert(TRANSFER_ARG_AMOUNT, amount)?;
@@ -299,38 +435,65 @@ impl DeployExt for Deploy {
         Deploy::with_payment_and_session(params, payment, session)
     }
 
-    fn write_deploy<W>(&self, mut output: W) -> Result<()>
+    fn write_deploy<W>(&self, mut output: W, format: DeployFormat) -> Result<()>
     where
         W: Write,
     {
-        let content = serde_json::to_string_pretty(self)?;
-        output
-            .write_all(content.as_bytes())
-            .map_err(|error| Error::IoError {
-                context: "unable to write deploy".to_owned(),
-                error,
-            })
+        let content = match format {
+            DeployFormat::Json => serde_json::to_string_pretty(self)?.into_bytes(),
+            DeployFormat::Binary => self.to_bytes()?,
+            DeployFormat::Base64 => base64::encode(self.to_bytes()?).into_bytes(),
+        };
+        output.write_all(&content).map_err(|error| Error::IoError {
+            context: "unable to write deploy".to_owned(),
+            error,
+        })
     }
 
-    fn read_deploy<R>(input: R) -> Result<Deploy>
+    fn read_deploy<R>(input: R, deploy_limits: &DeployLimits) -> Result<Deploy>
     where
         R: Read,
     {
-        let reader = BufReader::new(input);
-        let deploy: Deploy = serde_json::from_reader(reader)?;
-        deploy.is_valid_size(MAX_SERIALIZED_SIZE)?;
+        let mut bytes = Vec::new();
+        BufReader::new(input)
+            .read_to_end(&mut bytes)
+            .map_err(|error| Error::IoError {
+                context: "unable to read deploy".to_owned(),
+                error,
+            })?;
+        let deploy: Deploy = match DeployFormat::detect(&bytes) {
+            DeployFormat::Json => serde_json::from_slice(&bytes)?,
+            DeployFormat::Binary => bytesrepr::deserialize(bytes)?,
+            DeployFormat::Base64 => {
+                let text = String::from_utf8_lossy(&bytes);
+                let decoded = base64::decode(text.split_whitespace().collect::<String>()).map_err(
+                    |error| Error::InvalidArgument {
+                        context: "deploy",
+                        error: format!("failed to decode base64: {}", error),
+                    },
+                )?;
+                bytesrepr::deserialize(decoded)?
+            }
+        };
+        deploy_limits.validate(&deploy)?;
         Ok(deploy)
     }
 
-    fn sign_and_write_deploy<R, W>(input: R, secret_key: SecretKey, output: W) -> Result<()>
+    fn sign_and_write_deploy<R, W>(
+        input: R,
+        signer: &dyn Signer,
+        output: W,
+        format: DeployFormat,
+        deploy_limits: &DeployLimits,
+    ) -> Result<()>
     where
         R: Read,
         W: Write,
     {
-        let mut deploy = Deploy::read_deploy(input)?;
-        deploy.sign(&secret_key);
-        deploy.is_valid_size(MAX_SERIALIZED_SIZE)?;
-        deploy.write_deploy(output)?;
+        let deploy = Deploy::read_deploy(input, deploy_limits)?;
+        let deploy = signer::sign(&deploy, signer)?;
+        deploy_limits.validate(&deploy)?;
+        deploy.write_deploy(output, format)?;
         Ok(())
     }
 }
@@ -340,9 +503,10 @@ mod tests {
     use std::convert::TryInto;
 
     use casper_node::{crypto::AsymmetricKeyExt, types::ExcessiveSizeDeployError};
+    use casper_types::SecretKey;
 
     use super::*;
-    use crate::{DeployStrParams, PaymentStrParams, SessionStrParams};
+    use crate::{signer::PemSigner, DeployStrParams, PaymentStrParams, SessionStrParams};
 
     const PKG_HASH: &str = "09dcee4b212cfd53642ab323fbef07dafafc6f945a80a00147f62910a915c4e6";
     const ENTRYPOINT: &str = "entrypoint";
@@ -463,7 +627,9 @@ mod tests {
             session_params.try_into().unwrap(),
         )
         .unwrap();
-        deploy.write_deploy(&mut output).unwrap();
+        deploy
+            .write_deploy(&mut output, DeployFormat::Json)
+            .unwrap();
 
         // The test output can be used to generate data for SAMPLE_DEPLOY:
         // let secret_key = SecretKey::generate_ed25519().unwrap();
@@ -472,8 +638,9 @@ mod tests {
 
         let result = String::from_utf8(output).unwrap();
 
-        let expected = Deploy::read_deploy(SAMPLE_DEPLOY.as_bytes()).unwrap();
-        let actual = Deploy::read_deploy(result.as_bytes()).unwrap();
+        let expected =
+            Deploy::read_deploy(SAMPLE_DEPLOY.as_bytes(), &DeployLimits::default()).unwrap();
+        let actual = Deploy::read_deploy(result.as_bytes(), &DeployLimits::default()).unwrap();
 
         assert_eq!(expected.header().account(), actual.header().account());
         assert_eq!(expected.header().ttl(), actual.header().ttl());
@@ -490,7 +657,7 @@ mod tests {
             PaymentStrParams::with_package_hash(PKG_HASH, VERSION, ENTRYPOINT, args_simple(), "");
         // Create a string arg of 1048576 letter 'a's to ensure the deploy is greater than 1048576
         // bytes.
-        let large_args_simple = format!("name_01:string='{:a<1048576}'", "");
+        let large_args_simple = format!("name_01:string='{}'", "a".repeat(1_048_576));
 
         let session_params = SessionStrParams::with_package_hash(
             PKG_HASH,
@@ -509,8 +676,9 @@ mod tests {
                 max_deploy_size,
                 actual_deploy_size,
             })) => {
-                assert_eq!(max_deploy_size, MAX_SERIALIZED_SIZE);
-                assert!(actual_deploy_size > MAX_SERIALIZED_SIZE as usize);
+                let expected_max_deploy_size = DeployLimits::default().max_deploy_size;
+                assert_eq!(max_deploy_size, expected_max_deploy_size);
+                assert!(actual_deploy_size > expected_max_deploy_size as usize);
             }
             Err(error) => panic!("unexpected error: {}", error),
             Ok(_) => panic!("failed to error while creating an excessively large deploy"),
@@ -520,13 +688,65 @@ mod tests {
     #[test]
     fn should_read_deploy() {
         let bytes = SAMPLE_DEPLOY.as_bytes();
-        assert!(matches!(Deploy::read_deploy(bytes), Ok(_)));
+        assert!(Deploy::read_deploy(bytes, &DeployLimits::default()).is_ok());
+    }
+
+    #[test]
+    fn should_round_trip_deploy_in_all_formats() {
+        let deploy =
+            Deploy::read_deploy(SAMPLE_DEPLOY.as_bytes(), &DeployLimits::default()).unwrap();
+
+        for format in &[
+            DeployFormat::Json,
+            DeployFormat::Binary,
+            DeployFormat::Base64,
+        ] {
+            let mut output = Vec::new();
+            deploy.write_deploy(&mut output, *format).unwrap();
+            assert_eq!(DeployFormat::detect(&output), *format);
+
+            let read_back = Deploy::read_deploy(&output[..], &DeployLimits::default()).unwrap();
+            assert_eq!(deploy, read_back, "failed to round trip {:?}", format);
+        }
+    }
+
+    #[test]
+    fn should_detect_binary_starting_with_brace() {
+        let deploy =
+            Deploy::read_deploy(SAMPLE_DEPLOY.as_bytes(), &DeployLimits::default()).unwrap();
+        let mut output = Vec::new();
+        deploy
+            .write_deploy(&mut output, DeployFormat::Binary)
+            .unwrap();
+        output[0] = b'{';
+        assert_eq!(DeployFormat::detect(&output), DeployFormat::Binary);
+        assert!(matches!(
+            Deploy::read_deploy(&output[..], &DeployLimits::default()),
+            Err(Error::ToBytesError(_))
+        ));
+
+        // Invalid JSON is still reported as such.
+        assert!(matches!(
+            Deploy::read_deploy(&b"{\"hash\": "[..], &DeployLimits::default()),
+            Err(Error::InvalidJson(_))
+        ));
+    }
+
+    #[test]
+    fn should_write_binary_deploy_as_canonical_bytes() {
+        let deploy =
+            Deploy::read_deploy(SAMPLE_DEPLOY.as_bytes(), &DeployLimits::default()).unwrap();
+        let mut output = Vec::new();
+        deploy
+            .write_deploy(&mut output, DeployFormat::Binary)
+            .unwrap();
+        assert_eq!(output, deploy.to_bytes().unwrap());
     }
 
     #[test]
     fn should_sign_deploy() {
         let bytes = SAMPLE_DEPLOY.as_bytes();
-        let mut deploy = Deploy::read_deploy(bytes).unwrap();
+        let mut deploy = Deploy::read_deploy(bytes, &DeployLimits::default()).unwrap();
         deploy
             .is_valid()
             .unwrap_or_else(|error| panic!("{} - {:#?}", error, deploy));
@@ -537,9 +757,16 @@ mod tests {
         );
 
         let mut result = Vec::new();
-        let secret_key = SecretKey::generate_ed25519().unwrap();
-        Deploy::sign_and_write_deploy(bytes, secret_key, &mut result).unwrap();
-        let signed_deploy = Deploy::read_deploy(&result[..]).unwrap();
+        let signer = PemSigner::new(SecretKey::generate_ed25519().unwrap());
+        Deploy::sign_and_write_deploy(
+            bytes,
+            &signer,
+            &mut result,
+            DeployFormat::Json,
+            &DeployLimits::default(),
+        )
+        .unwrap();
+        let signed_deploy = Deploy::read_deploy(&result[..], &DeployLimits::default()).unwrap();
 
         assert_eq!(
             signed_deploy.approvals().len(),
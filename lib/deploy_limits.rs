@@ -0,0 +1,243 @@
+use std::{fs, time::Duration};
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use casper_node::types::{Deploy, TimeDiff};
+use casper_types::{bytesrepr::ToBytes, U512};
+
+use crate::error::{Error, Result};
+
+/// The name of the payment arg which specifies the amount of motes paid for executing a `Deploy`.
+const ARG_AMOUNT: &str = "amount";
+
+/// The limits applied by a network to the `Deploy`s it will accept.
+///
+/// These mirror the `[deploys]` section of the network's chainspec.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DeployLimits {
+    /// The maximum permissible size in bytes of a `Deploy` when serialized via `ToBytes`.
+    pub(crate) max_deploy_size: u32,
+    /// The maximum permissible time to live of a `Deploy`.
+    pub(crate) max_ttl: TimeDiff,
+    /// The maximum number of dependencies a `Deploy` may declare.
+    pub(crate) max_dependencies: u8,
+    /// The maximum amount of gas which may be used by all `Deploy`s in a single block, and hence
+    /// the maximum gas which may be bought by the payment of a single `Deploy`.
+    pub(crate) block_gas_limit: u64,
+    /// The maximum permissible size in bytes of the serialized payment args.
+    pub(crate) payment_args_max_length: u32,
+}
+
+impl DeployLimits {
+    /// The name of the preset holding the Casper Mainnet limits.
+    pub(crate) const MAINNET: &'static str = "mainnet";
+
+    /// Returns the limits for the given named network preset, if it exists.
+    pub(crate) fn preset(name: &str) -> Option<Self> {
+        match name {
+            Self::MAINNET => Some(DeployLimits {
+                max_deploy_size: 1_024 * 1_024,
+                max_ttl: TimeDiff::from(Duration::from_secs(24 * 60 * 60)),
+                max_dependencies: 10,
+                block_gas_limit: 10_000_000_000_000,
+                payment_args_max_length: 1_024,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Reads the limits from the `[deploys]` section of the chainspec TOML file at `path`.
+    pub(crate) fn from_chainspec_file(path: &str) -> Result<Self> {
+        let contents = fs::read_to_string(path).map_err(|error| Error::IoError {
+            context: format!("unable to read chainspec file at '{}'", path),
+            error,
+        })?;
+        Self::from_chainspec_toml(&contents).map_err(|error| Error::FailedToParseChainspec {
+            context: path.to_string(),
+            error,
+        })
+    }
+
+    fn from_chainspec_toml(contents: &str) -> std::result::Result<Self, toml::de::Error> {
+        let chainspec: ChainspecToml = toml::from_str(contents)?;
+        let deploys = chainspec.deploys;
+        Ok(DeployLimits {
+            max_deploy_size: deploys.max_deploy_size,
+            max_ttl: deploys.max_ttl,
+            max_dependencies: deploys.max_dependencies,
+            block_gas_limit: deploys.block_gas_limit,
+            payment_args_max_length: deploys.payment_args_max_length,
+        })
+    }
+
+    /// Checks that `deploy` satisfies all of these limits.
+    pub(crate) fn validate(&self, deploy: &Deploy) -> Result<()> {
+        deploy.is_valid_size(self.max_deploy_size)?;
+
+        let header = deploy.header();
+        if header.ttl() > self.max_ttl {
+            return Err(DeployLimitError::ExcessiveTimeToLive {
+                max_ttl: self.max_ttl,
+                got: header.ttl(),
+            }
+            .into());
+        }
+
+        if header.dependencies().len() > self.max_dependencies as usize {
+            return Err(DeployLimitError::ExcessiveDependencies {
+                max_dependencies: self.max_dependencies,
+                got: header.dependencies().len(),
+            }
+            .into());
+        }
+
+        // Transfers have a fixed cost, so only check the payment amount for other deploys.  The
+        // amount is in motes, which buy gas at the deploy's gas price.
+        if !deploy.session().is_transfer() {
+            let maybe_amount = deploy
+                .payment()
+                .args()
+                .get(ARG_AMOUNT)
+                .and_then(|value| value.clone().into_t::<U512>().ok());
+            let gas_price = header.gas_price();
+            if let Some(amount) = maybe_amount {
+                let maybe_gas = amount.checked_div(U512::from(gas_price));
+                if let Some(gas) = maybe_gas.filter(|gas| *gas > U512::from(self.block_gas_limit)) {
+                    return Err(DeployLimitError::ExceededBlockGasLimit {
+                        block_gas_limit: self.block_gas_limit,
+                        gas_price,
+                        got: gas,
+                    }
+                    .into());
+                }
+            }
+        }
+
+        let payment_args_length = deploy.payment().args().serialized_length();
+        if payment_args_length > self.payment_args_max_length as usize {
+            return Err(DeployLimitError::ExcessivePaymentArgsLength {
+                max_length: self.payment_args_max_length,
+                got: payment_args_length,
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for DeployLimits {
+    fn default() -> Self {
+        Self::preset(Self::MAINNET).expect("mainnet preset should exist")
+    }
+}
+
+/// The subset of a chainspec TOML file which is relevant to the client.
+#[derive(Deserialize)]
+struct ChainspecToml {
+    deploys: DeploysToml,
+}
+
+/// The relevant fields of the `[deploys]` section of a chainspec TOML file.
+#[derive(Deserialize)]
+struct DeploysToml {
+    max_deploy_size: u32,
+    max_ttl: TimeDiff,
+    max_dependencies: u8,
+    block_gas_limit: u64,
+    payment_args_max_length: u32,
+}
+
+/// Error returned when a `Deploy` violates the limits of the network it is intended for.
+#[derive(Error, Debug)]
+pub enum DeployLimitError {
+    /// The time to live exceeds the maximum.
+    #[error("time-to-live of {got} exceeds the maximum of {max_ttl}")]
+    ExcessiveTimeToLive {
+        /// The maximum permissible time to live.
+        max_ttl: TimeDiff,
+        /// The time to live of the `Deploy`.
+        got: TimeDiff,
+    },
+
+    /// Too many dependencies.
+    #[error("{got} dependencies exceeds the maximum of {max_dependencies}")]
+    ExcessiveDependencies {
+        /// The maximum number of dependencies.
+        max_dependencies: u8,
+        /// The number of dependencies of the `Deploy`.
+        got: usize,
+    },
+
+    /// The gas bought by the payment amount exceeds the block gas limit.
+    #[error(
+        "payment amount at a gas price of {gas_price} buys {got} gas, which exceeds the block gas \
+        limit of {block_gas_limit}"
+    )]
+    ExceededBlockGasLimit {
+        /// The block gas limit.
+        block_gas_limit: u64,
+        /// The gas price of the `Deploy`.
+        gas_price: u64,
+        /// The gas bought by the payment amount.
+        got: U512,
+    },
+
+    /// The serialized payment args are too long.
+    #[error("payment args length of {got} bytes exceeds the maximum of {max_length}")]
+    ExcessivePaymentArgsLength {
+        /// The maximum permissible length.
+        max_length: u32,
+        /// The serialized length of the payment args of the `Deploy`.
+        got: usize,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHAINSPEC: &str = r#"
+[protocol]
+version = '1.0.0'
+
+[deploys]
+max_payment_cost = '0'
+max_ttl = '2hours'
+max_dependencies = 3
+max_block_size = 10_485_760
+max_deploy_size = 524_288
+block_max_deploy_count = 50
+block_max_transfer_count = 1250
+block_max_approval_count = 2600
+block_gas_limit = 5_000_000_000
+payment_args_max_length = 512
+session_args_max_length = 1024
+native_transfer_minimum_motes = 2_500_000_000
+"#;
+
+    #[test]
+    fn should_parse_chainspec() {
+        let limits = DeployLimits::from_chainspec_toml(CHAINSPEC).unwrap();
+        assert_eq!(limits.max_deploy_size, 524_288);
+        assert_eq!(
+            limits.max_ttl,
+            TimeDiff::from(Duration::from_secs(2 * 60 * 60))
+        );
+        assert_eq!(limits.max_dependencies, 3);
+        assert_eq!(limits.block_gas_limit, 5_000_000_000);
+        assert_eq!(limits.payment_args_max_length, 512);
+    }
+
+    #[test]
+    fn should_fail_to_parse_chainspec_without_deploys_section() {
+        assert!(DeployLimits::from_chainspec_toml("[protocol]\nversion = '1.0.0'").is_err());
+    }
+
+    #[test]
+    fn should_have_presets() {
+        assert!(DeployLimits::preset(DeployLimits::MAINNET).is_some());
+        assert!(DeployLimits::preset("not a network").is_none());
+    }
+}
@@ -0,0 +1,505 @@
+//! Checks the args of a call to a stored contract against the parameters of its entry point.
+
+use std::convert::TryFrom;
+
+use casper_execution_engine::core::engine_state::ExecutableDeployItem;
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{FromBytes, ToBytes},
+    CLType, CLValue, Contract, ContractHash, ContractPackage, EntryPoint, Key, RuntimeArgs,
+    StoredValue, U512,
+};
+
+use crate::{
+    error::{Error, Result},
+    rpc::RpcCall,
+    validation, GlobalStateStrParams,
+};
+
+const STATE_ROOT_HASH: &str = "state_root_hash";
+
+/// The node against which the stored contract is looked up.
+pub(crate) struct Node<'a> {
+    pub(crate) maybe_rpc_id: &'a str,
+    pub(crate) node_address: &'a str,
+    pub(crate) verbosity_level: u64,
+}
+
+impl<'a> Node<'a> {
+    fn rpc_call(&self) -> RpcCall {
+        RpcCall::new(self.maybe_rpc_id, self.node_address, self.verbosity_level)
+    }
+
+    async fn state_root_hash(&self) -> Result<String> {
+        let response = self.rpc_call().get_state_root_hash("").await?;
+        match response
+            .get_result()
+            .and_then(|result| result.get(STATE_ROOT_HASH))
+            .and_then(|hash| hash.as_str())
+        {
+            Some(hash) => Ok(hash.to_string()),
+            None => Err(Error::InvalidRpcResponse(response)),
+        }
+    }
+
+    async fn query(&self, state_root_hash: &str, key: Key, path: &str) -> Result<StoredValue> {
+        let global_state_str_params = GlobalStateStrParams {
+            is_block_hash: false,
+            hash_value: state_root_hash,
+        };
+        let response = self
+            .rpc_call()
+            .query_global_state(global_state_str_params, &key.to_formatted_string(), path)
+            .await?;
+        Ok(validation::proven_stored_value(&response)?)
+    }
+}
+
+/// Looks up the stored contract called by `session` as of the node's latest state, and checks
+/// `session`'s args against the parameters of the called entry point.
+///
+/// Returns `session` with any args whose values are compatible with, but not of, their parameter's
+/// type converted to that type.  Contracts and packages called by name are looked up in the named
+/// keys of `account`.
+pub(crate) async fn check_session(
+    node: Node<'_>,
+    account: AccountHash,
+    session: ExecutableDeployItem,
+) -> Result<ExecutableDeployItem> {
+    let state_root_hash = node.state_root_hash().await?;
+    let srh = state_root_hash.as_str();
+    match session {
+        ExecutableDeployItem::StoredContractByHash {
+            hash,
+            entry_point,
+            args,
+        } => {
+            let contract = contract(node.query(srh, Key::from(hash), "").await?, "session_hash")?;
+            let args = check_args(&contract, &entry_point, args)?;
+            Ok(ExecutableDeployItem::StoredContractByHash {
+                hash,
+                entry_point,
+                args,
+            })
+        }
+        ExecutableDeployItem::StoredContractByName {
+            name,
+            entry_point,
+            args,
+        } => {
+            let stored_value = node.query(srh, Key::Account(account), &name).await?;
+            let contract = contract(stored_value, "session_name")?;
+            let args = check_args(&contract, &entry_point, args)?;
+            Ok(ExecutableDeployItem::StoredContractByName {
+                name,
+                entry_point,
+                args,
+            })
+        }
+        ExecutableDeployItem::StoredVersionedContractByHash {
+            hash,
+            version,
+            entry_point,
+            args,
+        } => {
+            let stored_value = node.query(srh, Key::from(hash), "").await?;
+            let contract_hash = contract_hash(stored_value, version, "session_package_hash")?;
+            let stored_value = node.query(srh, Key::from(contract_hash), "").await?;
+            let contract = contract(stored_value, "session_package_hash")?;
+            let args = check_args(&contract, &entry_point, args)?;
+            Ok(ExecutableDeployItem::StoredVersionedContractByHash {
+                hash,
+                version,
+                entry_point,
+                args,
+            })
+        }
+        ExecutableDeployItem::StoredVersionedContractByName {
+            name,
+            version,
+            entry_point,
+            args,
+        } => {
+            let stored_value = node.query(srh, Key::Account(account), &name).await?;
+            let contract_hash = contract_hash(stored_value, version, "session_package_name")?;
+            let stored_value = node.query(srh, Key::from(contract_hash), "").await?;
+            let contract = contract(stored_value, "session_package_name")?;
+            let args = check_args(&contract, &entry_point, args)?;
+            Ok(ExecutableDeployItem::StoredVersionedContractByName {
+                name,
+                version,
+                entry_point,
+                args,
+            })
+        }
+        ExecutableDeployItem::ModuleBytes { .. } | ExecutableDeployItem::Transfer { .. } => {
+            Err(Error::InvalidArgument {
+                context: "check_entry_point",
+                error: "requires the session to be a stored contract or contract package"
+                    .to_string(),
+            })
+        }
+    }
+}
+
+fn contract(stored_value: StoredValue, context: &'static str) -> Result<Contract> {
+    match stored_value {
+        StoredValue::Contract(contract) => Ok(contract),
+        other => Err(Error::InvalidArgument {
+            context,
+            error: format!("expected a contract, but found {}", other.type_name()),
+        }),
+    }
+}
+
+/// Returns the hash of the given version of the package, or of its highest enabled version if
+/// `version` is `None`, as the node does when executing the deploy.
+fn contract_hash(
+    stored_value: StoredValue,
+    version: Option<u32>,
+    context: &'static str,
+) -> Result<ContractHash> {
+    let package = match stored_value {
+        StoredValue::ContractPackage(package) => package,
+        other => {
+            return Err(Error::InvalidArgument {
+                context,
+                error: format!(
+                    "expected a contract package, but found {}",
+                    other.type_name()
+                ),
+            })
+        }
+    };
+    let maybe_contract_hash = match version {
+        Some(version) => enabled_version(&package, version),
+        None => package.current_contract_hash(),
+    };
+    maybe_contract_hash.ok_or_else(|| Error::InvalidArgument {
+        context,
+        error: match version {
+            Some(version) => format!("the package has no enabled version {}", version),
+            None => "the package has no enabled versions".to_string(),
+        },
+    })
+}
+
+fn enabled_version(package: &ContractPackage, version: u32) -> Option<ContractHash> {
+    package
+        .enabled_versions()
+        .into_iter()
+        .filter(|(key, _)| key.contract_version() == version)
+        .max_by_key(|(key, _)| *key)
+        .map(|(_, contract_hash)| contract_hash)
+}
+
+/// Checks `args` against the parameters of the contract's entry point named `entry_point_name`,
+/// returning them with any compatible values converted to their parameter's type.
+///
+/// All mismatches are collected into a single error.
+fn check_args(
+    contract: &Contract,
+    entry_point_name: &str,
+    args: RuntimeArgs,
+) -> Result<RuntimeArgs> {
+    let entry_point = contract.entry_point(entry_point_name).ok_or_else(|| {
+        let mut names: Vec<_> = contract.entry_points().keys().cloned().collect();
+        names.sort();
+        Error::EntryPointArgsMismatch {
+            entry_point: entry_point_name.to_string(),
+            problems: vec![format!(
+                "the contract has no such entry point; it has: {}",
+                names.join(", ")
+            )],
+        }
+    })?;
+    check_entry_point_args(entry_point, args)
+}
+
+fn check_entry_point_args(entry_point: &EntryPoint, args: RuntimeArgs) -> Result<RuntimeArgs> {
+    let mut problems = vec![];
+    let mut checked_args = RuntimeArgs::new();
+    for named_arg in args.named_args() {
+        let name = named_arg.name();
+        let value = named_arg.cl_value();
+        let parameter = match entry_point
+            .args()
+            .iter()
+            .find(|parameter| parameter.name() == name)
+        {
+            Some(parameter) => parameter,
+            None => {
+                problems.push(format!("unexpected arg '{}'", name));
+                continue;
+            }
+        };
+        match coerce(value, parameter.cl_type()) {
+            Some(value) => checked_args.insert_cl_value(name, value),
+            None => problems.push(format!(
+                "arg '{}' is a {:?} with no equivalent {:?}",
+                name,
+                value.cl_type(),
+                parameter.cl_type()
+            )),
+        }
+    }
+    for parameter in entry_point.args() {
+        if args.get(parameter.name()).is_none() {
+            problems.push(format!(
+                "missing arg '{}' of type {:?}",
+                parameter.name(),
+                parameter.cl_type()
+            ));
+        }
+    }
+
+    if problems.is_empty() {
+        Ok(checked_args)
+    } else {
+        Err(Error::EntryPointArgsMismatch {
+            entry_point: entry_point.name().to_string(),
+            problems,
+        })
+    }
+}
+
+/// Returns `value` as a `CLValue` of type `cl_type`, if it is either already of that type, or is an
+/// integer whose value is representable in the integer `cl_type`, or is such a value, or an
+/// `Option` of such a value, for the inner type of an `Option`.
+fn coerce(value: &CLValue, cl_type: &CLType) -> Option<CLValue> {
+    if value.cl_type() == cl_type {
+        return Some(value.clone());
+    }
+    if let CLType::Option(inner_type) = cl_type {
+        let inner_value = match value.cl_type() {
+            CLType::Option(value_inner_type) => match value.inner_bytes().split_first()? {
+                (0, _) => return Some(CLValue::from_components(cl_type.clone(), vec![0])),
+                (_, inner_bytes) => coerce(
+                    &CLValue::from_components(*value_inner_type.clone(), inner_bytes.to_vec()),
+                    inner_type,
+                )?,
+            },
+            _ => coerce(value, inner_type)?,
+        };
+        let mut bytes = vec![1];
+        bytes.extend_from_slice(inner_value.inner_bytes());
+        return Some(CLValue::from_components(cl_type.clone(), bytes));
+    }
+    integer(value).and_then(|integer| integer.to_cl_value(cl_type))
+}
+
+/// An integer read from a `CLValue` of any integer type.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Integer {
+    NonNegative(U512),
+    Negative(i64),
+}
+
+fn integer(value: &CLValue) -> Option<Integer> {
+    let signed = |signed: i64| {
+        if signed < 0 {
+            Integer::Negative(signed)
+        } else {
+            Integer::NonNegative(U512::from(signed as u64))
+        }
+    };
+    let integer = match value.cl_type() {
+        CLType::U8 => Integer::NonNegative(U512::from(value.clone().into_t::<u8>().ok()?)),
+        CLType::U32 => Integer::NonNegative(U512::from(value.clone().into_t::<u32>().ok()?)),
+        CLType::U64 => Integer::NonNegative(U512::from(value.clone().into_t::<u64>().ok()?)),
+        // `U128`, `U256` and `U512` share the same serialized form, which holds only the
+        // significant bytes of the value.
+        CLType::U128 | CLType::U256 | CLType::U512 => {
+            Integer::NonNegative(U512::from_bytes(value.inner_bytes()).ok()?.0)
+        }
+        CLType::I32 => signed(i64::from(value.clone().into_t::<i32>().ok()?)),
+        CLType::I64 => signed(value.clone().into_t::<i64>().ok()?),
+        _ => return None,
+    };
+    Some(integer)
+}
+
+impl Integer {
+    fn to_cl_value(self, cl_type: &CLType) -> Option<CLValue> {
+        let unsigned = match self {
+            Integer::NonNegative(unsigned) => unsigned,
+            Integer::Negative(signed) => {
+                return match cl_type {
+                    CLType::I32 => CLValue::from_t(i32::try_from(signed).ok()?).ok(),
+                    CLType::I64 => CLValue::from_t(signed).ok(),
+                    _ => None,
+                }
+            }
+        };
+        let as_u64 = || {
+            if unsigned <= U512::from(u64::MAX) {
+                Some(unsigned.as_u64())
+            } else {
+                None
+            }
+        };
+        let with_bits = |bits: usize| {
+            if unsigned.bits() <= bits {
+                Some(CLValue::from_components(
+                    cl_type.clone(),
+                    unsigned.to_bytes().ok()?,
+                ))
+            } else {
+                None
+            }
+        };
+        match cl_type {
+            CLType::U8 => CLValue::from_t(u8::try_from(as_u64()?).ok()?).ok(),
+            CLType::U32 => CLValue::from_t(u32::try_from(as_u64()?).ok()?).ok(),
+            CLType::U64 => CLValue::from_t(as_u64()?).ok(),
+            CLType::U128 => with_bits(128),
+            CLType::U256 => with_bits(256),
+            CLType::U512 => with_bits(512),
+            CLType::I32 => CLValue::from_t(i32::try_from(as_u64()?).ok()?).ok(),
+            CLType::I64 => CLValue::from_t(i64::try_from(as_u64()?).ok()?).ok(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{runtime_args, EntryPointAccess, EntryPointType, Parameter, U128, U256};
+
+    use super::*;
+
+    fn entry_point() -> EntryPoint {
+        EntryPoint::new(
+            "transfer",
+            vec![
+                Parameter::new("amount", CLType::U512),
+                Parameter::new("id", CLType::Option(Box::new(CLType::U64))),
+                Parameter::new("memo", CLType::String),
+            ],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        )
+    }
+
+    #[test]
+    fn should_accept_matching_args() {
+        let args = runtime_args! {
+            "amount" => U512::from(10),
+            "id" => Some(1_u64),
+            "memo" => "a memo",
+        };
+        assert_eq!(
+            check_entry_point_args(&entry_point(), args.clone()).unwrap(),
+            args
+        );
+    }
+
+    #[test]
+    fn should_coerce_compatible_args() {
+        let args = runtime_args! {
+            "amount" => 10_u64,
+            "id" => 1_u8,
+            "memo" => "a memo",
+        };
+        let expected = runtime_args! {
+            "amount" => U512::from(10),
+            "id" => Some(1_u64),
+            "memo" => "a memo",
+        };
+        assert_eq!(
+            check_entry_point_args(&entry_point(), args).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn should_report_all_mismatches() {
+        let args = runtime_args! {
+            "amout" => U512::from(10),
+            "id" => -1_i64,
+            "memo" => 5_u32,
+        };
+        match check_entry_point_args(&entry_point(), args) {
+            Err(Error::EntryPointArgsMismatch {
+                entry_point,
+                problems,
+            }) => {
+                assert_eq!(entry_point, "transfer");
+                assert_eq!(
+                    problems,
+                    vec![
+                        "unexpected arg 'amout'",
+                        "arg 'id' is a I64 with no equivalent Option(U64)",
+                        "arg 'memo' is a U32 with no equivalent String",
+                        "missing arg 'amount' of type U512",
+                    ]
+                );
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn should_coerce_integers_which_fit() {
+        let coerced = |value: CLValue, cl_type: CLType| coerce(&value, &cl_type);
+
+        let u512 = CLValue::from_t(U512::from(u64::MAX)).unwrap();
+        assert_eq!(
+            coerced(u512.clone(), CLType::U64),
+            Some(CLValue::from_t(u64::MAX).unwrap())
+        );
+        assert_eq!(coerced(u512.clone(), CLType::U32), None);
+        assert_eq!(coerced(u512.clone(), CLType::I64), None);
+        assert_eq!(
+            coerced(u512, CLType::U128),
+            Some(CLValue::from_t(U128::from(u64::MAX)).unwrap())
+        );
+
+        let u256_max = CLValue::from_t(U256::MAX).unwrap();
+        assert_eq!(coerced(u256_max.clone(), CLType::U128), None);
+        assert_eq!(
+            coerced(u256_max, CLType::U512),
+            Some(CLValue::from_components(
+                CLType::U512,
+                U256::MAX.to_bytes().unwrap()
+            ))
+        );
+
+        let negative = CLValue::from_t(-7_i64).unwrap();
+        assert_eq!(
+            coerced(negative.clone(), CLType::I32),
+            Some(CLValue::from_t(-7_i32).unwrap())
+        );
+        assert_eq!(coerced(negative, CLType::U512), None);
+
+        let string = CLValue::from_t("7".to_string()).unwrap();
+        assert_eq!(coerced(string, CLType::U64), None);
+    }
+
+    #[test]
+    fn should_coerce_integers_within_options() {
+        let coerced = |value: CLValue, cl_type: CLType| coerce(&value, &cl_type);
+        let option_u512 = CLType::Option(Box::new(CLType::U512));
+
+        assert_eq!(
+            coerced(CLValue::from_t(Some(7_u64)).unwrap(), option_u512.clone()),
+            Some(CLValue::from_t(Some(U512::from(7))).unwrap())
+        );
+        assert_eq!(
+            coerced(CLValue::from_t(Option::<u64>::None).unwrap(), option_u512),
+            Some(CLValue::from_t(Option::<U512>::None).unwrap())
+        );
+        assert_eq!(
+            coerced(
+                CLValue::from_t(Some(u64::MAX)).unwrap(),
+                CLType::Option(Box::new(CLType::U32))
+            ),
+            None
+        );
+        assert_eq!(
+            coerced(CLValue::from_t(Some(7_u64)).unwrap(), CLType::U512),
+            None
+        );
+    }
+}
@@ -9,7 +9,7 @@ use casper_types::{
     bytesrepr::Error as ToBytesError, CLValueError, UIntParseError, URefFromStrError,
 };
 
-use crate::validation::ValidateResponseError;
+use crate::{deploy_limits::DeployLimitError, validation::ValidateResponseError};
 
 /// Crate-wide Result type wrapper.
 pub(crate) type Result<T> = std::result::Result<T, Error>;
@@ -77,6 +77,74 @@ pub enum Error {
     #[error("Deploy size too large: {0}")]
     DeploySizeTooLarge(#[from] ExcessiveSizeDeployError),
 
+    /// Deploy violates the limits of the network it is intended for.
+    #[error("Deploy exceeds network limits: {0}")]
+    DeployLimitExceeded(#[from] DeployLimitError),
+
+    /// Failed to parse a chainspec file.
+    #[error("Failed to parse chainspec at '{context}': {error}")]
+    FailedToParseChainspec {
+        /// Contextual description of where this error occurred including relevant paths,
+        /// filenames, etc.
+        context: String,
+        /// The actual error raised.
+        error: toml::de::Error,
+    },
+
+    /// Failed to parse a manifest file.
+    #[error("Failed to parse manifest at '{context}': {error}")]
+    FailedToParseManifest {
+        /// Contextual description of where this error occurred including relevant paths,
+        /// filenames, etc.
+        context: String,
+        /// An error message.
+        error: String,
+    },
+
+    /// Failed to parse a CSV file, such as a batch transfer's transfers file or report.
+    #[error("Failed to parse CSV file at '{context}': {error}")]
+    FailedToParseCsv {
+        /// Contextual description of where this error occurred including relevant paths,
+        /// filenames, etc.
+        context: String,
+        /// An error message.
+        error: String,
+    },
+
+    /// Failed to parse a deploy template file.
+    #[error("Failed to parse template at '{context}': {error}")]
+    FailedToParseTemplate {
+        /// Contextual description of where this error occurred including relevant paths,
+        /// filenames, etc.
+        context: String,
+        /// An error message.
+        error: String,
+    },
+
+    /// A signer failed to produce a valid signature.
+    #[error("Signer '{context}' failed: {error}")]
+    SignerError {
+        /// Contextual description of where this error occurred, e.g. the path to the signing
+        /// program.
+        context: String,
+        /// An error message.
+        error: String,
+    },
+
+    /// Failed to encrypt or decrypt a keystore.
+    #[error("Keystore error at '{context}': {error}")]
+    KeystoreError {
+        /// Contextual description of where this error occurred including relevant paths,
+        /// filenames, etc.
+        context: String,
+        /// An error message.
+        error: String,
+    },
+
+    /// A signature failed verification.
+    #[error("Invalid signature: {0}")]
+    InvalidSignature(String),
+
     /// Failed to get a response from the node.
     #[error("Failed to get RPC response: {0}")]
     FailedToGetResponse(reqwest::Error),
@@ -137,6 +205,54 @@ pub enum Error {
     #[error("Invalid CLValue error: {0}")]
     InvalidCLValue(String),
 
+    /// Failed to parse a file of session or payment args.
+    #[error("Failed to parse args file at '{context}': {error}")]
+    FailedToParseArgsFile {
+        /// The path of the file, followed by the line at which the error occurred if known.
+        context: String,
+        /// An error message.
+        error: String,
+    },
+
+    /// Failed to decode the raw bytes of a complex session or payment arg.
+    #[error(
+        "Failed to decode complex arg '{name}' at index {index} as {cl_type}: {error} at byte \
+        offset {offset}"
+    )]
+    FailedToDecodeComplexArg {
+        /// The name of the arg.
+        name: String,
+        /// The index of the arg in the file.
+        index: usize,
+        /// The `CLType` the arg was expected to have, or "unknown CLType" if the type itself
+        /// failed to decode.
+        cl_type: String,
+        /// The offset into the arg's raw bytes at which decoding failed.
+        offset: usize,
+        /// The actual error raised.
+        error: ToBytesError,
+    },
+
+    /// The session args do not match the parameters of the stored contract's entry point.
+    #[error("Args don't match entry point '{entry_point}': {}", problems.join("; "))]
+    EntryPointArgsMismatch {
+        /// The name of the entry point.
+        entry_point: String,
+        /// A description of each mismatch found.
+        problems: Vec<String>,
+    },
+
+    /// Failed to parse a simple session or payment arg.
+    #[error("Invalid simple arg \"{arg}\" at character {position}: {error}")]
+    InvalidSimpleArg {
+        /// The simple arg as given, which may hold several args.
+        arg: String,
+        /// The 1-based position of the character at which the error was found.
+        position: usize,
+        /// An error message.
+        error: String,
+    },
+
     /// Invalid argument.
     #[error("Invalid argument '{context}': {error}")]
     InvalidArgument {
@@ -180,6 +296,218 @@ pub enum Error {
     FFIPtrNullButRequired(&'static str),
 }
 
+/// The broad category of an [`Error`], which determines the exit code of the `casper-client`
+/// binary when the error occurs.
+///
+/// | Exit code | Category                                                        |
+/// |-----------|-----------------------------------------------------------------|
+/// | 0         | Success                                                         |
+/// | 1         | [`Internal`](ErrorCategory::Internal)                           |
+/// | 2         | [`InvalidArgument`](ErrorCategory::InvalidArgument)             |
+/// | 3         | [`Io`](ErrorCategory::Io)                                       |
+/// | 4         | [`Network`](ErrorCategory::Network)                             |
+/// | 5         | [`Rpc`](ErrorCategory::Rpc)                                     |
+/// | 6         | [`InvalidResponse`](ErrorCategory::InvalidResponse)             |
+/// | 7         | [`Crypto`](ErrorCategory::Crypto)                               |
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ErrorCategory {
+    /// An unexpected failure within the client.
+    Internal,
+    /// An arg, or a file or value it refers to, is invalid.
+    InvalidArgument,
+    /// Failed to read or write a file.
+    Io,
+    /// Failed to reach the node, e.g. a connection failure or timeout.
+    Network,
+    /// The node returned a JSON-RPC error, e.g. the deploy already exists.
+    Rpc,
+    /// The node's response could not be parsed or failed validation.
+    InvalidResponse,
+    /// A cryptographic operation, signer or keystore failed, or a signature is invalid.
+    Crypto,
+}
+
+impl ErrorCategory {
+    /// Returns the process exit code used by the `casper-client` binary for this category.
+    pub fn exit_code(self) -> i32 {
+        match self {
+            ErrorCategory::Internal => 1,
+            ErrorCategory::InvalidArgument => 2,
+            ErrorCategory::Io => 3,
+            ErrorCategory::Network => 4,
+            ErrorCategory::Rpc => 5,
+            ErrorCategory::InvalidResponse => 6,
+            ErrorCategory::Crypto => 7,
+        }
+    }
+}
+
+/// Generates both `Error::code()` and the conversion to the FFI `casper_error_t` from a single
+/// table, so that the string code is always the FFI variant's name without its `CASPER_` prefix.
+macro_rules! error_codes {
+    ($($(#[$meta:meta])* $pattern:pat => $ffi_code:ident,)*) => {
+        impl Error {
+            /// Returns a stable string identifying the kind of error.
+            ///
+            /// This is the name of the corresponding FFI `casper_error_t` variant without its
+            /// `CASPER_` prefix, e.g. `"RESPONSE_IS_ERROR"`.
+            pub fn code(&self) -> &'static str {
+                match self {
+                    $($(#[$meta])* $pattern => &stringify!($ffi_code)["CASPER_".len()..],)*
+                }
+            }
+        }
+
+        #[cfg(feature = "ffi")]
+        impl crate::ffi::AsFFIError for Error {
+            fn as_ffi_error(&self) -> crate::ffi::casper_error_t {
+                match self {
+                    $($(#[$meta])* $pattern => crate::ffi::casper_error_t::$ffi_code,)*
+                }
+            }
+        }
+    };
+}
+
+error_codes! {
+    Error::FailedToParseKey => CASPER_FAILED_TO_PARSE_KEY,
+    Error::FailedToParseURef { .. } => CASPER_FAILED_TO_PARSE_UREF,
+    Error::FailedToParseInt { .. } => CASPER_FAILED_TO_PARSE_INT,
+    Error::FailedToParseTimeDiff { .. } => CASPER_FAILED_TO_PARSE_TIME_DIFF,
+    Error::FailedToParseTimestamp { .. } => CASPER_FAILED_TO_PARSE_TIMESTAMP,
+    Error::FailedToParseUint { .. } => CASPER_FAILED_TO_PARSE_UINT,
+    Error::FailedToGetResponse(_) => CASPER_FAILED_TO_GET_RESPONSE,
+    Error::FailedToParseResponse(_) => CASPER_FAILED_TO_PARSE_RESPONSE,
+    Error::FileAlreadyExists(_) => CASPER_FILE_ALREADY_EXISTS,
+    Error::UnsupportedAlgorithm(_) => CASPER_UNSUPPORTED_ALGORITHM,
+    Error::ResponseIsError(_) => CASPER_RESPONSE_IS_ERROR,
+    Error::InvalidJson(_) => CASPER_INVALID_JSON,
+    Error::InvalidRpcResponse(_) => CASPER_INVALID_RPC_RESPONSE,
+    Error::FailedSending(_) => CASPER_FAILED_SENDING,
+    Error::IoError { .. } => CASPER_IO_ERROR,
+    Error::ToBytesError(_) => CASPER_TO_BYTES_ERROR,
+    Error::CryptoError { .. } => CASPER_CRYPTO_ERROR,
+    Error::InvalidCLValue(_) => CASPER_INVALID_CL_VALUE,
+    Error::FailedToDecodeComplexArg { .. } => CASPER_FAILED_TO_DECODE_COMPLEX_ARG,
+    Error::FailedToParseArgsFile { .. } => CASPER_FAILED_TO_PARSE_ARGS_FILE,
+    Error::EntryPointArgsMismatch { .. } => CASPER_ENTRY_POINT_ARGS_MISMATCH,
+    Error::InvalidSimpleArg { .. } => CASPER_INVALID_SIMPLE_ARG,
+    Error::InvalidArgument { .. } => CASPER_INVALID_ARGUMENT,
+    Error::InvalidResponse(_) => CASPER_INVALID_RESPONSE,
+    #[cfg(feature = "ffi")]
+    Error::FFISetupNotCalled => CASPER_FFI_SETUP_NOT_CALLED,
+    #[cfg(feature = "ffi")]
+    Error::FFIPtrNullButRequired(_) => CASPER_FFI_PTR_NULL_BUT_REQUIRED,
+    Error::ConflictingArguments { .. } => CASPER_CONFLICTING_ARGUMENTS,
+    Error::DeploySizeTooLarge(_) => CASPER_DEPLOY_SIZE_TOO_LARGE,
+    // The FFI variant keeps its original name, as renaming it would break C callers.
+    Error::FailedToParseDictionaryIdentifier => CASPER_FAILED_TO_CREATE_DICTIONARY_IDENTIFIER,
+    Error::FailedToParseStateIdentifier => CASPER_FAILED_TO_PARSE_STATE_IDENTIFIER,
+    Error::DeployLimitExceeded(_) => CASPER_DEPLOY_LIMIT_EXCEEDED,
+    Error::FailedToParseChainspec { .. } => CASPER_FAILED_TO_PARSE_CHAINSPEC,
+    Error::FailedToParseManifest { .. } => CASPER_FAILED_TO_PARSE_MANIFEST,
+    Error::FailedToParseCsv { .. } => CASPER_FAILED_TO_PARSE_CSV,
+    Error::FailedToParseTemplate { .. } => CASPER_FAILED_TO_PARSE_TEMPLATE,
+    Error::SignerError { .. } => CASPER_SIGNER_ERROR,
+    Error::KeystoreError { .. } => CASPER_KEYSTORE_ERROR,
+    Error::InvalidSignature(_) => CASPER_INVALID_SIGNATURE,
+}
+
+impl Error {
+    /// Returns true if the error shows that the node didn't act on a request, i.e. the node
+    /// returned a JSON-RPC error, or no connection to it could be made.
+    ///
+    /// After any other error while sending a `Deploy`, such as a timeout, a dropped connection or
+    /// an invalid response, whether the node accepted the `Deploy` is unknown.
+    pub(crate) fn is_definite_rejection(&self) -> bool {
+        match self {
+            Error::ResponseIsError(_) => true,
+            Error::FailedToGetResponse(error) => error.is_connect(),
+            _ => false,
+        }
+    }
+
+    /// Returns the broad category of the error.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Error::FailedToParseKey
+            | Error::FailedToParseURef { .. }
+            | Error::FailedToParseInt { .. }
+            | Error::FailedToParseTimeDiff { .. }
+            | Error::FailedToParseTimestamp { .. }
+            | Error::FailedToParseUint { .. }
+            | Error::FileAlreadyExists(_)
+            | Error::UnsupportedAlgorithm(_)
+            | Error::InvalidCLValue(_)
+            | Error::FailedToDecodeComplexArg { .. }
+            | Error::FailedToParseArgsFile { .. }
+            | Error::EntryPointArgsMismatch { .. }
+            | Error::InvalidSimpleArg { .. }
+            | Error::InvalidArgument { .. }
+            | Error::ConflictingArguments { .. }
+            | Error::DeploySizeTooLarge(_)
+            | Error::FailedToParseDictionaryIdentifier
+            | Error::FailedToParseStateIdentifier
+            | Error::DeployLimitExceeded(_)
+            | Error::FailedToParseChainspec { .. }
+            | Error::FailedToParseManifest { .. }
+            | Error::FailedToParseCsv { .. }
+            | Error::FailedToParseTemplate { .. } => ErrorCategory::InvalidArgument,
+            Error::IoError { .. } => ErrorCategory::Io,
+            Error::FailedToGetResponse(_) | Error::FailedSending(_) => ErrorCategory::Network,
+            Error::ResponseIsError(_) => ErrorCategory::Rpc,
+            Error::FailedToParseResponse(_)
+            | Error::InvalidJson(_)
+            | Error::InvalidRpcResponse(_)
+            | Error::InvalidResponse(_) => ErrorCategory::InvalidResponse,
+            Error::CryptoError { .. }
+            | Error::SignerError { .. }
+            | Error::KeystoreError { .. }
+            | Error::InvalidSignature(_) => ErrorCategory::Crypto,
+            Error::ToBytesError(_) => ErrorCategory::Internal,
+            #[cfg(feature = "ffi")]
+            Error::FFISetupNotCalled | Error::FFIPtrNullButRequired(_) => ErrorCategory::Internal,
+        }
+    }
+
+    /// Returns the contextual description of where the error occurred, if the error has one.
+    pub fn context(&self) -> Option<String> {
+        match self {
+            Error::FailedToParseURef { context, .. }
+            | Error::FailedToParseInt { context, .. }
+            | Error::FailedToParseTimeDiff { context, .. }
+            | Error::FailedToParseTimestamp { context, .. }
+            | Error::FailedToParseUint { context, .. }
+            | Error::CryptoError { context, .. }
+            | Error::InvalidArgument { context, .. }
+            | Error::ConflictingArguments { context, .. } => Some(context.to_string()),
+            Error::FailedToParseChainspec { context, .. }
+            | Error::FailedToParseManifest { context, .. }
+            | Error::FailedToParseCsv { context, .. }
+            | Error::FailedToParseTemplate { context, .. }
+            | Error::FailedToParseArgsFile { context, .. }
+            | Error::SignerError { context, .. }
+            | Error::KeystoreError { context, .. }
+            | Error::IoError { context, .. } => Some(context.clone()),
+            Error::FailedToDecodeComplexArg { name, .. } => Some(name.clone()),
+            Error::EntryPointArgsMismatch { entry_point, .. } => Some(entry_point.clone()),
+            Error::InvalidSimpleArg { arg, .. } => Some(arg.clone()),
+            Error::FileAlreadyExists(path) => Some(path.display().to_string()),
+            #[cfg(feature = "ffi")]
+            Error::FFIPtrNullButRequired(context) => Some(context.to_string()),
+            _ => None,
+        }
+    }
+
+    /// Returns the code of the JSON-RPC error returned by the node, if this is such an error.
+    pub fn rpc_error_code(&self) -> Option<i64> {
+        match self {
+            Error::ResponseIsError(error) => Some(error.code),
+            _ => None,
+        }
+    }
+}
+
 impl From<ToBytesError> for Error {
     fn from(error: ToBytesError) -> Self {
         Error::ToBytesError(error)
@@ -194,3 +522,53 @@ impl From<CLValueError> for Error {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_describe_rpc_error() {
+        let error = Error::ResponseIsError(jsonrpc_lite::Error {
+            code: -32008,
+            message: "deploy already exists".to_string(),
+            data: None,
+        });
+        assert_eq!(error.code(), "RESPONSE_IS_ERROR");
+        assert_eq!(error.category(), ErrorCategory::Rpc);
+        assert_eq!(error.category().exit_code(), 5);
+        assert_eq!(error.context(), None);
+        assert_eq!(error.rpc_error_code(), Some(-32008));
+    }
+
+    #[test]
+    fn should_describe_contextual_errors() {
+        let error = Error::InvalidArgument {
+            context: "ttl",
+            error: "invalid".to_string(),
+        };
+        assert_eq!(error.code(), "INVALID_ARGUMENT");
+        assert_eq!(error.category(), ErrorCategory::InvalidArgument);
+        assert_eq!(error.context().as_deref(), Some("ttl"));
+        assert_eq!(error.rpc_error_code(), None);
+
+        let error = Error::IoError {
+            context: "unable to read 'file'".to_string(),
+            error: std::io::Error::from(std::io::ErrorKind::NotFound),
+        };
+        assert_eq!(error.code(), "IO_ERROR");
+        assert_eq!(error.category().exit_code(), 3);
+        assert_eq!(error.context().as_deref(), Some("unable to read 'file'"));
+    }
+
+    #[test]
+    fn should_use_ffi_error_name_as_code() {
+        let error = Error::FailedToParseDictionaryIdentifier;
+        assert_eq!(error.code(), "FAILED_TO_CREATE_DICTIONARY_IDENTIFIER");
+        #[cfg(feature = "ffi")]
+        assert!(matches!(
+            crate::ffi::AsFFIError::as_ffi_error(&error),
+            crate::ffi::casper_error_t::CASPER_FAILED_TO_CREATE_DICTIONARY_IDENTIFIER
+        ));
+    }
+}
@@ -55,51 +55,24 @@ pub enum casper_error_t {
     CASPER_DEPLOY_SIZE_TOO_LARGE = -24,
     CASPER_FAILED_TO_CREATE_DICTIONARY_IDENTIFIER = -25,
     CASPER_FAILED_TO_PARSE_STATE_IDENTIFIER = -26,
+    CASPER_DEPLOY_LIMIT_EXCEEDED = -27,
+    CASPER_FAILED_TO_PARSE_CHAINSPEC = -28,
+    CASPER_FAILED_TO_PARSE_MANIFEST = -29,
+    CASPER_FAILED_TO_PARSE_TEMPLATE = -30,
+    CASPER_SIGNER_ERROR = -31,
+    CASPER_KEYSTORE_ERROR = -32,
+    CASPER_INVALID_SIGNATURE = -33,
+    CASPER_FAILED_TO_DECODE_COMPLEX_ARG = -34,
+    CASPER_FAILED_TO_PARSE_ARGS_FILE = -35,
+    CASPER_ENTRY_POINT_ARGS_MISMATCH = -36,
+    CASPER_INVALID_SIMPLE_ARG = -37,
+    CASPER_FAILED_TO_PARSE_CSV = -38,
 }
 
-trait AsFFIError {
+pub(crate) trait AsFFIError {
     fn as_ffi_error(&self) -> casper_error_t;
 }
 
-impl AsFFIError for Error {
-    fn as_ffi_error(&self) -> casper_error_t {
-        match self {
-            Error::FailedToParseKey => casper_error_t::CASPER_FAILED_TO_PARSE_KEY,
-            Error::FailedToParseURef { .. } => casper_error_t::CASPER_FAILED_TO_PARSE_UREF,
-            Error::FailedToParseInt { .. } => casper_error_t::CASPER_FAILED_TO_PARSE_INT,
-            Error::FailedToParseTimeDiff { .. } => casper_error_t::CASPER_FAILED_TO_PARSE_TIME_DIFF,
-            Error::FailedToParseTimestamp { .. } => {
-                casper_error_t::CASPER_FAILED_TO_PARSE_TIMESTAMP
-            }
-            Error::FailedToParseUint { .. } => casper_error_t::CASPER_FAILED_TO_PARSE_UINT,
-            Error::FailedToGetResponse(_) => casper_error_t::CASPER_FAILED_TO_GET_RESPONSE,
-            Error::FailedToParseResponse(_) => casper_error_t::CASPER_FAILED_TO_PARSE_RESPONSE,
-            Error::FileAlreadyExists(_) => casper_error_t::CASPER_FILE_ALREADY_EXISTS,
-            Error::UnsupportedAlgorithm(_) => casper_error_t::CASPER_UNSUPPORTED_ALGORITHM,
-            Error::ResponseIsError(_) => casper_error_t::CASPER_RESPONSE_IS_ERROR,
-            Error::InvalidJson(_) => casper_error_t::CASPER_INVALID_JSON,
-            Error::InvalidRpcResponse(_) => casper_error_t::CASPER_INVALID_RPC_RESPONSE,
-            Error::FailedSending(_) => casper_error_t::CASPER_FAILED_SENDING,
-            Error::IoError { .. } => casper_error_t::CASPER_IO_ERROR,
-            Error::ToBytesError(_) => casper_error_t::CASPER_TO_BYTES_ERROR,
-            Error::CryptoError { .. } => casper_error_t::CASPER_CRYPTO_ERROR,
-            Error::InvalidCLValue(_) => casper_error_t::CASPER_INVALID_CL_VALUE,
-            Error::InvalidArgument { .. } => casper_error_t::CASPER_INVALID_ARGUMENT,
-            Error::InvalidResponse(_) => casper_error_t::CASPER_INVALID_RESPONSE,
-            Error::FFISetupNotCalled => casper_error_t::CASPER_FFI_SETUP_NOT_CALLED,
-            Error::FFIPtrNullButRequired(_) => casper_error_t::CASPER_FFI_PTR_NULL_BUT_REQUIRED,
-            Error::ConflictingArguments { .. } => casper_error_t::CASPER_CONFLICTING_ARGUMENTS,
-            Error::DeploySizeTooLarge(_) => casper_error_t::CASPER_DEPLOY_SIZE_TOO_LARGE,
-            Error::FailedToParseDictionaryIdentifier => {
-                casper_error_t::CASPER_FAILED_TO_CREATE_DICTIONARY_IDENTIFIER
-            }
-            Error::FailedToParseStateIdentifier => {
-                casper_error_t::CASPER_FAILED_TO_PARSE_STATE_IDENTIFIER
-            }
-        }
-    }
-}
-
 /// Private macro for parsing arguments from c strings, (const char *, or *const c_char in rust
 /// terms). The sad path contract here is that we indicate there was an error by returning `false`,
 /// then we store the argument -name- as an Error::InvalidArgument in LAST_ERROR. The happy path is
@@ -258,6 +231,22 @@ pub extern "C" fn casper_get_last_error(buf: *mut c_uchar, len: usize) -> usize
     0
 }
 
+/// Gets the stable code string of the last error copied to the provided buffer.
+///
+/// The code is the name of the `casper_error_t` value returned with the error, without its
+/// `CASPER_` prefix, e.g. "RESPONSE_IS_ERROR".
+///
+/// * `buf` is the buffer where the result will be stored.
+/// * `len` is the length of the `buf` buffer in bytes.
+/// - returns the number of bytes written to `buf`.
+#[no_mangle]
+pub extern "C" fn casper_get_last_error_code(buf: *mut c_uchar, len: usize) -> usize {
+    if let Some(last_err) = &*LAST_ERROR.lock().expect("should lock") {
+        return copy_str_to_buf(last_err.code(), buf, len);
+    }
+    0
+}
+
 /// Creates a `Deploy` and sends it to the network for execution.
 ///
 /// See [put_deploy](super::put_deploy) for more details.
@@ -269,6 +258,7 @@ pub extern "C" fn casper_put_deploy(
     deploy_params: *const casper_deploy_params_t,
     session_params: *const casper_session_params_t,
     payment_params: *const casper_payment_params_t,
+    check_entry_point: bool,
     response_buf: *mut c_uchar,
     response_buf_len: usize,
 ) -> casper_error_t {
@@ -287,6 +277,7 @@ pub extern "C" fn casper_put_deploy(
             deploy_params,
             session_params,
             payment_params,
+            check_entry_point,
         )
         .await;
         let response = try_unwrap_rpc!(result);
@@ -312,6 +303,7 @@ pub extern "C" fn casper_make_deploy(
     let payment_params = try_arg_into!(payment_params);
     let result = super::make_deploy(
         maybe_output_path,
+        "",
         deploy_params,
         session_params,
         payment_params,
@@ -329,13 +321,25 @@ pub extern "C" fn casper_make_deploy(
 pub extern "C" fn casper_sign_deploy_file(
     input_path: *const c_char,
     secret_key: *const c_char,
+    signer: *const c_char,
     maybe_output_path: *const c_char,
+    chainspec: *const c_char,
     force: bool,
 ) -> casper_error_t {
     let input_path = try_unsafe_arg!(input_path);
     let secret_key = try_unsafe_arg!(secret_key);
+    let signer = try_unsafe_arg!(signer);
     let maybe_output_path = try_unsafe_arg!(maybe_output_path);
-    let result = super::sign_deploy_file(input_path, secret_key, maybe_output_path, force);
+    let chainspec = try_unsafe_arg!(chainspec);
+    let result = super::sign_deploy_file(
+        input_path,
+        secret_key,
+        signer,
+        maybe_output_path,
+        "",
+        chainspec,
+        force,
+    );
     try_unwrap_result!(result);
     casper_error_t::CASPER_SUCCESS
 }
@@ -349,6 +353,7 @@ pub extern "C" fn casper_send_deploy_file(
     node_address: *const c_char,
     verbosity_level: u64,
     input_path: *const c_char,
+    chainspec: *const c_char,
     response_buf: *mut c_uchar,
     response_buf_len: usize,
 ) -> casper_error_t {
@@ -357,9 +362,16 @@ pub extern "C" fn casper_send_deploy_file(
     let maybe_rpc_id = try_unsafe_arg!(maybe_rpc_id);
     let node_address = try_unsafe_arg!(node_address);
     let input_path = try_unsafe_arg!(input_path);
+    let chainspec = try_unsafe_arg!(chainspec);
     runtime.block_on(async move {
-        let result =
-            super::send_deploy_file(maybe_rpc_id, node_address, verbosity_level, input_path).await;
+        let result = super::send_deploy_file(
+            maybe_rpc_id,
+            node_address,
+            verbosity_level,
+            input_path,
+            chainspec,
+        )
+        .await;
         let response = try_unwrap_rpc!(result);
         copy_str_to_buf(&response, response_buf, response_buf_len);
         casper_error_t::CASPER_SUCCESS
@@ -430,6 +442,7 @@ pub extern "C" fn casper_make_transfer(
     let payment_params = try_arg_into!(payment_params);
     let result = super::make_transfer(
         maybe_output_path,
+        "",
         amount,
         target_account,
         transfer_id,
@@ -803,6 +816,8 @@ pub struct casper_deploy_params_t {
     dependencies_len: usize,
     chain_name: *const c_char,
     session_account: *const c_char,
+    chainspec: *const c_char,
+    signer: *const c_char,
 }
 
 impl TryInto<super::DeployStrParams<'_>> for casper_deploy_params_t {
@@ -823,14 +838,18 @@ impl TryInto<super::DeployStrParams<'_>> for casper_deploy_params_t {
             self.session_account,
             "casper_deploy_params_t.session_account",
         )?;
+        let chainspec = unsafe_str_arg(self.chainspec, "casper_deploy_params_t.chainspec")?;
+        let signer = unsafe_str_arg(self.signer, "casper_deploy_params_t.signer")?;
         Ok(super::DeployStrParams {
             secret_key,
+            signer,
             timestamp,
             ttl,
             gas_price,
             dependencies,
             chain_name,
             session_account,
+            chainspec,
         })
     }
 }
@@ -0,0 +1,459 @@
+//! Creation of the `accounts.toml` file which defines the genesis accounts, validators and
+//! delegators of a network alongside its chainspec.
+
+use std::{collections::BTreeSet, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use casper_types::{
+    system::auction::{DelegationRate, DELEGATION_RATE_DENOMINATOR},
+    AsymmetricType, PublicKey, U512,
+};
+
+use crate::{
+    deploy::OutputKind,
+    error::{Error, Result},
+    key_format,
+    keygen::{self, ED25519, PUBLIC_KEY_HEX, PUBLIC_KEY_PEM},
+    units,
+};
+
+/// The separator between the fields of an account, validator or delegator specification.
+const SEPARATOR: char = ',';
+
+/// The contents of an `accounts.toml` file, as expected by `casper-node`.
+#[derive(Serialize, Deserialize, Debug)]
+struct AccountsConfig {
+    accounts: Vec<AccountConfig>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    delegators: Vec<DelegatorConfig>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct AccountConfig {
+    public_key: PublicKey,
+    balance: U512,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    validator: Option<ValidatorConfig>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct ValidatorConfig {
+    bonded_amount: U512,
+    #[serde(default)]
+    delegation_rate: DelegationRate,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct DelegatorConfig {
+    validator_public_key: PublicKey,
+    delegator_public_key: PublicKey,
+    balance: U512,
+    delegated_amount: U512,
+}
+
+fn invalid_spec(context: &'static str, spec: &str, error: String) -> Error {
+    Error::InvalidArgument {
+        context,
+        error: format!("'{}': {}", spec, error),
+    }
+}
+
+/// Reads a public key from a directory written by `keygen`, or else as for
+/// [`key_format::show`](../key_format/fn.show.html).
+fn public_key(value: &str) -> Result<PublicKey> {
+    let dir = Path::new(value);
+    if dir.is_dir() {
+        return [PUBLIC_KEY_HEX, PUBLIC_KEY_PEM]
+            .iter()
+            .map(|filename| dir.join(filename))
+            .find(|path| path.is_file())
+            .ok_or_else(|| Error::InvalidArgument {
+                context: "public_key",
+                error: format!(
+                    "directory '{}' contains neither {} nor {}",
+                    value, PUBLIC_KEY_HEX, PUBLIC_KEY_PEM
+                ),
+            })
+            .and_then(|path| key_format::read_public_key(&path.to_string_lossy()));
+    }
+    key_format::read_public_key(value)
+}
+
+fn amount(context: &'static str, value: &str) -> Result<U512> {
+    units::parse_motes(context, value)
+}
+
+/// Splits `spec` into its fields, checking there are between `min` and `max` of them.
+fn fields<'a>(
+    context: &'static str,
+    spec: &'a str,
+    min: usize,
+    max: usize,
+    format: &str,
+) -> Result<Vec<&'a str>> {
+    let fields: Vec<&str> = spec.split(SEPARATOR).map(str::trim).collect();
+    if fields.len() < min || fields.len() > max {
+        return Err(invalid_spec(context, spec, format!("expected {}", format)));
+    }
+    Ok(fields)
+}
+
+fn parse_account(spec: &str) -> Result<AccountConfig> {
+    let fields = fields("account", spec, 2, 2, "KEY,BALANCE")?;
+    Ok(AccountConfig {
+        public_key: public_key(fields[0])?,
+        balance: amount("balance", fields[1])?,
+        validator: None,
+    })
+}
+
+fn parse_validator(spec: &str) -> Result<AccountConfig> {
+    let fields = fields(
+        "validator",
+        spec,
+        3,
+        4,
+        "KEY,BALANCE,BONDED_AMOUNT[,DELEGATION_RATE]",
+    )?;
+    let delegation_rate = match fields.get(3) {
+        Some(rate) => rate.parse().map_err(|error| Error::FailedToParseInt {
+            context: "delegation_rate",
+            error,
+        })?,
+        None => 0,
+    };
+    Ok(AccountConfig {
+        public_key: public_key(fields[0])?,
+        balance: amount("balance", fields[1])?,
+        validator: Some(ValidatorConfig {
+            bonded_amount: amount("bonded_amount", fields[2])?,
+            delegation_rate,
+        }),
+    })
+}
+
+fn parse_delegator(spec: &str) -> Result<DelegatorConfig> {
+    let fields = fields(
+        "delegator",
+        spec,
+        4,
+        4,
+        "KEY,VALIDATOR_KEY,BALANCE,DELEGATED_AMOUNT",
+    )?;
+    Ok(DelegatorConfig {
+        delegator_public_key: public_key(fields[0])?,
+        validator_public_key: public_key(fields[1])?,
+        balance: amount("balance", fields[2])?,
+        delegated_amount: amount("delegated_amount", fields[3])?,
+    })
+}
+
+/// Returns an error for genesis accounts which `casper-node` would reject.
+fn invalid(error: String) -> Error {
+    Error::InvalidArgument {
+        context: "genesis_accounts",
+        error,
+    }
+}
+
+/// Returns the public keys of the validators in `config`.
+fn validator_keys(config: &AccountsConfig) -> BTreeSet<&PublicKey> {
+    config
+        .accounts
+        .iter()
+        .filter(|account| account.validator.is_some())
+        .map(|account| &account.public_key)
+        .collect()
+}
+
+/// Checks `config` satisfies the conditions `casper-node` imposes on genesis accounts.
+fn validate(config: &AccountsConfig) -> Result<()> {
+    let mut public_keys = BTreeSet::new();
+    let mut validators = BTreeSet::new();
+    for account in &config.accounts {
+        if !public_keys.insert(&account.public_key) {
+            return Err(invalid(format!(
+                "account {} is specified more than once",
+                account.public_key.to_hex()
+            )));
+        }
+        if let Some(validator) = &account.validator {
+            if validator.bonded_amount.is_zero() {
+                return Err(invalid(format!(
+                    "validator {} must have a non-zero bonded amount",
+                    account.public_key.to_hex()
+                )));
+            }
+            if validator.delegation_rate > DELEGATION_RATE_DENOMINATOR {
+                return Err(invalid(format!(
+                    "validator {} has delegation rate {}, which must not exceed {}",
+                    account.public_key.to_hex(),
+                    validator.delegation_rate,
+                    DELEGATION_RATE_DENOMINATOR
+                )));
+            }
+            validators.insert(&account.public_key);
+        }
+    }
+    if validators.is_empty() {
+        return Err(invalid(
+            "at least one validator is required for the network to make progress".to_string(),
+        ));
+    }
+
+    validate_delegators(&config.delegators, &validators)
+}
+
+/// Checks each of `delegators` delegates a non-zero amount, only once, to one of `validators`.
+fn validate_delegators(
+    delegators: &[DelegatorConfig],
+    validators: &BTreeSet<&PublicKey>,
+) -> Result<()> {
+    let mut delegations = BTreeSet::new();
+    for delegator in delegators {
+        if delegator.delegated_amount.is_zero() {
+            return Err(invalid(format!(
+                "delegator {} must have a non-zero delegated amount",
+                delegator.delegator_public_key.to_hex()
+            )));
+        }
+        if !validators.contains(&delegator.validator_public_key) {
+            return Err(invalid(format!(
+                "delegator {} delegates to {}, which is not a genesis validator",
+                delegator.delegator_public_key.to_hex(),
+                delegator.validator_public_key.to_hex()
+            )));
+        }
+        if !delegations.insert((
+            &delegator.validator_public_key,
+            &delegator.delegator_public_key,
+        )) {
+            return Err(invalid(format!(
+                "delegator {} delegates to {} more than once",
+                delegator.delegator_public_key.to_hex(),
+                delegator.validator_public_key.to_hex()
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn to_toml(config: &AccountsConfig) -> Result<String> {
+    let contents = toml::to_string(config).map_err(|error| Error::InvalidArgument {
+        context: "genesis_accounts",
+        error: error.to_string(),
+    })?;
+    // Check the output parses back as expected, as `casper-node` will parse it.
+    let parsed: AccountsConfig =
+        toml::from_str(&contents).map_err(|error| Error::InvalidArgument {
+            context: "genesis_accounts",
+            error: format!("generated invalid TOML: {}", error),
+        })?;
+    validate(&parsed)?;
+    Ok(contents)
+}
+
+/// Parses a `COUNT,BALANCE,BONDED_AMOUNT[,DELEGATION_RATE]` specification, generating `COUNT`
+/// Ed25519 key pairs in numbered subdirectories of `keys_dir` and returning the corresponding
+/// validator specifications.
+fn generate_validators(spec: &str, keys_dir: &str, force: bool) -> Result<Vec<String>> {
+    let fields = fields(
+        "generate_validators",
+        spec,
+        3,
+        4,
+        "COUNT,BALANCE,BONDED_AMOUNT[,DELEGATION_RATE]",
+    )?;
+    let count: usize = fields[0].parse().map_err(|error| Error::FailedToParseInt {
+        context: "generate_validators",
+        error,
+    })?;
+    keygen::generate_numbered_files(keys_dir, ED25519, count, "", force)?;
+    Ok(keygen::numbered_subdirs(count)
+        .into_iter()
+        .map(|subdir| {
+            let mut spec = Path::new(keys_dir).join(subdir).display().to_string();
+            for field in &fields[1..] {
+                spec.push(SEPARATOR);
+                spec.push_str(field);
+            }
+            spec
+        })
+        .collect())
+}
+
+fn parse_specs(
+    accounts: &[&str],
+    validators: &[String],
+    delegators: &[&str],
+) -> Result<AccountsConfig> {
+    Ok(AccountsConfig {
+        accounts: validators
+            .iter()
+            .map(|spec| parse_validator(spec))
+            .chain(accounts.iter().map(|spec| parse_account(spec)))
+            .collect::<Result<_>>()?,
+        delegators: delegators
+            .iter()
+            .map(|spec| parse_delegator(spec))
+            .collect::<Result<_>>()?,
+    })
+}
+
+pub(crate) fn make_accounts(
+    accounts: &[&str],
+    validators: &[&str],
+    delegators: &[&str],
+    generated_validators: &str,
+    keys_dir: &str,
+    maybe_output_path: &str,
+    force: bool,
+) -> Result<()> {
+    let mut validators: Vec<String> = validators.iter().map(ToString::to_string).collect();
+    if !generated_validators.is_empty() {
+        // Check the other specifications before writing any key files.  The generated validators'
+        // keys are new, so delegators can only delegate to the specified validators.
+        let config = parse_specs(accounts, &validators, delegators)?;
+        validate_delegators(&config.delegators, &validator_keys(&config))?;
+        validators.extend(generate_validators(generated_validators, keys_dir, force)?);
+    }
+    let config = parse_specs(accounts, &validators, delegators)?;
+    validate(&config)?;
+    OutputKind::write_all(maybe_output_path, to_toml(&config)?.as_bytes(), force)
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_node::crypto::AsymmetricKeyExt;
+    use casper_types::SecretKey;
+
+    use super::*;
+
+    fn new_key() -> String {
+        PublicKey::from(&SecretKey::generate_ed25519().unwrap()).to_hex()
+    }
+
+    fn config(validators: &[String], accounts: &[String], delegators: &[String]) -> Result<String> {
+        fn as_strs(specs: &[String]) -> Vec<&str> {
+            specs.iter().map(String::as_str).collect()
+        }
+        let config = parse_specs(&as_strs(accounts), validators, &as_strs(delegators))?;
+        validate(&config)?;
+        to_toml(&config)
+    }
+
+    #[test]
+    fn should_write_accounts_toml_in_node_format() {
+        let validator = new_key();
+        let account = new_key();
+        let delegator = new_key();
+        let toml = config(
+            &[format!("{},1000,500,10", validator)],
+            &[format!("{},2000", account)],
+            &[format!("{},{},300,100", delegator, validator)],
+        )
+        .unwrap();
+        let expected = format!(
+            "[[accounts]]\n\
+            public_key = \"{}\"\n\
+            balance = \"1000\"\n\
+            \n\
+            [accounts.validator]\n\
+            bonded_amount = \"500\"\n\
+            delegation_rate = 10\n\
+            \n\
+            [[accounts]]\n\
+            public_key = \"{}\"\n\
+            balance = \"2000\"\n\
+            \n\
+            [[delegators]]\n\
+            validator_public_key = \"{}\"\n\
+            delegator_public_key = \"{}\"\n\
+            balance = \"300\"\n\
+            delegated_amount = \"100\"\n",
+            validator, account, validator, delegator
+        );
+        assert_eq!(toml, expected);
+    }
+
+    #[test]
+    fn should_reject_invalid_genesis_accounts() {
+        let validator = new_key();
+        let account = new_key();
+        let invalid_configs = [
+            config(&[], &[format!("{},1", account)], &[]),
+            config(&[format!("{},1,0", validator)], &[], &[]),
+            config(&[format!("{},1,1,101", validator)], &[], &[]),
+            config(
+                &[format!("{},1,1", validator)],
+                &[format!("{},1", validator)],
+                &[],
+            ),
+            config(
+                &[format!("{},1,1", validator)],
+                &[],
+                &[format!("{},{},1,1", account, account)],
+            ),
+            config(
+                &[format!("{},1,1", validator)],
+                &[],
+                &[
+                    format!("{},{},1,1", account, validator),
+                    format!("{},{},1,1", account, validator),
+                ],
+            ),
+        ];
+        for result in invalid_configs {
+            assert!(matches!(
+                result,
+                Err(Error::InvalidArgument {
+                    context: "genesis_accounts",
+                    ..
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn should_reject_delegator_to_undeclared_validator() {
+        let validator = new_key();
+        let account = new_key();
+        for delegatee in [new_key(), account.clone()] {
+            let expected_error = format!(
+                "delegates to {}, which is not a genesis validator",
+                delegatee
+            );
+            let result = config(
+                &[format!("{},1,1", validator)],
+                &[format!("{},1", account)],
+                &[format!("{},{},1,1", new_key(), delegatee)],
+            );
+            assert!(matches!(
+                result,
+                Err(Error::InvalidArgument {
+                    context: "genesis_accounts",
+                    error,
+                }) if error.contains(&expected_error)
+            ));
+        }
+    }
+
+    #[test]
+    fn should_reject_malformed_specs() {
+        assert!(matches!(
+            parse_account(&new_key()),
+            Err(Error::InvalidArgument {
+                context: "account",
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse_validator(&format!("{},1,x", new_key())),
+            Err(Error::FailedToParseUint {
+                context: "bonded_amount",
+                ..
+            })
+        ));
+    }
+}
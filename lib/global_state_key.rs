@@ -0,0 +1,281 @@
+//! Parsing and inspection of the formatted [`Key`]s under which values are stored in global state.
+//!
+//! Each variant of `Key` has a formatted form, a prefix naming the variant followed by its
+//! address, e.g. `account-hash-<HEX>` or `era-<NUMBER>`.  Wherever a key identifies the start of a
+//! query, a hex-encoded public key may be given instead, standing for the key of its account.
+
+use std::convert::TryFrom;
+
+use serde::Serialize;
+
+use casper_types::{
+    account::AccountHash, bytesrepr::ToBytes, AccessRights, AsymmetricType, DeployHash, EraId, Key,
+    PublicKey, TransferAddr, URef,
+};
+
+use crate::error::{Error, Result};
+
+/// Prefixes of `Key` variants which exist only in later versions of the network, so can't be
+/// parsed by this client.
+const UNSUPPORTED_PREFIXES: [&str; 2] = ["era-summary-", "chainspec-registry-"];
+
+/// Details of a formatted key, as shown by [`parse`].
+#[derive(Serialize, Debug, PartialEq, Eq)]
+pub struct KeyDescription {
+    /// The key in its canonical formatted form.
+    pub key: String,
+    /// The name of the key's variant, e.g. "Account" or "EraInfo".
+    pub variant: String,
+    /// The hex-encoded address of the key, or `None` for a [`Key::EraInfo`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hex: Option<String>,
+    /// The access rights of a [`Key::URef`], e.g. "READ_ADD_WRITE".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_rights: Option<String>,
+    /// The era ID of a [`Key::EraInfo`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub era_id: Option<u64>,
+    /// The public key which was given in place of a [`Key::Account`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+    /// The hex-encoded serialized key, as used in a `CLValue`.
+    pub bytes: String,
+}
+
+/// Parses `value` as a formatted key, explaining why any unsupported or malformed key is invalid.
+pub(crate) fn from_formatted_str(value: &str) -> std::result::Result<Key, String> {
+    if let Some(prefix) = UNSUPPORTED_PREFIXES
+        .iter()
+        .find(|prefix| value.starts_with(*prefix))
+    {
+        return Err(format!(
+            "'{}' keys are only supported by later versions of the network",
+            prefix
+        ));
+    }
+    Key::from_formatted_str(value).map_err(|error| error.to_string())
+}
+
+/// Parses `value` as the key at which to start a query: a formatted key, or a hex-encoded public
+/// key standing for the key of its account.
+///
+/// The reason for any failure is given by [`parse`].
+pub(crate) fn parse_query_key(value: &str) -> Result<Key> {
+    parse_key_or_public_key("key", value)
+        .map(|(key, _)| key)
+        .map_err(|_| Error::FailedToParseKey)
+}
+
+/// Returns a description of the key `value`, which may be any formatted key or a hex-encoded
+/// public key.
+///
+/// To list the supported formats, call [`supported_key_formats`].
+pub fn parse(value: &str) -> Result<KeyDescription> {
+    let (key, maybe_public_key) = parse_key_or_public_key("key", value)?;
+    let bytes = key.to_bytes().map_err(|error| Error::InvalidArgument {
+        context: "key",
+        error: format!("failed to serialize: {}", error),
+    })?;
+
+    let mut description = KeyDescription {
+        key: key.to_formatted_string(),
+        variant: variant(&key).to_string(),
+        hex: None,
+        access_rights: None,
+        era_id: None,
+        public_key: maybe_public_key.map(|public_key| public_key.to_hex()),
+        bytes: base16::encode_lower(&bytes),
+    };
+    match key {
+        Key::Account(account_hash) | Key::Bid(account_hash) | Key::Withdraw(account_hash) => {
+            description.hex = Some(base16::encode_lower(&account_hash.value()))
+        }
+        Key::Hash(addr) | Key::Balance(addr) | Key::Dictionary(addr) => {
+            description.hex = Some(base16::encode_lower(&addr))
+        }
+        Key::URef(uref) => {
+            description.hex = Some(base16::encode_lower(&uref.addr()));
+            description.access_rights = Some(uref.access_rights().to_string());
+        }
+        Key::Transfer(transfer_addr) => {
+            description.hex = Some(base16::encode_lower(&transfer_addr.value()))
+        }
+        Key::DeployInfo(deploy_hash) => {
+            description.hex = Some(base16::encode_lower(&deploy_hash.value()))
+        }
+        Key::EraInfo(era_id) => description.era_id = Some(era_id.value()),
+        Key::SystemContractRegistry => description.hex = Some(base16::encode_lower(&[0; 32])),
+    }
+    Ok(description)
+}
+
+/// Returns a string listing an example of each supported key format, with the variant it parses
+/// as.
+pub fn supported_key_formats() -> String {
+    let bytes = (1..33).collect::<Vec<_>>();
+    let addr = <[u8; 32]>::try_from(bytes.as_ref()).unwrap();
+    let public_key =
+        PublicKey::from_hex("01c9e33693951aaac23c49bee44ad6f863eedcd38c084a3a8f11237716a3df9c2c")
+            .expect("should parse public key");
+    let keys = [
+        Key::Account(AccountHash::new(addr)),
+        Key::Hash(addr),
+        Key::URef(URef::new(addr, AccessRights::READ_ADD_WRITE)),
+        Key::Transfer(TransferAddr::new(addr)),
+        Key::DeployInfo(DeployHash::new(addr)),
+        Key::EraInfo(EraId::new(1)),
+        Key::Balance(addr),
+        Key::Bid(AccountHash::new(addr)),
+        Key::Withdraw(AccountHash::new(addr)),
+        Key::Dictionary(addr),
+        Key::SystemContractRegistry,
+    ];
+
+    let mut formats = format!("{:<90}# PublicKey, for Key::Account\n", public_key.to_hex());
+    for key in keys.iter() {
+        formats.push_str(&format!(
+            "{:<90}# Key::{}\n",
+            key.to_formatted_string(),
+            variant(key)
+        ));
+    }
+    formats.push_str(&format!(
+        "The hex of a uref is followed by its access rights, from 000 (NONE) to 007 \
+        (READ_ADD_WRITE). The system contract registry key is unique, always written as above. \
+        Keys with the prefixes {} are only supported by later versions of the network.",
+        UNSUPPORTED_PREFIXES
+            .iter()
+            .map(|prefix| format!("'{}'", prefix))
+            .collect::<Vec<_>>()
+            .join(" and ")
+    ));
+    formats
+}
+
+fn parse_key_or_public_key(context: &'static str, value: &str) -> Result<(Key, Option<PublicKey>)> {
+    let value = value.trim();
+    let key_error = match from_formatted_str(value) {
+        Ok(key) => return Ok((key, None)),
+        Err(error) => error,
+    };
+    if let Ok(public_key) = PublicKey::from_hex(value) {
+        return Ok((Key::Account(public_key.to_account_hash()), Some(public_key)));
+    }
+    let error = if has_known_prefix(value) {
+        format!("can't parse '{}' as a key: {}", value, key_error)
+    } else {
+        format!(
+            "'{}' is neither a formatted key nor a hex-encoded public key",
+            value
+        )
+    };
+    Err(Error::InvalidArgument { context, error })
+}
+
+/// Returns true if `value` starts with the prefix of a `Key` variant, supported or not.
+fn has_known_prefix(value: &str) -> bool {
+    const PREFIXES: [&str; 11] = [
+        "account-hash-",
+        "hash-",
+        "uref-",
+        "transfer-",
+        "deploy-",
+        "era-",
+        "balance-",
+        "bid-",
+        "withdraw-",
+        "dictionary-",
+        "system-contract-registry-",
+    ];
+    PREFIXES
+        .iter()
+        .chain(UNSUPPORTED_PREFIXES.iter())
+        .any(|prefix| value.starts_with(prefix))
+}
+
+fn variant(key: &Key) -> &'static str {
+    match key {
+        Key::Account(_) => "Account",
+        Key::Hash(_) => "Hash",
+        Key::URef(_) => "URef",
+        Key::Transfer(_) => "Transfer",
+        Key::DeployInfo(_) => "DeployInfo",
+        Key::EraInfo(_) => "EraInfo",
+        Key::Balance(_) => "Balance",
+        Key::Bid(_) => "Bid",
+        Key::Withdraw(_) => "Withdraw",
+        Key::Dictionary(_) => "Dictionary",
+        Key::SystemContractRegistry => "SystemContractRegistry",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_every_supported_key_format() {
+        let formats = supported_key_formats();
+        let examples = formats
+            .lines()
+            .filter_map(|line| line.split_once('#'))
+            .collect::<Vec<_>>();
+        assert_eq!(examples.len(), 12);
+        for (example, variant) in examples {
+            let description = parse(example).unwrap();
+            assert!(
+                variant.contains(&format!("Key::{}", description.variant)),
+                "{}",
+                example
+            );
+            let key = from_formatted_str(&description.key).unwrap();
+            assert_eq!(key.to_formatted_string(), description.key);
+        }
+    }
+
+    #[test]
+    fn should_describe_uref_and_era_keys() {
+        let addr = [7; 32];
+        let uref = Key::URef(URef::new(addr, AccessRights::READ_ADD));
+        let description = parse(&uref.to_formatted_string()).unwrap();
+        assert_eq!(description.variant, "URef");
+        assert_eq!(description.hex, Some(base16::encode_lower(&addr)));
+        assert_eq!(description.access_rights, Some("READ_ADD".to_string()));
+        assert_eq!(
+            description.bytes,
+            base16::encode_lower(&uref.to_bytes().unwrap())
+        );
+
+        let description = parse("era-42").unwrap();
+        assert_eq!(description.variant, "EraInfo");
+        assert_eq!(description.era_id, Some(42));
+        assert_eq!(description.hex, None);
+    }
+
+    #[test]
+    fn should_parse_public_key_as_account_key() {
+        let public_key = PublicKey::from_hex(
+            "01c9e33693951aaac23c49bee44ad6f863eedcd38c084a3a8f11237716a3df9c2c",
+        )
+        .unwrap();
+        let key = parse_query_key(&public_key.to_hex()).unwrap();
+        assert_eq!(key, Key::Account(public_key.to_account_hash()));
+        let description = parse(&public_key.to_hex()).unwrap();
+        assert_eq!(description.public_key, Some(public_key.to_hex()));
+    }
+
+    #[test]
+    fn should_explain_invalid_keys() {
+        let error = |value| match parse(value) {
+            Err(Error::InvalidArgument {
+                context: "key",
+                error,
+            }) => error,
+            other => panic!("unexpected result for {}: {:?}", value, other),
+        };
+        assert!(error("era-summary-00").contains("later versions of the network"));
+        assert!(error("chainspec-registry-00").contains("later versions of the network"));
+        assert!(error("deploy-zz").contains("deploy-info-key"));
+        assert!(error("foo").contains("neither a formatted key nor a hex-encoded public key"));
+    }
+}
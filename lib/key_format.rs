@@ -0,0 +1,398 @@
+//! Inspection of keys, and conversion of keys between encodings.
+//!
+//! Keys can be read from PEM, DER, base64 or hex, and can be either secret or public.  PEM and DER
+//! identify the kind of key they hold.  Base64 is taken to be the base64 encoding of the DER, i.e.
+//! the body of a PEM file.  Hex is the algorithm tag followed by the raw key bytes, as in
+//! "public_key_hex".  As an Ed25519 secret key and an Ed25519 public key have the same length in
+//! hex, hex keys are treated as public keys unless specified otherwise.
+
+use std::{fs, path::Path, str};
+
+use serde::Serialize;
+use zeroize::Zeroizing;
+
+use casper_node::crypto::AsymmetricKeyExt;
+use casper_types::{
+    account, checksummed_hex, AsymmetricType, PublicKey, SecretKey, Tagged, ED25519_TAG,
+    SECP256K1_TAG,
+};
+
+use crate::{
+    deploy::OutputKind,
+    error::{Error, Result},
+    keygen::{ED25519, SECP256K1},
+    keystore,
+};
+
+/// Name of the PEM key encoding.
+pub const PEM: &str = "pem";
+/// Name of the DER key encoding.
+pub const DER: &str = "der";
+/// Name of the base64 key encoding, i.e. base64-encoded DER.
+pub const BASE64: &str = "base64";
+/// Name of the hex key encoding, i.e. the hex-encoded algorithm tag followed by the key bytes.
+pub const HEX: &str = "hex";
+
+/// List of supported key encodings: "pem", "der", "base64" and "hex".
+pub const FORMATS: [&str; 4] = [PEM, DER, BASE64, HEX];
+
+const PEM_PREFIX: &str = "-----BEGIN";
+
+/// Details of a secret or public key, as shown by [`show`].
+#[derive(Serialize, Debug)]
+pub struct KeyInfo {
+    /// The key's algorithm, "Ed25519" or "secp256k1".
+    pub algorithm: String,
+    /// Whether the key read was a secret key rather than a public key.
+    pub is_secret_key: bool,
+    /// The public key as hex, with the algorithm tag prefixed.
+    pub public_key: String,
+    /// The public key as checksummed hex, with the algorithm tag prefixed.
+    pub public_key_checksummed: String,
+    /// The formatted account hash of the public key.
+    pub account_hash: String,
+}
+
+enum Key {
+    Secret(SecretKey),
+    Public(PublicKey),
+}
+
+impl Key {
+    fn public_key(&self) -> PublicKey {
+        match self {
+            Key::Secret(secret_key) => PublicKey::from(secret_key),
+            Key::Public(public_key) => public_key.clone(),
+        }
+    }
+
+    fn to_der(&self) -> Result<Vec<u8>> {
+        match self {
+            Key::Secret(secret_key) => secret_key.to_der().map_err(|error| Error::CryptoError {
+                context: "secret_key",
+                error,
+            }),
+            Key::Public(public_key) => public_key.to_der().map_err(|error| Error::CryptoError {
+                context: "public_key",
+                error,
+            }),
+        }
+    }
+
+    fn to_pem(&self) -> Result<String> {
+        match self {
+            Key::Secret(secret_key) => secret_key.to_pem().map_err(|error| Error::CryptoError {
+                context: "secret_key",
+                error,
+            }),
+            Key::Public(public_key) => public_key.to_pem().map_err(|error| Error::CryptoError {
+                context: "public_key",
+                error,
+            }),
+        }
+    }
+
+    fn to_hex(&self) -> Result<String> {
+        let secret_key = match self {
+            Key::Secret(secret_key) => secret_key,
+            Key::Public(public_key) => return Ok(public_key.to_hex()),
+        };
+        let mut bytes = Vec::with_capacity(SecretKey::ED25519_LENGTH + 1);
+        match secret_key {
+            SecretKey::Ed25519(key) => {
+                bytes.push(ED25519_TAG);
+                bytes.extend_from_slice(key.as_bytes());
+            }
+            SecretKey::Secp256k1(key) => {
+                bytes.push(SECP256K1_TAG);
+                bytes.extend_from_slice(&key.to_bytes());
+            }
+            SecretKey::System => return Err(invalid_key("system keys are not supported")),
+        }
+        Ok(base16::encode_lower(&bytes))
+    }
+}
+
+fn invalid_key<T: ToString>(error: T) -> Error {
+    Error::InvalidArgument {
+        context: "key",
+        error: error.to_string(),
+    }
+}
+
+/// Reads a key from `input`, which is the path to a key file, or if no such file exists, the key
+/// itself.
+fn read_key(input: &str, hex_is_secret: bool) -> Result<Key> {
+    let from_file = Path::new(input).is_file();
+    let contents = if from_file {
+        fs::read(input).map_err(|error| Error::IoError {
+            context: format!("unable to read key file at '{}'", input),
+            error,
+        })?
+    } else {
+        input.as_bytes().to_vec()
+    };
+
+    if keystore::is_keystore(&contents) {
+        if !from_file {
+            return Err(invalid_key("a keystore must be provided as a file"));
+        }
+        return keystore::read_secret_key(input).map(Key::Secret);
+    }
+
+    let text = match str::from_utf8(&contents) {
+        Ok(text) => text.trim(),
+        Err(_) => return from_der(&contents),
+    };
+    if text.starts_with(PEM_PREFIX) {
+        return SecretKey::from_pem(text)
+            .map(Key::Secret)
+            .or_else(|_| PublicKey::from_pem(text).map(Key::Public))
+            .map_err(|error| invalid_key(format!("invalid PEM: {}", error)));
+    }
+    if !text.is_empty() && text.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return if hex_is_secret {
+            secret_key_from_hex(text).map(Key::Secret)
+        } else {
+            PublicKey::from_hex(text)
+                .map(Key::Public)
+                .map_err(|error| invalid_key(format!("invalid hex public key: {}", error)))
+        };
+    }
+    if from_file {
+        return base64::decode(text).map_or_else(|_| from_der(&contents), |der| from_der(&der));
+    }
+    base64::decode(text)
+        .ok()
+        .and_then(|der| from_der(&der).ok())
+        .ok_or_else(|| {
+            invalid_key(format!(
+                "'{}' is neither a key file nor a valid PEM, base64 or hex key",
+                input
+            ))
+        })
+}
+
+/// Reads the public key at `input`, or the public key of the secret key at `input`, where `input`
+/// is as for [`show`].  A hex key is read as a public key.
+pub(crate) fn read_public_key(input: &str) -> Result<PublicKey> {
+    read_key(input, false).map(|key| key.public_key())
+}
+
+fn from_der(der: &[u8]) -> Result<Key> {
+    SecretKey::from_der(der)
+        .map(Key::Secret)
+        .or_else(|_| PublicKey::from_der(der).map(Key::Public))
+        .map_err(|error| invalid_key(format!("invalid DER: {}", error)))
+}
+
+fn secret_key_from_hex(hex: &str) -> Result<SecretKey> {
+    let bytes = checksummed_hex::decode(hex)
+        .map_err(|error| invalid_key(format!("invalid hex secret key: {}", error)))?;
+    let result = match bytes.split_first() {
+        Some((&ED25519_TAG, key_bytes)) => SecretKey::ed25519_from_bytes(key_bytes),
+        Some((&SECP256K1_TAG, key_bytes)) => SecretKey::secp256k1_from_bytes(key_bytes),
+        _ => {
+            return Err(invalid_key(format!(
+                "invalid hex secret key: expected tag {} or {}",
+                ED25519_TAG, SECP256K1_TAG
+            )))
+        }
+    };
+    result.map_err(|error| invalid_key(format!("invalid hex secret key: {}", error)))
+}
+
+/// Encodes `bytes` as hex with mixed-case checksums, as decoded by
+/// [`checksummed_hex::decode`](casper_types::checksummed_hex::decode).
+fn encode_checksummed_hex(bytes: &[u8]) -> String {
+    let hash = account::blake2b(bytes);
+    let mut hash_bits = hash
+        .iter()
+        .cycle()
+        .flat_map(|byte| (0..8).map(move |offset| (byte >> offset) & 1 == 1));
+    base16::encode_lower(bytes)
+        .chars()
+        .map(|hex_char| {
+            if hex_char.is_ascii_alphabetic() && hash_bits.next().unwrap_or(true) {
+                hex_char.to_ascii_uppercase()
+            } else {
+                hex_char
+            }
+        })
+        .collect()
+}
+
+/// Reads the key at `input` and returns its details.
+///
+/// `input` is the path to a secret or public key file, or if no such file exists, the key itself
+/// as PEM, base64 or hex.  A keystore's password is read via
+/// [`keystore::read_password`](../keystore/fn.read_password.html).  If `hex_is_secret` is true, a
+/// hex key is read as a secret key rather than as a public key.
+pub fn show(input: &str, hex_is_secret: bool) -> Result<KeyInfo> {
+    let key = read_key(input, hex_is_secret)?;
+    let public_key = key.public_key();
+    let algorithm = match public_key {
+        PublicKey::Ed25519(_) => ED25519,
+        PublicKey::Secp256k1(_) => SECP256K1,
+        PublicKey::System => return Err(invalid_key("system keys are not supported")),
+    };
+    let public_key_checksummed = format!(
+        "{}{}",
+        encode_checksummed_hex(&[public_key.tag()]),
+        encode_checksummed_hex(&Vec::<u8>::from(&public_key))
+    );
+    Ok(KeyInfo {
+        algorithm: algorithm.to_string(),
+        is_secret_key: matches!(key, Key::Secret(_)),
+        public_key: public_key.to_hex(),
+        public_key_checksummed,
+        account_hash: public_key.to_account_hash().to_formatted_string(),
+    })
+}
+
+/// Reads the key at `input` and writes it in the encoding `format` to `maybe_output_path`, or to
+/// `stdout` if empty.
+///
+/// `format` must be one of [`FORMATS`].  `input` and `hex_is_secret` are as for [`show`].  A
+/// secret key remains a secret key; to get the public key of a secret key, see
+/// [`keygen::generate_public_key_files`](../keygen/fn.generate_public_key_files.html).
+///
+/// If `force` is true, and a file exists at `maybe_output_path`, it will be overwritten.  If
+/// `force` is false and a file exists at `maybe_output_path`,
+/// [`Error::FileAlreadyExists`](../enum.Error.html#variant.FileAlreadyExists) is returned.
+pub fn convert(
+    input: &str,
+    format: &str,
+    hex_is_secret: bool,
+    maybe_output_path: &str,
+    force: bool,
+) -> Result<()> {
+    if !FORMATS.contains(&format) {
+        return Err(Error::InvalidArgument {
+            context: "format",
+            error: format!("'{}' must be one of {:?}", format, FORMATS),
+        });
+    }
+    let key = read_key(input, hex_is_secret)?;
+    let output = Zeroizing::new(match format {
+        PEM => key.to_pem()?.into_bytes(),
+        DER => key.to_der()?,
+        BASE64 => base64::encode(key.to_der()?).into_bytes(),
+        _ => key.to_hex()?.into_bytes(),
+    });
+    match key {
+        Key::Secret(_) => OutputKind::write_all_private(maybe_output_path, &output, force),
+        Key::Public(_) => OutputKind::write_all(maybe_output_path, &output, force),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn should_encode_checksummed_hex_decodable_by_casper_types() {
+        for secret_key in [
+            SecretKey::generate_ed25519().unwrap(),
+            SecretKey::generate_secp256k1().unwrap(),
+        ] {
+            let public_key = PublicKey::from(&secret_key);
+            let bytes = Vec::<u8>::from(&public_key);
+            let encoded = encode_checksummed_hex(&bytes);
+            assert_eq!(encoded.to_lowercase(), base16::encode_lower(&bytes));
+            assert_eq!(checksummed_hex::decode(&encoded).unwrap(), bytes);
+
+            let info = show(&public_key.to_hex(), false).unwrap();
+            assert_eq!(
+                PublicKey::from_hex(&info.public_key_checksummed).unwrap(),
+                public_key
+            );
+        }
+    }
+
+    #[test]
+    fn should_convert_between_all_formats() {
+        let temp_dir = TempDir::new().unwrap();
+        for secret_key in [
+            SecretKey::generate_ed25519().unwrap(),
+            SecretKey::generate_secp256k1().unwrap(),
+        ] {
+            let public_key = PublicKey::from(&secret_key);
+            let mut input = temp_dir.path().join("secret_key.pem");
+            secret_key.to_file(&input).unwrap();
+
+            for format in FORMATS {
+                let output = temp_dir.path().join(format);
+                convert(
+                    input.to_str().unwrap(),
+                    format,
+                    true,
+                    output.to_str().unwrap(),
+                    true,
+                )
+                .unwrap();
+                let info = show(output.to_str().unwrap(), true).unwrap();
+                assert!(info.is_secret_key);
+                assert_eq!(info.public_key, public_key.to_hex());
+                input = output;
+            }
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn should_write_only_secret_keys_readable_only_by_owner() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let secret_key = SecretKey::generate_ed25519().unwrap();
+        let public_key_hex = PublicKey::from(&secret_key).to_hex();
+        let secret_key_path = temp_dir.path().join("secret_key.pem");
+        secret_key.to_file(&secret_key_path).unwrap();
+
+        let mode = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+        // A public key is written with the same permissions as any other new file.
+        let probe = temp_dir.path().join("probe");
+        fs::write(&probe, "").unwrap();
+        for (input, expected_mode) in [
+            (secret_key_path.to_str().unwrap(), 0o600),
+            (public_key_hex.as_str(), mode(&probe)),
+        ] {
+            let output = temp_dir.path().join("converted");
+            convert(input, HEX, false, output.to_str().unwrap(), true).unwrap();
+            assert_eq!(mode(&output), expected_mode);
+        }
+    }
+
+    #[test]
+    fn should_read_hex_as_public_key_unless_specified() {
+        let secret_key = SecretKey::generate_ed25519().unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        let info = show(&public_key.to_hex(), false).unwrap();
+        assert!(!info.is_secret_key);
+        assert_eq!(info.public_key, public_key.to_hex());
+
+        let secret_key_hex = Key::Secret(secret_key).to_hex().unwrap();
+        let info = show(&secret_key_hex, true).unwrap();
+        assert!(info.is_secret_key);
+        assert_eq!(info.public_key, public_key.to_hex());
+    }
+
+    #[test]
+    fn should_fail_for_invalid_key() {
+        assert!(matches!(
+            show("not a key", false),
+            Err(Error::InvalidArgument { context: "key", .. })
+        ));
+        assert!(matches!(
+            convert("01", "json", false, "", false),
+            Err(Error::InvalidArgument {
+                context: "format",
+                ..
+            })
+        ));
+    }
+}
@@ -1,14 +1,26 @@
 //! Cryptographic key generation.
 
-use std::{fs, path::Path};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use serde::Serialize;
 
 use casper_node::crypto::AsymmetricKeyExt;
 use casper_types::{AsymmetricType, PublicKey, SecretKey};
 
-use crate::error::{Error, Result};
+use crate::{
+    deploy,
+    error::{Error, Result},
+    keystore, mnemonic, vanity,
+};
 
 /// Default filename for the PEM-encoded secret key file.
 pub const SECRET_KEY_PEM: &str = "secret_key.pem";
+/// Default filename for the password-encrypted secret key file.
+pub const SECRET_KEY_JSON: &str = "secret_key.json";
 /// Default filename for the hex-encoded public key file.
 pub const PUBLIC_KEY_HEX: &str = "public_key_hex";
 /// Default filename for the PEM-encoded public key file.
@@ -16,12 +28,46 @@ pub const PUBLIC_KEY_PEM: &str = "public_key.pem";
 
 /// List of keygen related filenames: "secret_key.pem", "public_key.pem" and "public_key_hex".
 pub const FILES: [&str; 3] = [SECRET_KEY_PEM, PUBLIC_KEY_PEM, PUBLIC_KEY_HEX];
+/// List of encrypted keygen related filenames: "secret_key.json", "public_key.pem" and
+/// "public_key_hex".
+pub const ENCRYPTED_FILES: [&str; 3] = [SECRET_KEY_JSON, PUBLIC_KEY_PEM, PUBLIC_KEY_HEX];
+
+/// Default filename for the index of key pairs written by [`generate_numbered_files`].
+pub const INDEX_CSV: &str = "index.csv";
 
 /// Name of Ed25519 algorithm.
 pub const ED25519: &str = "Ed25519";
 /// Name of secp256k1 algorithm.
 pub const SECP256K1: &str = "secp256k1";
 
+/// The default BIP-44 derivation path for secp256k1 keys, using Casper's coin type of 506.
+pub const DEFAULT_SECP256K1_PATH: &str = "m/44'/506'/0'/0/0";
+/// The default SLIP-10 derivation path for Ed25519 keys, using Casper's coin type of 506.  As
+/// SLIP-10 only supports hardened derivation for Ed25519, every level is hardened.
+pub const DEFAULT_ED25519_PATH: &str = "m/44'/506'/0'/0'/0'";
+/// The number of words in a mnemonic generated by default.
+pub const DEFAULT_MNEMONIC_WORD_COUNT: usize = 24;
+
+/// What the prefix passed to [`generate_vanity_files`] is matched against.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VanityTarget {
+    /// The hex-encoded public key, following the algorithm tag.
+    PublicKey,
+    /// The hex-encoded account hash.
+    AccountHash,
+}
+
+/// The progress of a search by [`generate_vanity_files`].
+#[derive(Clone, Copy, Debug)]
+pub struct VanityProgress {
+    /// The number of keys tried so far.
+    pub attempts: u64,
+    /// The number of keys which are expected to be tried before finding a match.
+    pub expected_attempts: u64,
+    /// The time taken so far.
+    pub elapsed: Duration,
+}
+
 /// Generates a new asymmetric key pair using the specified algorithm, and writes them to files in
 /// the specified directory.
 ///
@@ -33,41 +79,251 @@ pub const SECP256K1: &str = "secp256k1";
 /// files exist, [`Error::FileAlreadyExists`](../enum.Error.html#variant.FileAlreadyExists) is
 /// returned and no files are written.
 pub fn generate_files(output_dir: &str, algorithm: &str, force: bool) -> Result<()> {
-    if output_dir.is_empty() {
+    generate(
+        Path::new(output_dir),
+        || random_secret_key(algorithm),
+        None,
+        force,
+    )
+    .map(|_| ())
+}
+
+/// Generates a new asymmetric key pair using the specified algorithm, and writes them to files in
+/// the specified directory, with the secret key encrypted using `password`.
+///
+/// As for [`generate_files`], except that the secret key is written to "secret_key.json" as a
+/// keystore; see [`keystore`](../keystore/index.html) for details.
+pub fn generate_encrypted_files(
+    output_dir: &str,
+    algorithm: &str,
+    password: &str,
+    force: bool,
+) -> Result<()> {
+    generate(
+        Path::new(output_dir),
+        || random_secret_key(algorithm),
+        Some(password),
+        force,
+    )
+    .map(|_| ())
+}
+
+/// Generates a new BIP-39 mnemonic phrase of `word_count` words, which must be 12, 15, 18, 21 or
+/// 24.
+///
+/// Keys can be derived from the mnemonic using [`generate_files_from_mnemonic`].
+pub fn generate_mnemonic(word_count: usize) -> Result<String> {
+    mnemonic::generate(word_count)
+}
+
+/// Derives an asymmetric key pair of the specified algorithm from a BIP-39 mnemonic, and writes
+/// them to files in the specified directory.
+///
+/// secp256k1 keys are derived using BIP-32, and Ed25519 keys using SLIP-10.  If
+/// `derivation_path` is empty, [`DEFAULT_SECP256K1_PATH`] or [`DEFAULT_ED25519_PATH`] is used.
+///
+/// If `password` is empty, the files are written as for [`generate_files`], otherwise as for
+/// [`generate_encrypted_files`].
+pub fn generate_files_from_mnemonic(
+    output_dir: &str,
+    algorithm: &str,
+    mnemonic: &str,
+    derivation_path: &str,
+    password: &str,
+    force: bool,
+) -> Result<()> {
+    generate(
+        Path::new(output_dir),
+        || mnemonic::derive_secret_key(mnemonic, algorithm, derivation_path),
+        non_empty(password),
+        force,
+    )
+    .map(|_| ())
+}
+
+/// Generates `count` new asymmetric key pairs using the specified algorithm, writing each to a
+/// numbered subdirectory of the specified directory, e.g. "001", "002", etc.
+///
+/// Each subdirectory holds the files written by [`generate_files`], or if `password` is not empty,
+/// by [`generate_encrypted_files`].  The hex-encoded public key and formatted account hash of each
+/// key pair are listed in "index.csv" in the specified directory.
+///
+/// If `force` is true, existing files will be overwritten. If `force` is false and any of the
+/// files exist, [`Error::FileAlreadyExists`](../enum.Error.html#variant.FileAlreadyExists) is
+/// returned and no files are written.
+pub fn generate_numbered_files(
+    output_dir: &str,
+    algorithm: &str,
+    count: usize,
+    password: &str,
+    force: bool,
+) -> Result<()> {
+    if count == 0 {
+        return Err(Error::InvalidArgument {
+            context: "count",
+            error: "must be at least 1".to_string(),
+        });
+    }
+    random_secret_key(algorithm)?;
+    let maybe_password = non_empty(password);
+    let output_dir = create_output_dir(Path::new(output_dir), &[INDEX_CSV], force)?;
+
+    let subdirs = numbered_subdirs(count);
+    if !force {
+        for subdir in &subdirs {
+            for file in key_files(maybe_password) {
+                let path = output_dir.join(subdir).join(file);
+                if path.exists() {
+                    return Err(Error::FileAlreadyExists(path));
+                }
+            }
+        }
+    }
+
+    let index_path = output_dir.join(INDEX_CSV);
+    let index_error = |error: csv::Error| Error::IoError {
+        context: format!("unable to write index file at {}", index_path.display()),
+        error: error.into(),
+    };
+    let mut writer = csv::Writer::from_path(&index_path).map_err(index_error)?;
+    for subdir in &subdirs {
+        let public_key = generate(
+            &output_dir.join(subdir),
+            || random_secret_key(algorithm),
+            maybe_password,
+            force,
+        )?;
+        writer
+            .serialize(IndexRow {
+                directory: subdir,
+                public_key: public_key.to_hex(),
+                account_hash: public_key.to_account_hash().to_formatted_string(),
+            })
+            .map_err(index_error)?;
+    }
+    writer.flush().map_err(|error| Error::IoError {
+        context: format!("unable to write index file at {}", index_path.display()),
+        error,
+    })
+}
+
+/// Generates random key pairs of the specified algorithm on every CPU core until finding one whose
+/// public key or account hash, as hex, starts with `prefix`, then writes the key pair to files in
+/// the specified directory.
+///
+/// For [`VanityTarget::PublicKey`], `prefix` is matched against the hex-encoded public key
+/// following the algorithm tag.  `prefix` is case-insensitive.  Every extra hex digit makes the
+/// search take 16 times longer; `on_progress` is called about once a second with an estimate.
+///
+/// The files are written as for [`generate_files_from_mnemonic`], and the public key is returned.
+pub fn generate_vanity_files(
+    output_dir: &str,
+    algorithm: &str,
+    prefix: &str,
+    target: VanityTarget,
+    password: &str,
+    force: bool,
+    on_progress: impl FnMut(&VanityProgress),
+) -> Result<PublicKey> {
+    let maybe_password = non_empty(password);
+    // Check for existing files before a potentially long search.
+    create_output_dir(Path::new(output_dir), &key_files(maybe_password), force)?;
+    let secret_key = vanity::search(algorithm, prefix, target, on_progress)?;
+    generate(
+        Path::new(output_dir),
+        || Ok(secret_key),
+        maybe_password,
+        force,
+    )
+}
+
+/// Reads the secret key at `secret_key_path`, which may be either a PEM file or a keystore, and
+/// writes the corresponding public key to files in the specified directory.
+///
+/// The public key is written to "public_key.pem" and also in hex format to "public_key_hex", as for
+/// [`generate_files`].
+///
+/// If `force` is true, existing files will be overwritten. If `force` is false and either of the
+/// files exist, [`Error::FileAlreadyExists`](../enum.Error.html#variant.FileAlreadyExists) is
+/// returned and no files are written.
+pub fn generate_public_key_files(
+    secret_key_path: &str,
+    output_dir: &str,
+    force: bool,
+) -> Result<()> {
+    let output_dir = create_output_dir(
+        Path::new(output_dir),
+        &[PUBLIC_KEY_PEM, PUBLIC_KEY_HEX],
+        force,
+    )?;
+    let secret_key = keystore::read_secret_key(secret_key_path)?;
+    write_public_key_files(&output_dir, &PublicKey::from(&secret_key))
+}
+
+/// A row of the index file written by [`generate_numbered_files`].
+#[derive(Serialize)]
+struct IndexRow<'a> {
+    directory: &'a str,
+    public_key: String,
+    account_hash: String,
+}
+
+fn non_empty(password: &str) -> Option<&str> {
+    if password.is_empty() {
+        None
+    } else {
+        Some(password)
+    }
+}
+
+pub(crate) fn random_secret_key(algorithm: &str) -> Result<SecretKey> {
+    if algorithm.eq_ignore_ascii_case(ED25519) {
+        Ok(SecretKey::generate_ed25519().unwrap())
+    } else if algorithm.eq_ignore_ascii_case(SECP256K1) {
+        Ok(SecretKey::generate_secp256k1().unwrap())
+    } else {
+        Err(Error::UnsupportedAlgorithm(algorithm.to_string()))
+    }
+}
+
+/// Returns the names of the subdirectories written by [`generate_numbered_files`], zero-padded to
+/// at least three digits.
+pub(crate) fn numbered_subdirs(count: usize) -> Vec<String> {
+    let width = count.to_string().len().max(3);
+    (1..=count)
+        .map(|number| format!("{:0width$}", number, width = width))
+        .collect()
+}
+
+/// Creates `output_dir` if required, returning its canonical path.  If `force` is false and any of
+/// `files` exist in the directory, returns an error.
+fn create_output_dir(output_dir: &Path, files: &[&str], force: bool) -> Result<PathBuf> {
+    if output_dir.as_os_str().is_empty() {
         return Err(Error::InvalidArgument {
             context: "generate_files",
             error: "empty output_dir provided, must be a valid path".to_string(),
         });
     }
-    let _ = fs::create_dir_all(output_dir).map_err(move |error| Error::IoError {
-        context: format!("unable to create directory at '{}'", output_dir),
+    fs::create_dir_all(output_dir).map_err(move |error| Error::IoError {
+        context: format!("unable to create directory at '{}'", output_dir.display()),
+        error,
+    })?;
+    let output_dir = output_dir.canonicalize().map_err(|error| Error::IoError {
+        context: format!("unable get canonical path at '{}'", output_dir.display()),
         error,
     })?;
-    let output_dir = Path::new(output_dir)
-        .canonicalize()
-        .map_err(|error| Error::IoError {
-            context: format!("unable get canonical path at '{}'", output_dir),
-            error,
-        })?;
 
     if !force {
-        for file in FILES.iter().map(|filename| output_dir.join(filename)) {
+        for file in files.iter().map(|filename| output_dir.join(filename)) {
             if file.exists() {
                 return Err(Error::FileAlreadyExists(file));
             }
         }
     }
+    Ok(output_dir)
+}
 
-    let secret_key = if algorithm.eq_ignore_ascii_case(ED25519) {
-        SecretKey::generate_ed25519().unwrap()
-    } else if algorithm.eq_ignore_ascii_case(SECP256K1) {
-        SecretKey::generate_secp256k1().unwrap()
-    } else {
-        return Err(Error::UnsupportedAlgorithm(algorithm.to_string()));
-    };
-
-    let public_key = PublicKey::from(&secret_key);
-
+fn write_public_key_files(output_dir: &Path, public_key: &PublicKey) -> Result<()> {
     let public_key_hex_path = output_dir.join(PUBLIC_KEY_HEX);
     fs::write(public_key_hex_path, public_key.to_hex()).map_err(|error| Error::IoError {
         context: format!(
@@ -77,21 +333,63 @@ pub fn generate_files(output_dir: &str, algorithm: &str, force: bool) -> Result<
         error,
     })?;
 
-    let secret_key_path = output_dir.join(SECRET_KEY_PEM);
-    secret_key
-        .to_file(&secret_key_path)
-        .map_err(|error| Error::CryptoError {
-            context: "secret_key",
-            error,
-        })?;
-
     let public_key_path = output_dir.join(PUBLIC_KEY_PEM);
     public_key
         .to_file(&public_key_path)
         .map_err(|error| Error::CryptoError {
             context: "public_key",
             error,
-        })?;
+        })
+}
+
+fn key_files(maybe_password: Option<&str>) -> [&'static str; 3] {
+    if maybe_password.is_some() {
+        ENCRYPTED_FILES
+    } else {
+        FILES
+    }
+}
+
+fn generate(
+    output_dir: &Path,
+    secret_key: impl FnOnce() -> Result<SecretKey>,
+    maybe_password: Option<&str>,
+    force: bool,
+) -> Result<PublicKey> {
+    let output_dir = create_output_dir(output_dir, &key_files(maybe_password), force)?;
+
+    let secret_key = secret_key()?;
+
+    // Encrypt before writing any files, so that an invalid password leaves nothing behind.
+    let maybe_keystore = maybe_password
+        .map(|password| keystore::encrypt(&secret_key, password))
+        .transpose()?;
 
-    Ok(())
+    match maybe_keystore {
+        Some(keystore) => {
+            let secret_key_path = output_dir.join(SECRET_KEY_JSON);
+            deploy::write_private_file(&secret_key_path, keystore.as_bytes()).map_err(|error| {
+                Error::IoError {
+                    context: format!(
+                        "unable to write secret key file at {}",
+                        secret_key_path.display()
+                    ),
+                    error,
+                }
+            })?;
+        }
+        None => {
+            let secret_key_path = output_dir.join(SECRET_KEY_PEM);
+            secret_key
+                .to_file(&secret_key_path)
+                .map_err(|error| Error::CryptoError {
+                    context: "secret_key",
+                    error,
+                })?;
+        }
+    }
+
+    let public_key = PublicKey::from(&secret_key);
+    write_public_key_files(&output_dir, &public_key)?;
+    Ok(public_key)
 }
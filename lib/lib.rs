@@ -12,15 +12,32 @@
     unused_qualifications
 )]
 
+mod arg_tokenizer;
+mod batch_transfer;
 mod cl_type;
+pub mod config;
 mod deploy;
+mod deploy_limits;
+mod entry_point;
 mod error;
 #[cfg(feature = "ffi")]
 pub mod ffi;
+mod genesis;
+pub mod global_state_key;
+pub mod key_format;
 pub mod keygen;
+pub mod keystore;
+mod manifest;
+mod message;
+mod mnemonic;
+pub mod output;
 mod parsing;
 mod rpc;
+mod signer;
+mod template;
+pub mod units;
 mod validation;
+mod vanity;
 
 use std::{convert::TryInto, fs, io::Cursor};
 
@@ -33,15 +50,21 @@ use casper_node::{
     rpcs::state::{DictionaryIdentifier, GlobalStateIdentifier},
     types::{BlockHash, Deploy},
 };
-use casper_types::Key;
+use casper_types::{bytesrepr::ToBytes, Key};
 
+pub use batch_transfer::BatchTransferSummary;
 pub use cl_type::help;
 pub use deploy::ListDeploysResult;
-use deploy::{DeployExt, DeployParams, OutputKind};
-pub use error::Error;
+use deploy::{DeployExt, DeployFormat, DeployParams, OutputKind};
+pub use deploy_limits::DeployLimitError;
 use error::Result;
+pub use error::{Error, ErrorCategory};
+pub use manifest::PutDeploysSummary;
+pub use message::MESSAGE_PREFIX;
 pub use rpc::map_hashing_error;
 use rpc::RpcCall;
+pub use signer::{ExecSigner, PemSigner, Signer};
+use template::DeployTemplate;
 pub use validation::ValidateResponseError;
 
 /// Creates a `Deploy` and sends it to the network for execution.
@@ -62,6 +85,14 @@ pub use validation::ValidateResponseError;
 ///   [`SessionStrParams`](struct.SessionStrParams.html) for more details.
 /// * `payment_params` contains payment-related options for this `Deploy`. See
 ///   [`PaymentStrParams`](struct.PaymentStrParams.html) for more details.
+/// * If `check_entry_point` is true, the session must call a stored contract or contract package.
+///   Before the `Deploy` is sent, the contract is looked up in the node's latest global state, and
+///   the session args are checked against the parameters of the called entry point.  Integer args
+///   whose values fit in their parameter's type, including the inner type of an `Option`, are
+///   converted to that type, e.g. a `u64` to a `u512`.  Any other mismatch, or an unexpected or
+///   missing arg, causes
+///   [`Error::EntryPointArgsMismatch`](enum.Error.html#variant.EntryPointArgsMismatch) to be
+///   returned and the `Deploy` not to be sent.
 pub async fn put_deploy(
     maybe_rpc_id: &str,
     node_address: &str,
@@ -69,12 +100,24 @@ pub async fn put_deploy(
     deploy_params: DeployStrParams<'_>,
     session_params: SessionStrParams<'_>,
     payment_params: PaymentStrParams<'_>,
+    check_entry_point: bool,
 ) -> Result<JsonRpc> {
-    let deploy = Deploy::with_payment_and_session(
-        deploy_params.try_into()?,
-        payment_params.try_into()?,
-        session_params.try_into()?,
-    )?;
+    let deploy_params: DeployParams = deploy_params.try_into()?;
+    let mut session: ExecutableDeployItem = session_params.try_into()?;
+    if check_entry_point {
+        let account = match &deploy_params.session_account {
+            Some(account) => account.clone(),
+            None => deploy_params.signer.public_key()?,
+        };
+        let node = entry_point::Node {
+            maybe_rpc_id,
+            node_address,
+            verbosity_level,
+        };
+        session = entry_point::check_session(node, account.to_account_hash(), session).await?;
+    }
+    let deploy =
+        Deploy::with_payment_and_session(deploy_params, payment_params.try_into()?, session)?;
     RpcCall::new(maybe_rpc_id, node_address, verbosity_level)
         .put_deploy(deploy)
         .await
@@ -87,6 +130,9 @@ pub async fn put_deploy(
 /// using [`send_deploy_file()`](fn.send_deploy_file.html).
 ///
 /// * `maybe_output_path` specifies the output file, or if empty, will print it to `stdout`.
+/// * `output_format` specifies the encoding of the output: one of `"json"`, `"binary"` (the
+///   canonical `ToBytes` encoding) or `"base64"` (the base64-encoded `ToBytes` encoding).  If
+///   empty, `"json"` is used.
 /// * `deploy_params` contains deploy-related options for this `Deploy`. See
 ///   [`DeployStrParams`](struct.DeployStrParams.html) for more details.
 /// * `session_params` contains session-related options for this `Deploy`. See
@@ -99,11 +145,13 @@ pub async fn put_deploy(
 ///   will not be written.
 pub fn make_deploy(
     maybe_output_path: &str,
+    output_format: &str,
     deploy_params: DeployStrParams<'_>,
     session_params: SessionStrParams<'_>,
     payment_params: PaymentStrParams<'_>,
     force: bool,
 ) -> Result<()> {
+    let format = parsing::deploy_format(output_format)?;
     let output = if maybe_output_path.is_empty() {
         OutputKind::Stdout
     } else {
@@ -115,17 +163,82 @@ pub fn make_deploy(
         payment_params.try_into()?,
         session_params.try_into()?,
     )?
-    .write_deploy(output.get()?)?;
+    .write_deploy(output.get()?, format)?;
 
     output.commit()
 }
 
+/// Creates a `Deploy` from a template file and outputs it to a file or stdout.
+///
+/// The template is a TOML file, or a JSON file if it has a `.json` extension, with up to three
+/// sections.  `[deploy]` holds header defaults: `chain_name`, `ttl`, `gas_price`, `dependencies`,
+/// `session_account` and `chainspec`.  `[session]` holds `hash`, `name`, `package_hash`,
+/// `package_name`, `path`, `version`, `entry_point`, `transfer` and `args_complex` in the same
+/// form as the corresponding fields of [`SessionStrParams`](struct.SessionStrParams.html), and
+/// `args`, a list of simple args in the form `"NAME:TYPE='VALUE'"`.  `[payment]` holds `amount`
+/// and the same fields as `[session]` except `transfer`.
+///
+/// Any string in the template may contain `${NAME}` placeholders, which are replaced by the value
+/// of the variable `NAME`.  `$$` is replaced by a literal `$`.
+///
+/// * `maybe_output_path` specifies the output file, or if empty, will print it to `stdout`.
+/// * `output_format` specifies the encoding of the output: one of `"json"`, `"binary"` (the
+///   canonical `ToBytes` encoding) or `"base64"` (the base64-encoded `ToBytes` encoding).  If
+///   empty, `"json"` is used.
+/// * `template_path` is the path to the template file.
+/// * `template_vars` are the template variables, each in the form `"NAME=VALUE"`.  Every
+///   placeholder in the template must have a corresponding variable.
+/// * `deploy_params` contains deploy-related options for this `Deploy`.  Each non-empty field
+///   overrides the corresponding value in the template's `[deploy]` section.  If neither specifies
+///   a `ttl` or `gas_price`, `"30min"` and `"1"` respectively are used.  See
+///   [`DeployStrParams`](struct.DeployStrParams.html) for more details.
+/// * If `force` is true, and a file exists at `maybe_output_path`, it will be overwritten. If
+///   `force` is false and a file exists at `maybe_output_path`,
+///   [`Error::FileAlreadyExists`](enum.Error.html#variant.FileAlreadyExists) is returned and a file
+///   will not be written.
+pub fn make_deploy_from_template(
+    maybe_output_path: &str,
+    output_format: &str,
+    template_path: &str,
+    template_vars: Vec<&str>,
+    deploy_params: DeployStrParams<'_>,
+    force: bool,
+) -> Result<()> {
+    let template = DeployTemplate::read(template_path, &template_vars)?;
+    let deploy_params = template.deploy_str_params(deploy_params);
+    if deploy_params.chain_name.is_empty() {
+        return Err(Error::InvalidArgument {
+            context: "chain_name",
+            error: "must be specified by either the template or the deploy params".to_string(),
+        });
+    }
+    make_deploy(
+        maybe_output_path,
+        output_format,
+        deploy_params,
+        template.session_str_params(),
+        template.payment_str_params(),
+        force,
+    )
+}
+
 /// Reads a previously-saved `Deploy` from a file, cryptographically signs it, and outputs it to a
 /// file or stdout.
 ///
-/// * `input_path` specifies the path to the previously-saved `Deploy` file.
-/// * `secret_key` specifies the path to the secret key with which to sign the `Deploy`.
+/// * `input_path` specifies the path to the previously-saved `Deploy` file.  It may be encoded as
+///   JSON, binary or base64; the encoding is detected automatically.
+/// * `secret_key` specifies the path to the secret key file with which to sign the `Deploy`.  It
+///   must be empty if `signer` is provided.
+/// * `signer` specifies the signer to use instead of `secret_key`: `pem:` followed by the path to a
+///   secret key file, or `exec:` followed by the path to an external signing program.  See
+///   [`ExecSigner`](struct.ExecSigner.html) for the protocol such a program must implement.
 /// * `maybe_output_path` specifies the output file, or if empty, will print it to `stdout`.
+/// * `output_format` specifies the encoding of the output: one of `"json"`, `"binary"` (the
+///   canonical `ToBytes` encoding) or `"base64"` (the base64-encoded `ToBytes` encoding).  If
+///   empty, the encoding of the input file is used.
+/// * `chainspec` is the path to a chainspec TOML file or the name of a network preset (only
+///   `"mainnet"`), from which the limits the `Deploy` must satisfy are taken.  An existing file
+///   takes precedence over a preset of the same name.  If empty, the `"mainnet"` limits are used.
 /// * If `force` is true, and a file exists at `maybe_output_path`, it will be overwritten. If
 ///   `force` is false and a file exists at `maybe_output_path`,
 ///   [`Error::FileAlreadyExists`](enum.Error.html#variant.FileAlreadyExists) is returned and a file
@@ -133,10 +246,14 @@ pub fn make_deploy(
 pub fn sign_deploy_file(
     input_path: &str,
     secret_key: &str,
+    signer: &str,
     maybe_output_path: &str,
+    output_format: &str,
+    chainspec: &str,
     force: bool,
 ) -> Result<()> {
-    let secret_key = parsing::secret_key(secret_key)?;
+    let signer = signer::from_args(secret_key, signer)?;
+    let deploy_limits = parsing::deploy_limits(chainspec)?;
 
     let input = fs::read(input_path).map_err(|error| Error::IoError {
         context: format!("unable to read deploy file at '{}'", input_path),
@@ -149,7 +266,19 @@ pub fn sign_deploy_file(
         OutputKind::file(maybe_output_path, force)
     };
 
-    Deploy::sign_and_write_deploy(Cursor::new(input), secret_key, output.get()?)?;
+    let format = if output_format.is_empty() {
+        DeployFormat::detect(&input)
+    } else {
+        parsing::deploy_format(output_format)?
+    };
+
+    Deploy::sign_and_write_deploy(
+        Cursor::new(input),
+        signer.as_ref(),
+        output.get()?,
+        format,
+        &deploy_limits,
+    )?;
 
     output.commit()
 }
@@ -166,18 +295,128 @@ pub fn sign_deploy_file(
 ///   count of the field.  When `verbosity_level` is greater than `1`, the request will be printed
 ///   to `stdout` with no abbreviation of long fields.  When `verbosity_level` is `0`, the request
 ///   will not be printed to `stdout`.
-/// * `input_path` specifies the path to the previously-saved `Deploy` file.
+/// * `input_path` specifies the path to the previously-saved `Deploy` file.  It may be encoded as
+///   JSON, binary or base64; the encoding is detected automatically.
+/// * `chainspec` is the path to a chainspec TOML file or the name of a network preset (only
+///   `"mainnet"`), from which the limits the `Deploy` must satisfy are taken.  An existing file
+///   takes precedence over a preset of the same name.  If empty, the `"mainnet"` limits are used.
 pub async fn send_deploy_file(
     maybe_rpc_id: &str,
     node_address: &str,
     verbosity_level: u64,
     input_path: &str,
+    chainspec: &str,
 ) -> Result<JsonRpc> {
+    let deploy_limits = parsing::deploy_limits(chainspec)?;
     RpcCall::new(maybe_rpc_id, node_address, verbosity_level)
-        .send_deploy_file(input_path)
+        .send_deploy_file(input_path, &deploy_limits)
         .await
 }
 
+/// Signs an arbitrary message, returning the hex-encoded signature with the algorithm tag
+/// prefixed.
+///
+/// The message is prefixed with [`MESSAGE_PREFIX`] before signing, so that the signature can't be
+/// used as an approval of a `Deploy`.
+///
+/// * `message` is the message to sign, e.g. the bytes of a UTF-8 string.
+/// * `secret_key` is the path to the secret key file with which to sign, which may be a keystore.
+pub fn sign_message(message: &[u8], secret_key: &str) -> Result<String> {
+    message::sign_with_key_file(message, secret_key)
+}
+
+/// Verifies a signature produced by [`sign_message()`](fn.sign_message.html).
+///
+/// * `message` is the message which was signed, without [`MESSAGE_PREFIX`].
+/// * `signature` is the hex-encoded signature with the algorithm tag prefixed.
+/// * `public_key` is the hex-encoded public key, or the path to a PEM-encoded public key file.
+///
+/// If the signature is not valid,
+/// [`Error::InvalidSignature`](enum.Error.html#variant.InvalidSignature) is returned.
+pub fn verify_message(message: &[u8], signature: &str, public_key: &str) -> Result<()> {
+    message::verify_with_key(message, signature, public_key)
+}
+
+/// Encodes a value as its `ToBytes` serialized form, returning it hex-encoded.
+///
+/// * `cl_type` is the type of the value, written as for a simple session arg, e.g.
+///   `"map<string,u512>"`. To get a list of supported types, call
+///   [`supported_cl_type_list()`](help/fn.supported_cl_type_list.html).
+/// * `value` is the value, written as for a simple session arg, e.g. `"{a: 1, b: 2}"`. To get a
+///   list of examples, call
+///   [`supported_cl_type_examples()`](help/fn.supported_cl_type_examples.html).
+/// * If `include_type` is true, the whole `CLValue` is serialized, i.e. the length-prefixed value
+///   followed by its type, as is needed for the `raw_bytes` of an arg in a complex args file.
+///   Otherwise only the value itself is serialized.
+pub fn encode_cl_value(cl_type: &str, value: &str, include_type: bool) -> Result<String> {
+    let cl_value = cl_type::encode(cl_type, value)?;
+    if include_type {
+        Ok(base16::encode_lower(&cl_value.to_bytes()?))
+    } else {
+        Ok(base16::encode_lower(cl_value.inner_bytes()))
+    }
+}
+
+/// Decodes a hex-encoded `ToBytes` serialized value, returning it as a `CLValue` in the JSON form
+/// used in a `Deploy`, i.e. with fields `cl_type`, `bytes` and `parsed`.
+///
+/// * `cl_type` is the type of the serialized value, written as for a simple session arg. If empty,
+///   `hex` must instead hold a whole serialized `CLValue`, which carries its own type.
+/// * `hex` is the hex-encoded serialized value.
+///
+/// An error is returned unless `hex` holds exactly one value of the type.
+pub fn decode_cl_value(cl_type: &str, hex: &str) -> Result<serde_json::Value> {
+    let bytes = base16::decode(hex.trim()).map_err(|error| Error::InvalidArgument {
+        context: "hex",
+        error: error.to_string(),
+    })?;
+    let cl_value = cl_type::decode(cl_type, &bytes)?;
+    Ok(serde_json::to_value(&cl_value)?)
+}
+
+/// Writes an `accounts.toml` file defining the genesis accounts, validators and delegators of a
+/// network, in the format expected by `casper-node`.
+///
+/// In each of the specifications below, `KEY` is a directory written by `keygen`, a public key
+/// file, or a hex-encoded public key, and amounts are in motes unless given with a unit suffix,
+/// e.g. `2.5cspr`.
+///
+/// * `accounts` are specifications of funded accounts, each `KEY,BALANCE`.
+/// * `validators` are specifications of genesis validators, each
+///   `KEY,BALANCE,BONDED_AMOUNT[,DELEGATION_RATE]`.  The delegation rate defaults to 0.
+/// * `delegators` are specifications of genesis delegators, each
+///   `KEY,VALIDATOR_KEY,BALANCE,DELEGATED_AMOUNT`.  `VALIDATOR_KEY` must be the key of one of the
+///   `validators`.
+/// * `generated_validators`, if not empty, is a `COUNT,BALANCE,BONDED_AMOUNT[,DELEGATION_RATE]`
+///   specification of further validators whose Ed25519 keys are generated as for
+///   [`keygen::generate_numbered_files`] in `keys_dir`.
+/// * `maybe_output_path` specifies the output file, or if empty, will print it to `stdout`.
+/// * If `force` is true, existing files will be overwritten.
+///
+/// The accounts are checked as `casper-node` would check them at genesis: there must be at least
+/// one validator, no account may be specified twice, bonded and delegated amounts must be
+/// non-zero, delegation rates must not exceed 100, and each delegator must delegate to a genesis
+/// validator at most once.  The delegators are checked before any validator keys are generated.
+pub fn make_genesis_accounts(
+    accounts: &[&str],
+    validators: &[&str],
+    delegators: &[&str],
+    generated_validators: &str,
+    keys_dir: &str,
+    maybe_output_path: &str,
+    force: bool,
+) -> Result<()> {
+    genesis::make_accounts(
+        accounts,
+        validators,
+        delegators,
+        generated_validators,
+        keys_dir,
+        maybe_output_path,
+        force,
+    )
+}
+
 /// Transfers funds between purses.
 ///
 /// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
@@ -190,7 +429,8 @@ pub async fn send_deploy_file(
 ///   count of the field.  When `verbosity_level` is greater than `1`, the request will be printed
 ///   to `stdout` with no abbreviation of long fields.  When `verbosity_level` is `0`, the request
 ///   will not be printed to `stdout`.
-/// * `amount` is a string to be parsed as a `U512` specifying the amount to be transferred.
+/// * `amount` is a string to be parsed as a `U512` specifying the number of motes to be
+///   transferred, optionally with a unit suffix, e.g. `2500000000motes` or `2.5cspr`.
 /// * `target_account` is the `AccountHash`, `URef` or `PublicKey` of the account to which the funds
 ///   will be transferred, formatted as a hex-encoded string. The account's main purse will receive
 ///   the funds.
@@ -223,6 +463,96 @@ pub async fn transfer(
         .await
 }
 
+/// Creates a transfer `Deploy` for each row of a CSV file, signs them with a single key, and either
+/// sends them to the network, tracking each until it has been executed, or writes them to a
+/// directory.  A row of the CSV reconciliation report is written as each transfer completes.
+///
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbosity_level` is `1`, each JSON-RPC request will be printed to `stdout` with long
+///   string fields (e.g. hex-formatted raw Wasm bytes) shortened to a string indicating the char
+///   count of the field.  When `verbosity_level` is greater than `1`, the requests will be printed
+///   to `stdout` with no abbreviation of long fields.  When `verbosity_level` is `0`, the requests
+///   will not be printed to `stdout`.
+/// * `transfers_path` is the path to a CSV file with the header row `target,amount,transfer_id`.
+///   Each `target` is the `AccountHash`, `URef` or `PublicKey` of the account to which the funds
+///   will be transferred, each `amount` is the number of motes to transfer, optionally with a unit
+///   suffix as for `transfer`'s `amount`, and each `transfer_id` is a `u64` user-defined
+///   identifier which will be permanently associated with the transfer.
+/// * `report_path` is the path to which the reconciliation report is written.  A row is written
+///   and flushed as each transfer completes, holding the line number and fields of the input row,
+///   its `status` (one of `invalid`, `written`, `rejected`, `unconfirmed`, `pending`, `succeeded`
+///   or `failed`), and where known its `deploy_hash`, the `block_hash` and `cost` of its
+///   execution, and an `error`.  A transfer is `rejected` only if the node returned an error or
+///   couldn't be reached, and `unconfirmed` if it was sent but no valid response was received.
+///   Before a transfer is sent, a row holding its `deploy_hash` and the status `sending` is
+///   written.  Rows are in order of completion, and a transfer's latest row is its outcome.
+/// * `max_concurrency` is the maximum number of transfers being sent and tracked at any one time.
+///   If `1`, the transfers are sent sequentially in the order given.
+/// * `completion_timeout` is the maximum time to wait for each sent transfer to be executed, e.g.
+///   `"5min"`.  Transfers not executed within this time are reported as `pending`.  If empty,
+///   sent transfers are not tracked and are all reported as `pending`.
+/// * `dry_run_dir` is the path to a directory into which each signed transfer `Deploy` is written
+///   instead of being sent.  If empty, the transfers are sent.
+/// * `dry_run_format` specifies the encoding of the `Deploy`s written to `dry_run_dir`: one of
+///   `"json"`, `"binary"` (the canonical `ToBytes` encoding) or `"base64"` (the base64-encoded
+///   `ToBytes` encoding), written as `<DEPLOY HASH>.json`, `.bin` or `.b64` respectively.  If
+///   empty, `"json"` is used.
+/// * If `resume` is true, transfers recorded as written, pending or succeeded in an existing file
+///   at `report_path` are skipped and new rows are appended to it.  Transfers whose latest row is
+///   still `sending` or is `unconfirmed` are also skipped, as their `Deploy` may have been
+///   accepted by the node, and are counted as unconfirmed in the returned summary.  Their
+///   recorded deploy hashes can be checked with [`get_deploy()`](fn.get_deploy.html).  If a
+///   recorded transfer differs from the row at the same line of `transfers_path`,
+///   [`Error::InvalidArgument`](enum.Error.html#variant.InvalidArgument) is returned.
+/// * If `force` is true and `resume` is false, an existing file at `report_path` will be
+///   overwritten.  If both are false and a file exists at `report_path`,
+///   [`Error::FileAlreadyExists`](enum.Error.html#variant.FileAlreadyExists) is returned.
+/// * `deploy_params` contains deploy-related options applied to every `Deploy`.  Its signer is
+///   constructed once and used to sign every `Deploy`.  See
+///   [`DeployStrParams`](struct.DeployStrParams.html) for more details.
+/// * `payment_params` contains payment-related options applied to every `Deploy`. See
+///   [`PaymentStrParams`](struct.PaymentStrParams.html) for more details.
+///
+/// Failures to create, send or execute an individual transfer are recorded in the report rather
+/// than returned as an error.
+#[allow(clippy::too_many_arguments)]
+pub async fn batch_transfer(
+    node_address: &str,
+    verbosity_level: u64,
+    transfers_path: &str,
+    report_path: &str,
+    max_concurrency: usize,
+    completion_timeout: &str,
+    dry_run_dir: &str,
+    dry_run_format: &str,
+    resume: bool,
+    force: bool,
+    deploy_params: DeployStrParams<'_>,
+    payment_params: PaymentStrParams<'_>,
+) -> Result<BatchTransferSummary> {
+    let format = parsing::deploy_format(dry_run_format)?;
+    let dry_run = if dry_run_dir.is_empty() {
+        None
+    } else {
+        Some((dry_run_dir, format))
+    };
+    batch_transfer::batch_transfer(
+        node_address,
+        verbosity_level,
+        transfers_path,
+        report_path,
+        max_concurrency,
+        completion_timeout,
+        dry_run,
+        resume,
+        force,
+        deploy_params,
+        payment_params.try_into()?,
+    )
+    .await
+}
+
 /// Creates a transfer `Deploy` and outputs it to a file or stdout.
 ///
 /// As a file, the transfer `Deploy` can subsequently be signed by other parties using
@@ -230,7 +560,11 @@ pub async fn transfer(
 /// using [`send_deploy_file()`](fn.send_deploy_file.html).
 ///
 /// * `maybe_output_path` specifies the output file, or if empty, will print it to `stdout`.
-/// * `amount` is a string to be parsed as a `U512` specifying the amount to be transferred.
+/// * `output_format` specifies the encoding of the output: one of `"json"`, `"binary"` (the
+///   canonical `ToBytes` encoding) or `"base64"` (the base64-encoded `ToBytes` encoding).  If
+///   empty, `"json"` is used.
+/// * `amount` is a string to be parsed as a `U512` specifying the number of motes to be
+///   transferred, optionally with a unit suffix, e.g. `2500000000motes` or `2.5cspr`.
 /// * `target_account` is the `AccountHash`, `URef` or `PublicKey` of the account to which the funds
 ///   will be transferred, formatted as a hex-encoded string. The account's main purse will receive
 ///   the funds.
@@ -244,8 +578,10 @@ pub async fn transfer(
 ///   `force` is false and a file exists at `maybe_output_path`,
 ///   [`Error::FileAlreadyExists`](enum.Error.html#variant.FileAlreadyExists) is returned and a file
 ///   will not be written.
+#[allow(clippy::too_many_arguments)]
 pub fn make_transfer(
     maybe_output_path: &str,
+    output_format: &str,
     amount: &str,
     target_account: &str,
     transfer_id: &str,
@@ -253,6 +589,7 @@ pub fn make_transfer(
     payment_params: PaymentStrParams<'_>,
     force: bool,
 ) -> Result<()> {
+    let format = parsing::deploy_format(output_format)?;
     let output = if maybe_output_path.is_empty() {
         OutputKind::Stdout
     } else {
@@ -267,11 +604,76 @@ pub fn make_transfer(
         deploy_params.try_into()?,
         payment_params.try_into()?,
     )?
-    .write_deploy(output.get()?)?;
+    .write_deploy(output.get()?, format)?;
 
     output.commit()
 }
 
+/// Creates a `Deploy` for each entry of a manifest file and sends them to the network for
+/// execution.
+///
+/// Each entry describes the session and payment of one `Deploy` using the same fields as
+/// [`SessionStrParams`](struct.SessionStrParams.html) and
+/// [`PaymentStrParams`](struct.PaymentStrParams.html), e.g. `session_name`, `session_entry_point`,
+/// `session_args_simple` or `payment_amount`.  An entry may also have an `id` to identify it in the
+/// results file; if omitted, its zero-based position in the manifest is used.
+///
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbosity_level` is `1`, each JSON-RPC request will be printed to `stdout` with long
+///   string fields (e.g. hex-formatted raw Wasm bytes) shortened to a string indicating the char
+///   count of the field.  When `verbosity_level` is greater than `1`, the requests will be printed
+///   to `stdout` with no abbreviation of long fields.  When `verbosity_level` is `0`, the requests
+///   will not be printed to `stdout`.
+/// * `manifest_path` is the path to the manifest.  If it has a `.csv` extension it is parsed as CSV
+///   with a header row naming the fields, where multiple simple args in one field are separated by
+///   `;`.  A `;` or `\` within an arg must be escaped as `\;` or `\\` respectively.  Otherwise
+///   it is parsed as a JSON array of objects.
+/// * `results_path` is the path to the results file.  One JSON object is appended per line as each
+///   entry completes, holding the entry's `id` and its `deploy_hash` and/or an `error`.  Before a
+///   `Deploy` is sent, a line holding its `deploy_hash` and `"sending": true` is appended.  If
+///   sending fails, the line also holds `"rejected": true` when the node definitely didn't accept
+///   the `Deploy`, e.g. it returned an error or couldn't be reached.  Otherwise, e.g. if the
+///   request timed out, the `Deploy` is unconfirmed as the node may have accepted it.
+/// * `max_concurrency` is the maximum number of deploys being sent at any one time.
+/// * If `resume` is true, entries recorded as successfully sent in an existing file at
+///   `results_path` are skipped and new results are appended to it.  Entries whose latest record
+///   is still `"sending"` or unconfirmed are also skipped, as their `Deploy` may have been accepted
+///   by the node, and are counted as unconfirmed in the returned summary.  Their deploy hashes can
+///   be checked with [`get_deploy()`](fn.get_deploy.html).
+/// * If `force` is true and `resume` is false, an existing file at `results_path` will be
+///   overwritten.  If both are false and a file exists at `results_path`,
+///   [`Error::FileAlreadyExists`](enum.Error.html#variant.FileAlreadyExists) is returned.
+/// * `deploy_params` contains deploy-related options applied to every `Deploy`.  Its signer is
+///   constructed once and used to sign every `Deploy`.  See
+///   [`DeployStrParams`](struct.DeployStrParams.html) for more details.
+///
+/// Failures to create or send an individual `Deploy` are recorded in the results file rather than
+/// returned as an error.
+#[allow(clippy::too_many_arguments)]
+pub async fn put_deploys(
+    node_address: &str,
+    verbosity_level: u64,
+    manifest_path: &str,
+    results_path: &str,
+    max_concurrency: usize,
+    resume: bool,
+    force: bool,
+    deploy_params: DeployStrParams<'_>,
+) -> Result<PutDeploysSummary> {
+    manifest::put_deploys(
+        node_address,
+        verbosity_level,
+        manifest_path,
+        results_path,
+        max_concurrency,
+        resume,
+        force,
+        deploy_params,
+    )
+    .await
+}
+
 /// Retrieves a `Deploy` from the network.
 ///
 /// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
@@ -395,12 +797,16 @@ pub async fn get_state_root_hash(
 /// transfer-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20      # Key::Transfer
 /// deploy-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20        # Key::DeployInfo
 /// era-1                                                                          # Key::EraInfo
+/// balance-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20       # Key::Balance
 /// bid-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20           # Key::Bid
 /// withdraw-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20      # Key::Withdraw
 /// dictionary-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20    # Key::Dictionary
 /// The Key::SystemContractRegistry variant is unique and can only take the following value:
 /// system-contract-registry-0000000000000000000000000000000000000000000000000000000000000000
 /// ```
+///   Keys with the prefixes `era-summary-` and `chainspec-registry-` are only supported by later
+///   versions of the network.  To describe any key, call
+///   [`global_state_key::parse`](global_state_key/fn.parse.html).
 /// * `path` is comprised of components starting from the `key`, separated by `/`s.
 #[deprecated(note = "Users should use `casper_client::query_global_state` instead.")]
 pub async fn get_item(
@@ -549,12 +955,16 @@ pub async fn get_account_info(
 /// transfer-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20      # Key::Transfer
 /// deploy-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20        # Key::DeployInfo
 /// era-1                                                                          # Key::EraInfo
+/// balance-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20       # Key::Balance
 /// bid-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20           # Key::Bid
 /// withdraw-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20      # Key::Withdraw
 /// dictionary-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20    # Key::Dictionary
 /// The Key::SystemContractRegistry variant is unique and can only take the following value:
 /// system-contract-registry-0000000000000000000000000000000000000000000000000000000000000000
 /// ```
+///   Keys with the prefixes `era-summary-` and `chainspec-registry-` are only supported by later
+///   versions of the network.  To describe any key, call
+///   [`global_state_key::parse`](global_state_key/fn.parse.html).
 /// * `path` is comprised of components starting from the `key`, separated by `/`s.
 pub async fn query_global_state(
     maybe_rpc_id: &str,
@@ -640,10 +1050,17 @@ pub async fn get_validator_changes(
 }
 
 /// Container for `Deploy` construction options.
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 pub struct DeployStrParams<'a> {
-    /// Path to secret key file.
+    /// The path to the secret key file with which to sign the `Deploy`.
+    ///
+    /// Must be empty if `signer` is provided.
     pub secret_key: &'a str,
+    /// The signer of the `Deploy`, used instead of `secret_key`: `pem:` followed by the path to a
+    /// secret key file, or `exec:` followed by the path to an external signing program.
+    ///
+    /// See [`ExecSigner`](struct.ExecSigner.html) for the protocol such a program must implement.
+    pub signer: &'a str,
     /// RFC3339-like formatted timestamp. e.g. `2018-02-16T00:31:37Z`.
     ///
     /// If `timestamp` is empty, the current time will be used. Note that timestamp is UTC, not
@@ -672,6 +1089,36 @@ pub struct DeployStrParams<'a> {
     /// The hex-encoded public key of the account context under which the session code will be
     /// executed.
     pub session_account: &'a str,
+    /// The path to a chainspec TOML file or the name of a network preset (only `"mainnet"`), from
+    /// which the limits the `Deploy` must satisfy are taken.  An existing file takes precedence
+    /// over a preset of the same name.
+    ///
+    /// If `chainspec` is empty, the `"mainnet"` limits will be used.
+    pub chainspec: &'a str,
+}
+
+impl<'a> DeployStrParams<'a> {
+    /// Returns the signer given by `secret_key` or `signer`.
+    pub(crate) fn signer(&self) -> Result<Box<dyn Signer>> {
+        signer::from_args(self.secret_key, self.signer)
+    }
+
+    /// Parses these params using `signer` in place of the one given by `secret_key` or `signer`.
+    ///
+    /// This allows a signer to be constructed once via [`Self::signer`] and shared by many
+    /// `Deploy`s.
+    pub(crate) fn with_signer(self, signer: Box<dyn Signer>) -> Result<DeployParams> {
+        parsing::parse_deploy_params_with_signer(
+            signer,
+            self.timestamp,
+            self.ttl,
+            self.gas_price,
+            &self.dependencies,
+            self.chain_name,
+            self.session_account,
+            self.chainspec,
+        )
+    }
 }
 
 impl<'a> TryInto<DeployParams> for DeployStrParams<'a> {
@@ -680,21 +1127,25 @@ impl<'a> TryInto<DeployParams> for DeployStrParams<'a> {
     fn try_into(self) -> Result<DeployParams> {
         let DeployStrParams {
             secret_key,
+            signer,
             timestamp,
             ttl,
             gas_price,
             dependencies,
             chain_name,
             session_account,
+            chainspec,
         } = self;
         parsing::parse_deploy_params(
             secret_key,
+            signer,
             timestamp,
             ttl,
             gas_price,
             &dependencies,
             chain_name,
             session_account,
+            chainspec,
         )
     }
 }
@@ -706,8 +1157,19 @@ impl<'a> TryInto<DeployParams> for DeployStrParams<'a> {
 /// For methods taking `payment_args_simple`, this parameter is the payment contract arguments, in
 /// the form `<NAME:TYPE='VALUE'>` or `<NAME:TYPE=null>`.
 ///
-/// It can only be used with the following simple `CLType`s: bool, i32, i64, u8, u32, u64, u128,
-/// u256, u512, unit, string, key, account_hash, uref, public_key and `Option` of each of these.
+/// The value may instead be surrounded by double quotes, inside which `\"`, `\\`, `\n`, `\r` and
+/// `\t` are escape sequences, e.g. `msg:string="{\"a\": \"it's\"}"`.  A single-quoted value runs
+/// verbatim to the last `'` in the string, so needs no escaping.  Several args may be given in one
+/// string, separated by commas after a double-quoted or `null` value, e.g.
+/// `a:u8="1", b:string='x'`.  An invalid arg is reported as an [`Error::InvalidSimpleArg`] giving
+/// the position of the offending character.
+///
+/// The type may be any of the simple `CLType`s bool, i32, i64, u8, u32, u64, u128, u256, u512,
+/// unit, string, bytes, byte_array_N, key, account_hash, uref and public_key, or a compound type
+/// built from them and nested to any depth: `option<T>`, `list<T>`, `map<K,V>`, `tuple1<T1>`,
+/// `tuple2<T1,T2>`, `tuple3<T1,T2,T3>` or `result<T,E>`.  A `bytes` or `byte_array_N` value is
+/// written in hex, or may instead be written unquoted as `@` followed by the path of a file holding
+/// the bytes.
 ///
 /// Example inputs are:
 ///
@@ -723,9 +1185,19 @@ impl<'a> TryInto<DeployParams> for DeployStrParams<'a> {
 /// name_09:u512='8'
 /// name_10:unit=''
 /// name_11:string='a value'
+/// name_12:bytes='0a0b0c0d'
+/// name_13:bytes=@path/to/file
 /// key_account_name:key='account-hash-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20'
 /// key_hash_name:key='hash-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20'
 /// key_uref_name:key='uref-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20-000'
+/// key_transfer_name:key='transfer-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20'
+/// key_deploy_name:key='deploy-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20'
+/// key_era_name:key='era-1'
+/// key_balance_name:key='balance-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20'
+/// key_bid_name:key='bid-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20'
+/// key_withdraw_name:key='withdraw-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20'
+/// key_dictionary_name:key='dictionary-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20'
+/// key_system_contract_registry_name:key='system-contract-registry-0000000000000000000000000000000000000000000000000000000000000000'
 /// account_hash_name:account_hash='account-hash-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20'
 /// uref_name:uref='uref-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20-007'
 /// public_key_name:public_key='0119bf44096984cdfe8541bac167dc3b96c85086aa30b6b6cb0c5c38ad703166e1'
@@ -747,6 +1219,24 @@ impl<'a> TryInto<DeployParams> for DeployStrParams<'a> {
 /// name_10:opt_string=null       # None
 /// ```
 ///
+/// Within a compound value, lists are written as `[...]`, maps as `{KEY: VALUE, ...}`, tuples as
+/// `(...)` and results as `ok(...)` or `err(...)`, with elements separated by commas, and `null` is
+/// the `None` of an `option<T>`.  A string inside a compound value which contains any of
+/// `,:()[]{}<>"` must be double-quoted, with `"` and `\` escaped by a backslash.  A map's entries may
+/// be given in any order, and are serialized in the order of their keys, as for a `BTreeMap`:
+///
+/// ```text
+/// name_01:list<u64>='[1, 2, 3]'
+/// name_02:map<string,u64>='{bob: 2, alice: 1}'
+/// name_03:byte_array_4='0a0b0c0d'
+/// name_04:tuple2<u8,string>='(1, "a, b")'
+/// name_05:option<list<u256>>='[1, 2]'     # Some(vec![1, 2])
+/// name_06:option<list<u256>>=null         # None
+/// name_07:list<option<u8>>='[1, null]'    # vec![Some(1), None]
+/// name_08:result<u64,string>='ok(5)'      # Ok(5)
+/// name_09:result<u64,string>='err(failed)' # Err("failed".to_string())
+/// ```
+///
 /// To get a list of supported types, call
 /// [`supported_cl_type_list()`](help/fn.supported_cl_type_list.html). To get this list of examples
 /// for supported types, call
@@ -754,8 +1244,14 @@ impl<'a> TryInto<DeployParams> for DeployStrParams<'a> {
 ///
 /// ## `payment_args_complex`
 ///
-/// For methods taking `payment_args_complex`, this parameter is the payment contract arguments, in
-/// the form of a `ToBytes`-encoded file.
+/// For methods taking `payment_args_complex`, this parameter is the path to a JSON, TOML or YAML
+/// file holding the payment contract arguments, or `"-"` to read them from stdin. To get an example
+/// of such a file, call [`complex_arg_examples()`](help/fn.complex_arg_examples.html).
+///
+/// A `bytes` or `byte_array_N` value of the form `"@path/to/file"` is replaced by the contents of
+/// that file, read from disk, so an args file from an untrusted source may include any file the
+/// caller can read in the deploy.  A relative path is resolved against the directory of the args
+/// file, or against the current directory when reading from stdin.
 ///
 /// ---
 ///
@@ -805,7 +1301,8 @@ impl<'a> PaymentStrParams<'a> {
     /// Constructs a `PaymentStrParams` using a payment amount.
     ///
     /// `payment_amount` uses the standard-payment system contract rather than custom payment Wasm.
-    /// The value is the 'amount' arg of the standard-payment contract.
+    /// The value is the 'amount' arg of the standard-payment contract, a number of motes optionally
+    /// with a unit suffix, e.g. `2500000000motes` or `2.5cspr`.
     pub fn with_amount(payment_amount: &'a str) -> Self {
         Self {
             payment_amount,
@@ -933,8 +1430,14 @@ impl<'a> TryInto<ExecutableDeployItem> for SessionStrParams<'a> {
 ///
 /// ## `session_args_complex`
 ///
-/// For methods taking `session_args_complex`, this parameter is the session contract arguments, in
-/// the form of a `ToBytes`-encoded file.
+/// For methods taking `session_args_complex`, this parameter is the path to a JSON, TOML or YAML
+/// file holding the session contract arguments, or `"-"` to read them from stdin. To get an example
+/// of such a file, call [`complex_arg_examples()`](help/fn.complex_arg_examples.html).
+///
+/// A `bytes` or `byte_array_N` value of the form `"@path/to/file"` is replaced by the contents of
+/// that file, read from disk, so an args file from an untrusted source may include any file the
+/// caller can read in the deploy.  A relative path is resolved against the directory of the args
+/// file, or against the current directory when reading from stdin.
 ///
 /// ---
 ///
@@ -1472,7 +1975,7 @@ mod param_tests {
             let mut params = test_value();
             params.chain_name = "";
             let result: StdResult<DeployParams, Error> = params.try_into();
-            assert!(matches!(result, Ok(_)));
+            assert!(result.is_ok());
         }
 
         #[test]
@@ -0,0 +1,566 @@
+//! Support for creating and sending many `Deploy`s described by a manifest file.
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    convert::TryInto,
+    fs::{self, File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+
+use casper_node::types::Deploy;
+
+use crate::{
+    deploy::DeployExt,
+    error::{Error, Result},
+    rpc::RpcCall,
+    DeployStrParams, PaymentStrParams, SessionStrParams, Signer,
+};
+
+/// The separator used between multiple simple args in a single CSV manifest field.
+const CSV_ARGS_SIMPLE_SEPARATOR: char = ';';
+/// The char used in a CSV manifest field to escape a literal separator or escape char.
+const CSV_ARGS_SIMPLE_ESCAPE: char = '\\';
+
+/// A single entry of a manifest, describing the session and payment of one `Deploy`.
+///
+/// The field names match those of [`SessionStrParams`] and [`PaymentStrParams`].
+#[derive(Default, Deserialize, Debug)]
+#[serde(default, deny_unknown_fields)]
+struct ManifestEntry {
+    id: String,
+    session_hash: String,
+    session_name: String,
+    session_package_hash: String,
+    session_package_name: String,
+    session_path: String,
+    session_args_simple: Vec<String>,
+    session_args_complex: String,
+    session_version: String,
+    session_entry_point: String,
+    is_session_transfer: bool,
+    payment_amount: String,
+    payment_hash: String,
+    payment_name: String,
+    payment_package_hash: String,
+    payment_package_name: String,
+    payment_path: String,
+    payment_args_simple: Vec<String>,
+    payment_args_complex: String,
+    payment_version: String,
+    payment_entry_point: String,
+}
+
+/// Splits a CSV field holding multiple simple args on [`CSV_ARGS_SIMPLE_SEPARATOR`].
+///
+/// A separator or escape char preceded by [`CSV_ARGS_SIMPLE_ESCAPE`] is kept as a literal char of
+/// the current arg, so e.g. `name:string='a\;b'` is the single arg `name:string='a;b'`.  Any other
+/// escape char is kept as is.
+fn split_args_simple(value: &str) -> Vec<String> {
+    let mut args = vec![];
+    let mut current = String::new();
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            CSV_ARGS_SIMPLE_ESCAPE => match chars.peek() {
+                Some(&next @ (CSV_ARGS_SIMPLE_SEPARATOR | CSV_ARGS_SIMPLE_ESCAPE)) => {
+                    current.push(next);
+                    chars.next();
+                }
+                _ => current.push(c),
+            },
+            CSV_ARGS_SIMPLE_SEPARATOR => args.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    args.push(current);
+    args.into_iter()
+        .map(|arg| arg.trim().to_string())
+        .filter(|arg| !arg.is_empty())
+        .collect()
+}
+
+impl ManifestEntry {
+    /// Builds an entry from a CSV record keyed by the column headers.
+    fn from_csv_record(mut record: BTreeMap<String, String>) -> std::result::Result<Self, String> {
+        let mut take = |column: &str| record.remove(column).unwrap_or_default();
+        let is_session_transfer = match take("is_session_transfer").trim() {
+            "" | "false" => false,
+            "true" => true,
+            other => {
+                return Err(format!(
+                    "invalid value '{}' for is_session_transfer, expected 'true' or 'false'",
+                    other
+                ))
+            }
+        };
+        let entry = ManifestEntry {
+            id: take("id"),
+            session_hash: take("session_hash"),
+            session_name: take("session_name"),
+            session_package_hash: take("session_package_hash"),
+            session_package_name: take("session_package_name"),
+            session_path: take("session_path"),
+            session_args_simple: split_args_simple(&take("session_args_simple")),
+            session_args_complex: take("session_args_complex"),
+            session_version: take("session_version"),
+            session_entry_point: take("session_entry_point"),
+            is_session_transfer,
+            payment_amount: take("payment_amount"),
+            payment_hash: take("payment_hash"),
+            payment_name: take("payment_name"),
+            payment_package_hash: take("payment_package_hash"),
+            payment_package_name: take("payment_package_name"),
+            payment_path: take("payment_path"),
+            payment_args_simple: split_args_simple(&take("payment_args_simple")),
+            payment_args_complex: take("payment_args_complex"),
+            payment_version: take("payment_version"),
+            payment_entry_point: take("payment_entry_point"),
+        };
+        match record.keys().next() {
+            Some(unknown) => Err(format!("unknown column '{}'", unknown)),
+            None => Ok(entry),
+        }
+    }
+
+    fn session_str_params(&self) -> SessionStrParams<'_> {
+        SessionStrParams {
+            session_hash: &self.session_hash,
+            session_name: &self.session_name,
+            session_package_hash: &self.session_package_hash,
+            session_package_name: &self.session_package_name,
+            session_path: &self.session_path,
+            session_args_simple: self
+                .session_args_simple
+                .iter()
+                .map(String::as_str)
+                .collect(),
+            session_args_complex: &self.session_args_complex,
+            session_version: &self.session_version,
+            session_entry_point: &self.session_entry_point,
+            is_session_transfer: self.is_session_transfer,
+        }
+    }
+
+    fn payment_str_params(&self) -> PaymentStrParams<'_> {
+        PaymentStrParams {
+            payment_amount: &self.payment_amount,
+            payment_hash: &self.payment_hash,
+            payment_name: &self.payment_name,
+            payment_package_hash: &self.payment_package_hash,
+            payment_package_name: &self.payment_package_name,
+            payment_path: &self.payment_path,
+            payment_args_simple: self
+                .payment_args_simple
+                .iter()
+                .map(String::as_str)
+                .collect(),
+            payment_args_complex: &self.payment_args_complex,
+            payment_version: &self.payment_version,
+            payment_entry_point: &self.payment_entry_point,
+        }
+    }
+
+    fn make_deploy(
+        &self,
+        deploy_params: DeployStrParams<'_>,
+        signer: &Arc<dyn Signer>,
+    ) -> Result<Deploy> {
+        Deploy::with_payment_and_session(
+            deploy_params.with_signer(Box::new(Arc::clone(signer)))?,
+            self.payment_str_params().try_into()?,
+            self.session_str_params().try_into()?,
+        )
+    }
+}
+
+/// The outcome of sending the `Deploy` for a single manifest entry, as recorded in the results
+/// file.
+///
+/// A record with `sending` set is written before the `Deploy` is sent, so that if the run is
+/// interrupted before the outcome is recorded, the `Deploy` is not sent again on resuming.
+///
+/// A record with both a `deploy_hash` and an `error` has `rejected` set only if the node definitely
+/// didn't accept the `Deploy`.  Otherwise the `Deploy` may have been accepted despite the error, so
+/// it is not sent again on resuming.
+#[derive(Serialize, Deserialize, Debug)]
+struct EntryResult {
+    id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    deploy_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    sending: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    rejected: bool,
+}
+
+impl EntryResult {
+    /// Returns the state of the entry according to this record.
+    fn state(&self) -> EntryState {
+        match (&self.deploy_hash, &self.error) {
+            _ if self.sending => EntryState::Unconfirmed,
+            (Some(_), None) => EntryState::Sent,
+            (Some(_), Some(_)) if !self.rejected => EntryState::Unconfirmed,
+            _ => EntryState::Failed,
+        }
+    }
+}
+
+/// The state of a manifest entry according to an existing results file.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum EntryState {
+    /// The entry's `Deploy` was accepted by the node.
+    Sent,
+    /// The entry's `Deploy` was sent, but whether the node accepted it is unknown, either because
+    /// its outcome was never recorded or because no valid response was received.
+    Unconfirmed,
+    /// The entry failed, so should be retried.
+    Failed,
+}
+
+/// A summary of a [`put_deploys()`](crate::put_deploys) run.
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct PutDeploysSummary {
+    /// The number of entries whose `Deploy` was accepted by the node.
+    pub succeeded: usize,
+    /// The number of entries which failed, either while creating their `Deploy` or because the node
+    /// rejected it.
+    pub failed: usize,
+    /// The number of entries skipped because a previous run had already sent them successfully.
+    pub skipped: usize,
+    /// The number of entries whose `Deploy` was sent, in this or a previous run, without a valid
+    /// response being received, so whether the node accepted it is unknown.  Their deploy hashes
+    /// are recorded in the results file, so can be checked with `get_deploy`.
+    pub unconfirmed: usize,
+}
+
+fn manifest_error(path: &str, error: impl ToString) -> Error {
+    Error::FailedToParseManifest {
+        context: path.to_string(),
+        error: error.to_string(),
+    }
+}
+
+/// Reads the manifest at `path`, parsing it as CSV if it has a `.csv` extension, otherwise as a
+/// JSON array.
+///
+/// Entries without an `id` are assigned their zero-based position in the manifest.
+fn read_manifest(path: &str) -> Result<Vec<ManifestEntry>> {
+    let is_csv = Path::new(path)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("csv"));
+
+    let mut entries: Vec<ManifestEntry> = if is_csv {
+        let mut reader =
+            csv::Reader::from_path(path).map_err(|error| manifest_error(path, error))?;
+        reader
+            .deserialize::<BTreeMap<String, String>>()
+            .enumerate()
+            .map(|(index, maybe_record)| {
+                let record = maybe_record.map_err(|error| manifest_error(path, error))?;
+                ManifestEntry::from_csv_record(record).map_err(|error| {
+                    // Add 2 to the index to account for the header row and 1-based line numbers.
+                    manifest_error(path, format!("line {}: {}", index + 2, error))
+                })
+            })
+            .collect::<Result<_>>()?
+    } else {
+        let contents = fs::read(path).map_err(|error| Error::IoError {
+            context: format!("unable to read manifest file at '{}'", path),
+            error,
+        })?;
+        serde_json::from_slice(&contents).map_err(|error| manifest_error(path, error))?
+    };
+
+    let mut ids = HashSet::new();
+    for (index, entry) in entries.iter_mut().enumerate() {
+        if entry.id.is_empty() {
+            entry.id = index.to_string();
+        }
+        if !ids.insert(entry.id.clone()) {
+            return Err(manifest_error(path, format!("duplicate id '{}'", entry.id)));
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Returns the state of each entry recorded in an existing results file, as given by its latest
+/// record.
+fn read_entry_states(results_path: &str) -> Result<BTreeMap<String, EntryState>> {
+    let file = match File::open(results_path) {
+        Ok(file) => file,
+        Err(_) if !Path::new(results_path).exists() => return Ok(BTreeMap::new()),
+        Err(error) => {
+            return Err(Error::IoError {
+                context: format!("unable to read results file at '{}'", results_path),
+                error,
+            })
+        }
+    };
+
+    let mut states = BTreeMap::new();
+    for line in BufReader::new(file).lines() {
+        let line = line.map_err(|error| Error::IoError {
+            context: format!("unable to read results file at '{}'", results_path),
+            error,
+        })?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let result: EntryResult = serde_json::from_str(&line)?;
+        states.insert(result.id.clone(), result.state());
+    }
+    Ok(states)
+}
+
+/// Creates and sends a `Deploy` for every entry in the manifest at `manifest_path`.
+///
+/// See [`put_deploys()`](crate::put_deploys) for details of the parameters.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn put_deploys(
+    node_address: &str,
+    verbosity_level: u64,
+    manifest_path: &str,
+    results_path: &str,
+    max_concurrency: usize,
+    resume: bool,
+    force: bool,
+    deploy_params: DeployStrParams<'_>,
+) -> Result<PutDeploysSummary> {
+    if max_concurrency == 0 {
+        return Err(Error::InvalidArgument {
+            context: "max_concurrency",
+            error: "must be greater than 0".to_string(),
+        });
+    }
+
+    let entries = read_manifest(manifest_path)?;
+    // The signer is constructed once, so e.g. an encrypted secret key is only decrypted once.
+    let signer: Arc<dyn Signer> = Arc::from(deploy_params.signer()?);
+
+    let states = if resume {
+        read_entry_states(results_path)?
+    } else {
+        if Path::new(results_path).exists() && !force {
+            return Err(Error::FileAlreadyExists(results_path.into()));
+        }
+        BTreeMap::new()
+    };
+
+    let results_file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(resume)
+        .truncate(!resume)
+        .open(results_path)
+        .map_err(|error| Error::IoError {
+            context: format!("unable to open results file at '{}'", results_path),
+            error,
+        })?;
+
+    let results_file = Mutex::new(results_file);
+    let write_result = |result: &EntryResult| -> Result<()> {
+        let line = serde_json::to_string(result)?;
+        let mut results_file = results_file.lock().expect("should lock results file");
+        writeln!(results_file, "{}", line)
+            .and_then(|_| results_file.flush())
+            .map_err(|error| Error::IoError {
+                context: format!("unable to write results file at '{}'", results_path),
+                error,
+            })
+    };
+
+    let mut summary = PutDeploysSummary::default();
+    let mut pending = vec![];
+    for entry in entries {
+        match states.get(&entry.id) {
+            Some(EntryState::Sent) => summary.skipped += 1,
+            Some(EntryState::Unconfirmed) => summary.unconfirmed += 1,
+            Some(EntryState::Failed) | None => pending.push(entry),
+        }
+    }
+
+    let pending = pending.into_iter().map(|entry| {
+        send_entry(
+            node_address,
+            verbosity_level,
+            entry,
+            deploy_params.clone(),
+            &signer,
+            &write_result,
+        )
+    });
+
+    let mut results = stream::iter(pending).buffer_unordered(max_concurrency);
+    while let Some(maybe_result) = results.next().await {
+        let result = maybe_result?;
+        match result.state() {
+            EntryState::Sent => summary.succeeded += 1,
+            EntryState::Unconfirmed => summary.unconfirmed += 1,
+            EntryState::Failed => summary.failed += 1,
+        }
+        write_result(&result)?;
+    }
+
+    Ok(summary)
+}
+
+/// Creates and sends the `Deploy` for `entry`, first recording its deploy hash via `write_result`.
+async fn send_entry(
+    node_address: &str,
+    verbosity_level: u64,
+    entry: ManifestEntry,
+    deploy_params: DeployStrParams<'_>,
+    signer: &Arc<dyn Signer>,
+    write_result: &impl Fn(&EntryResult) -> Result<()>,
+) -> Result<EntryResult> {
+    let deploy = match entry.make_deploy(deploy_params, signer) {
+        Ok(deploy) => deploy,
+        Err(error) => {
+            return Ok(EntryResult {
+                id: entry.id,
+                deploy_hash: None,
+                error: Some(error.to_string()),
+                sending: false,
+                rejected: false,
+            })
+        }
+    };
+    let deploy_hash = Some(base16::encode_lower(deploy.id()));
+    write_result(&EntryResult {
+        id: entry.id.clone(),
+        deploy_hash: deploy_hash.clone(),
+        error: None,
+        sending: true,
+        rejected: false,
+    })?;
+    let outcome = RpcCall::new("", node_address, verbosity_level)
+        .put_deploy(deploy)
+        .await;
+    let rejected = matches!(&outcome, Err(error) if error.is_definite_rejection());
+    Ok(EntryResult {
+        id: entry.id,
+        deploy_hash,
+        error: outcome.err().map(|error| error.to_string()),
+        sending: false,
+        rejected,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    const JSON_MANIFEST: &str = r#"[
+        {
+            "id": "alice",
+            "session_name": "airdrop",
+            "session_entry_point": "claim",
+            "session_args_simple": ["recipient:account_hash='account-hash-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20'"],
+            "payment_amount": "100000"
+        },
+        {
+            "session_name": "airdrop",
+            "session_entry_point": "claim",
+            "payment_amount": "100000"
+        }
+    ]"#;
+
+    const CSV_MANIFEST: &str = "\
+id,session_name,session_entry_point,session_args_simple,payment_amount
+alice,airdrop,claim,name_01:bool='false';name_02:i32='42',100000
+,airdrop,claim,,100000
+";
+
+    fn write_manifest(temp_dir: &TempDir, file_name: &str, contents: &str) -> String {
+        let path = temp_dir.path().join(file_name);
+        fs::write(&path, contents).unwrap();
+        path.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn should_read_json_manifest() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = write_manifest(&temp_dir, "manifest.json", JSON_MANIFEST);
+        let entries = read_manifest(&path).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].id, "alice");
+        assert_eq!(entries[0].session_args_simple.len(), 1);
+        assert_eq!(entries[1].id, "1");
+        assert_eq!(entries[1].payment_amount, "100000");
+    }
+
+    #[test]
+    fn should_read_csv_manifest() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = write_manifest(&temp_dir, "manifest.csv", CSV_MANIFEST);
+        let entries = read_manifest(&path).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].id, "alice");
+        assert_eq!(
+            entries[0].session_args_simple,
+            vec!["name_01:bool='false'", "name_02:i32='42'"]
+        );
+        assert_eq!(entries[1].id, "1");
+        assert!(entries[1].session_args_simple.is_empty());
+    }
+
+    #[test]
+    fn should_split_csv_args_simple_on_unescaped_separators() {
+        assert_eq!(
+            split_args_simple(r"a:string='x\;y'; b:string='\\'\;;c:string='C:\dir'"),
+            vec![r"a:string='x;y'", r"b:string='\';", r"c:string='C:\dir'"]
+        );
+        assert!(split_args_simple(" ; ").is_empty());
+    }
+
+    #[test]
+    fn should_fail_to_read_manifest_with_unknown_csv_column() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = write_manifest(&temp_dir, "manifest.csv", "id,sesion_name\na,b\n");
+        assert!(matches!(
+            read_manifest(&path),
+            Err(Error::FailedToParseManifest { error, .. }) if error.contains("sesion_name")
+        ));
+    }
+
+    #[test]
+    fn should_fail_to_read_manifest_with_duplicate_ids() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = write_manifest(&temp_dir, "manifest.json", r#"[{"id": "a"}, {"id": "a"}]"#);
+        assert!(matches!(
+            read_manifest(&path),
+            Err(Error::FailedToParseManifest { .. })
+        ));
+    }
+
+    #[test]
+    fn should_read_entry_states() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = write_manifest(
+            &temp_dir,
+            "results.jsonl",
+            "{\"id\":\"a\",\"deploy_hash\":\"00\",\"sending\":true}\n\
+            {\"id\":\"a\",\"deploy_hash\":\"00\"}\n\
+            {\"id\":\"b\",\"error\":\"failed\"}\n\
+            {\"id\":\"c\",\"deploy_hash\":\"01\",\"sending\":true}\n\
+            {\"id\":\"d\",\"deploy_hash\":\"02\",\"error\":\"timed out\"}\n\
+            {\"id\":\"e\",\"deploy_hash\":\"03\",\"error\":\"invalid\",\"rejected\":true}\n",
+        );
+        let states = read_entry_states(&path).unwrap();
+        assert_eq!(states["a"], EntryState::Sent);
+        assert_eq!(states["b"], EntryState::Failed);
+        assert_eq!(states["c"], EntryState::Unconfirmed);
+        assert_eq!(states["d"], EntryState::Unconfirmed);
+        assert_eq!(states["e"], EntryState::Failed);
+    }
+}
@@ -0,0 +1,131 @@
+//! Signing and verification of arbitrary messages, e.g. to prove ownership of an account
+//! off-chain.
+//!
+//! Messages are prefixed with [`MESSAGE_PREFIX`] before being signed, so that a signed message can
+//! never be a valid deploy approval.
+
+use ed25519_dalek::ExpandedSecretKey;
+use k256::ecdsa::signature::{Signer as _, Verifier as _};
+
+use casper_types::{AsymmetricType, PublicKey, SecretKey, Signature};
+
+use crate::{
+    error::{Error, Result},
+    key_format, keystore,
+};
+
+/// The prefix added to every message before it is signed or verified.
+pub const MESSAGE_PREFIX: &[u8] = b"Casper Message:\n";
+
+fn prefixed(message: &[u8]) -> Vec<u8> {
+    let mut prefixed = Vec::with_capacity(MESSAGE_PREFIX.len() + message.len());
+    prefixed.extend_from_slice(MESSAGE_PREFIX);
+    prefixed.extend_from_slice(message);
+    prefixed
+}
+
+pub(crate) fn sign(message: &[u8], secret_key: &SecretKey) -> Result<Signature> {
+    let message = prefixed(message);
+    match secret_key {
+        SecretKey::Ed25519(secret_key) => {
+            let public_key = ed25519_dalek::PublicKey::from(secret_key);
+            let signature = ExpandedSecretKey::from(secret_key).sign(&message, &public_key);
+            Ok(Signature::Ed25519(signature))
+        }
+        SecretKey::Secp256k1(signing_key) => signing_key
+            .try_sign(&message)
+            .map(Signature::Secp256k1)
+            .map_err(|error| Error::InvalidArgument {
+                context: "secret_key",
+                error: format!("failed to sign message: {}", error),
+            }),
+        SecretKey::System => Err(Error::InvalidArgument {
+            context: "secret_key",
+            error: "system keys cannot sign messages".to_string(),
+        }),
+    }
+}
+
+pub(crate) fn verify(message: &[u8], signature: &Signature, public_key: &PublicKey) -> Result<()> {
+    let message = prefixed(message);
+    let is_valid = match (signature, public_key) {
+        (Signature::Ed25519(signature), PublicKey::Ed25519(public_key)) => {
+            public_key.verify_strict(&message, signature).is_ok()
+        }
+        (Signature::Secp256k1(signature), PublicKey::Secp256k1(public_key)) => {
+            public_key.verify(&message, signature).is_ok()
+        }
+        _ => {
+            return Err(Error::InvalidSignature(format!(
+                "{} signature cannot be verified with {} public key",
+                signature, public_key
+            )))
+        }
+    };
+    if is_valid {
+        Ok(())
+    } else {
+        Err(Error::InvalidSignature(format!(
+            "signature does not match the message and public key {}",
+            public_key.to_hex()
+        )))
+    }
+}
+
+pub(crate) fn sign_with_key_file(message: &[u8], secret_key_path: &str) -> Result<String> {
+    let secret_key = keystore::read_secret_key(secret_key_path)?;
+    sign(message, &secret_key).map(|signature| signature.to_hex())
+}
+
+pub(crate) fn verify_with_key(message: &[u8], signature: &str, public_key: &str) -> Result<()> {
+    let signature =
+        Signature::from_hex(signature.trim()).map_err(|error| Error::InvalidArgument {
+            context: "signature",
+            error: error.to_string(),
+        })?;
+    let public_key = key_format::read_public_key(public_key)?;
+    verify(message, &signature, &public_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_node::{
+        crypto::AsymmetricKeyExt,
+        types::{Approval, DeployHash},
+    };
+
+    use super::*;
+
+    const MESSAGE: &[u8] = b"I own this account";
+
+    #[test]
+    fn should_sign_and_verify() {
+        for secret_key in [
+            SecretKey::generate_ed25519().unwrap(),
+            SecretKey::generate_secp256k1().unwrap(),
+        ] {
+            let public_key = PublicKey::from(&secret_key);
+            let signature = sign(MESSAGE, &secret_key).unwrap();
+            verify(MESSAGE, &signature, &public_key).unwrap();
+
+            assert!(matches!(
+                verify(b"I own that account", &signature, &public_key),
+                Err(Error::InvalidSignature(_))
+            ));
+            let other_public_key = PublicKey::from(&SecretKey::generate_ed25519().unwrap());
+            assert!(matches!(
+                verify(MESSAGE, &signature, &other_public_key),
+                Err(Error::InvalidSignature(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn should_not_verify_deploy_approval() {
+        let secret_key = SecretKey::generate_ed25519().unwrap();
+        let public_key = PublicKey::from(&secret_key);
+        let deploy_hash = DeployHash::default();
+        let approval = Approval::create(&deploy_hash, &secret_key);
+        assert!(verify(deploy_hash.as_ref(), approval.signature(), &public_key).is_err());
+    }
+}
@@ -0,0 +1,230 @@
+//! Derivation of keys from BIP-39 mnemonics: BIP-32 for secp256k1 keys, and SLIP-10 for Ed25519
+//! keys.
+
+use std::str::FromStr;
+
+use bip32::{DerivationPath, XPrv};
+use bip39::Mnemonic;
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+
+use casper_types::SecretKey;
+
+use crate::{
+    error::{Error, Result},
+    keygen::{DEFAULT_ED25519_PATH, DEFAULT_SECP256K1_PATH, ED25519, SECP256K1},
+};
+
+/// The HMAC key used to derive the SLIP-10 master key for Ed25519.
+const ED25519_SEED_KEY: &[u8] = b"ed25519 seed";
+
+type HmacSha512 = Hmac<Sha512>;
+
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> [u8; 64] {
+    let mut mac = HmacSha512::new_from_slice(key).expect("HMAC accepts keys of any length");
+    for part in data {
+        mac.update(part);
+    }
+    mac.finalize().into_bytes().into()
+}
+
+/// Derives an Ed25519 secret key from `seed` along `path` as specified by SLIP-10.  Every level of
+/// `path` must be hardened.
+fn derive_ed25519(seed: &[u8], path: &DerivationPath) -> Result<[u8; 32]> {
+    let mut node = hmac_sha512(ED25519_SEED_KEY, &[seed]);
+    for child in path.iter() {
+        if !child.is_hardened() {
+            return Err(Error::InvalidArgument {
+                context: "derivation_path",
+                error: format!(
+                    "Ed25519 keys only support hardened derivation, but '{}' is not hardened",
+                    child
+                ),
+            });
+        }
+        let (key, chain_code) = node.split_at(32);
+        node = hmac_sha512(chain_code, &[&[0], key, &child.to_bytes()]);
+    }
+    let mut key = [0; 32];
+    key.copy_from_slice(&node[..32]);
+    Ok(key)
+}
+
+/// Derives a secp256k1 secret key from `seed` along `path` as specified by BIP-32.
+fn derive_secp256k1(seed: &[u8], path: &DerivationPath) -> Result<[u8; 32]> {
+    XPrv::derive_from_path(seed, path)
+        .map(|xprv| xprv.to_bytes())
+        .map_err(|error| Error::InvalidArgument {
+            context: "mnemonic",
+            error: format!("failed to derive key: {}", error),
+        })
+}
+
+/// Generates a new mnemonic phrase of `word_count` words.
+pub(crate) fn generate(word_count: usize) -> Result<String> {
+    Mnemonic::generate(word_count)
+        .map(|mnemonic| mnemonic.to_string())
+        .map_err(|error| Error::InvalidArgument {
+            context: "word_count",
+            error: error.to_string(),
+        })
+}
+
+/// Derives the secret key of the given algorithm from `mnemonic` along `path`, or along the
+/// algorithm's default path if `path` is empty.
+pub(crate) fn derive_secret_key(mnemonic: &str, algorithm: &str, path: &str) -> Result<SecretKey> {
+    let is_ed25519 = if algorithm.eq_ignore_ascii_case(ED25519) {
+        true
+    } else if algorithm.eq_ignore_ascii_case(SECP256K1) {
+        false
+    } else {
+        return Err(Error::UnsupportedAlgorithm(algorithm.to_string()));
+    };
+
+    let mnemonic = Mnemonic::parse(mnemonic).map_err(|error| Error::InvalidArgument {
+        context: "mnemonic",
+        error: error.to_string(),
+    })?;
+    let seed = mnemonic.to_seed("");
+
+    let path = match (path.is_empty(), is_ed25519) {
+        (false, _) => path,
+        (true, true) => DEFAULT_ED25519_PATH,
+        (true, false) => DEFAULT_SECP256K1_PATH,
+    };
+    let path = DerivationPath::from_str(path).map_err(|error| Error::InvalidArgument {
+        context: "derivation_path",
+        error: format!("'{}': {}", path, error),
+    })?;
+
+    let key_error = |error: String| Error::InvalidArgument {
+        context: "mnemonic",
+        error: format!("failed to derive key: {}", error),
+    };
+    if is_ed25519 {
+        let key = derive_ed25519(&seed, &path)?;
+        SecretKey::ed25519_from_bytes(key).map_err(|error| key_error(error.to_string()))
+    } else {
+        let key = derive_secp256k1(&seed, &path)?;
+        SecretKey::secp256k1_from_bytes(key).map_err(|error| key_error(error.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{AsymmetricType, PublicKey};
+
+    use super::*;
+
+    const MNEMONIC: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+        about";
+
+    fn derive_ed25519_hex(seed: &str, path: &str) -> String {
+        let seed = base16::decode(seed).unwrap();
+        let path = DerivationPath::from_str(path).unwrap();
+        base16::encode_lower(&derive_ed25519(&seed, &path).unwrap())
+    }
+
+    fn derive_secp256k1_hex(seed: &str, path: &str) -> String {
+        let seed = base16::decode(seed).unwrap();
+        let path = DerivationPath::from_str(path).unwrap();
+        base16::encode_lower(&derive_secp256k1(&seed, &path).unwrap())
+    }
+
+    #[test]
+    fn should_match_slip10_ed25519_test_vectors() {
+        // Test vector 1 from SLIP-10.
+        let seed = "000102030405060708090a0b0c0d0e0f";
+        assert_eq!(
+            derive_ed25519_hex(seed, "m"),
+            "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7"
+        );
+        assert_eq!(
+            derive_ed25519_hex(seed, "m/0'"),
+            "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3"
+        );
+        assert_eq!(
+            derive_ed25519_hex(seed, "m/0'/1'"),
+            "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2"
+        );
+    }
+
+    #[test]
+    fn should_match_bip32_secp256k1_test_vectors() {
+        // Test vector 1 from BIP-32.
+        let seed = "000102030405060708090a0b0c0d0e0f";
+        assert_eq!(
+            derive_secp256k1_hex(seed, "m"),
+            "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35"
+        );
+        assert_eq!(
+            derive_secp256k1_hex(seed, "m/0'"),
+            "edb2e14f9ee77d26dd93b4ecede8d16ed408ce149b6cd80b0715a2d911a0afea"
+        );
+        assert_eq!(
+            derive_secp256k1_hex(seed, "m/0'/1"),
+            "3c6cb8d0f6a264c91ea8b5030fadaa8e538b020f0a387421a12de9319dc93368"
+        );
+        assert_eq!(
+            derive_secp256k1_hex(seed, "m/0'/1/2'/2/1000000000"),
+            "471b76e389e528d6de6d816857e012c5455051cad6660850e58372a6c3e6e7c8"
+        );
+    }
+
+    #[test]
+    fn should_derive_bip39_seed() {
+        // The BIP-39 seed of `MNEMONIC` with an empty passphrase, from the BIP-39 test vectors.
+        let seed = Mnemonic::parse(MNEMONIC).unwrap().to_seed("");
+        assert_eq!(
+            base16::encode_lower(&seed),
+            "5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206\
+            dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4"
+        );
+    }
+
+    #[test]
+    fn should_derive_deterministically() {
+        for algorithm in [ED25519, SECP256K1] {
+            let first = derive_secret_key(MNEMONIC, algorithm, "").unwrap();
+            let second = derive_secret_key(MNEMONIC, algorithm, "").unwrap();
+            assert_eq!(PublicKey::from(&first), PublicKey::from(&second));
+        }
+
+        let account_0 = derive_secret_key(MNEMONIC, SECP256K1, "m/44'/506'/0'/0/0").unwrap();
+        let account_1 = derive_secret_key(MNEMONIC, SECP256K1, "m/44'/506'/0'/0/1").unwrap();
+        assert_ne!(PublicKey::from(&account_0), PublicKey::from(&account_1));
+        assert!(PublicKey::from(&account_0).to_hex().starts_with("02"));
+    }
+
+    #[test]
+    fn should_reject_non_hardened_ed25519_path() {
+        assert!(matches!(
+            derive_secret_key(MNEMONIC, ED25519, DEFAULT_SECP256K1_PATH),
+            Err(Error::InvalidArgument {
+                context: "derivation_path",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn should_reject_invalid_mnemonic() {
+        let invalid = MNEMONIC.replace("about", "abandon");
+        assert!(matches!(
+            derive_secret_key(&invalid, SECP256K1, ""),
+            Err(Error::InvalidArgument {
+                context: "mnemonic",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn should_generate_valid_mnemonic() {
+        let mnemonic = generate(12).unwrap();
+        assert_eq!(mnemonic.split_whitespace().count(), 12);
+        assert!(derive_secret_key(&mnemonic, ED25519, "").is_ok());
+        assert!(generate(13).is_err());
+    }
+}
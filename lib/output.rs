@@ -0,0 +1,395 @@
+//! Formatting of command output as JSON, YAML, NDJSON or a table, and selection of fields from it
+//! using a query expression.
+//!
+//! A query is a subset of JSONPath and jq syntax: a path of field names separated by `.`, e.g.
+//! `result.balance_value`, optionally prefixed with `$` or `.`.  Array elements are selected by
+//! index, e.g. `items[0]` or `items.0`, with negative indices counting from the end.  `[*]`, `[]`
+//! or `*` select every element of an array or every value of an object, applying the rest of the
+//! path to each and collecting the results in an array.  Field names which aren't plain
+//! identifiers can be quoted, e.g. `["field name"]`.
+
+use std::convert::TryFrom;
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::error::{Error, Result};
+
+/// Pretty-printed JSON.  A string selected by a query is printed without quotes.
+pub const JSON: &str = "json";
+/// JSON on a single line.
+pub const JSON_COMPACT: &str = "json-compact";
+/// YAML.
+pub const YAML: &str = "yaml";
+/// A table with a row per element of an array, or per field of an object.
+pub const TABLE: &str = "table";
+/// Newline-delimited JSON, with a line per element of an array.
+pub const NDJSON: &str = "ndjson";
+/// The supported output formats.
+pub const FORMATS: [&str; 5] = [JSON, JSON_COMPACT, YAML, TABLE, NDJSON];
+
+/// The gap between the columns of a table.
+const COLUMN_SEPARATOR: &str = "  ";
+
+/// A step in a parsed query.
+#[derive(PartialEq, Eq, Debug)]
+enum Segment {
+    Field(String),
+    Index(i64),
+    Wildcard,
+}
+
+fn query_error(expression: &str, error: impl ToString) -> Error {
+    Error::InvalidArgument {
+        context: "query",
+        error: format!("'{}': {}", expression, error.to_string()),
+    }
+}
+
+fn parse_query(expression: &str) -> Result<Vec<Segment>> {
+    let mut chars = expression.trim().chars().peekable();
+    if chars.peek() == Some(&'$') {
+        chars.next();
+    }
+    let mut segments = vec![];
+    let mut at_start = true;
+    while let Some(&char) = chars.peek() {
+        match char {
+            '.' => {
+                chars.next();
+                // A lone leading '.' is jq's identity.
+                if chars.peek().is_none() && at_start {
+                    break;
+                }
+                if matches!(chars.peek(), None | Some('.') | Some('[')) {
+                    return Err(query_error(expression, "expected a field name after '.'"));
+                }
+            }
+            '[' => {
+                chars.next();
+                let mut inner = String::new();
+                let mut in_quotes = false;
+                loop {
+                    match chars.next() {
+                        Some('"') => in_quotes = !in_quotes,
+                        Some(']') if !in_quotes => break,
+                        Some(char) => inner.push(char),
+                        None => return Err(query_error(expression, "unclosed '['")),
+                    }
+                }
+                let trimmed = inner.trim();
+                segments.push(if trimmed.is_empty() || trimmed == "*" {
+                    Segment::Wildcard
+                } else if let Ok(index) = trimmed.parse() {
+                    Segment::Index(index)
+                } else {
+                    Segment::Field(inner)
+                });
+            }
+            _ => {
+                let mut name = String::new();
+                while let Some(&char) = chars.peek() {
+                    if char == '.' || char == '[' {
+                        break;
+                    }
+                    name.push(char);
+                    chars.next();
+                }
+                segments.push(if name == "*" {
+                    Segment::Wildcard
+                } else if let Ok(index) = name.parse() {
+                    Segment::Index(index)
+                } else {
+                    Segment::Field(name)
+                });
+            }
+        }
+        at_start = false;
+    }
+    Ok(segments)
+}
+
+/// Applies `segment` to `value`, returning the selected values.
+fn select<'a>(value: &'a Value, segment: &Segment, path: &str) -> Result<Vec<&'a Value>> {
+    let not_found = || query_error(path, "not found");
+    match (segment, value) {
+        (Segment::Field(name), Value::Object(map)) => {
+            Ok(vec![map.get(name).ok_or_else(not_found)?])
+        }
+        (Segment::Index(index), Value::Array(array)) => {
+            let position = if *index < 0 {
+                array.len() as i64 + index
+            } else {
+                *index
+            };
+            usize::try_from(position)
+                .ok()
+                .and_then(|position| array.get(position))
+                .map(|element| vec![element])
+                .ok_or_else(not_found)
+        }
+        (Segment::Wildcard, Value::Array(array)) => Ok(array.iter().collect()),
+        (Segment::Wildcard, Value::Object(map)) => Ok(map.values().collect()),
+        (Segment::Field(_), _) => Err(query_error(path, "not an object")),
+        (Segment::Index(_), _) => Err(query_error(path, "not an array")),
+        (Segment::Wildcard, _) => Err(query_error(path, "not an array or object")),
+    }
+}
+
+/// Returns the part of `value` selected by the query `expression`.  An empty expression selects
+/// the whole value.
+pub fn query(value: &Value, expression: &str) -> Result<Value> {
+    let segments = parse_query(expression)?;
+    let mut selected = vec![value];
+    let mut is_multiple = false;
+    let mut path = String::new();
+    for segment in &segments {
+        match segment {
+            Segment::Field(name) => {
+                if !path.is_empty() {
+                    path.push('.');
+                }
+                path.push_str(name);
+            }
+            Segment::Index(index) => path.push_str(&format!("[{}]", index)),
+            Segment::Wildcard => {
+                path.push_str("[*]");
+                is_multiple = true;
+            }
+        }
+        selected = selected
+            .into_iter()
+            .map(|value| select(value, segment, &path))
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .flatten()
+            .collect();
+    }
+    if is_multiple {
+        Ok(Value::Array(selected.into_iter().cloned().collect()))
+    } else {
+        Ok(selected[0].clone())
+    }
+}
+
+/// Returns `value` as JSON, with long hex strings shortened as by
+/// [`pretty_print_at_level`](../fn.pretty_print_at_level.html) at verbosity level `1`.
+pub fn to_abbreviated_json<T: ?Sized + Serialize>(value: &T) -> Result<Value> {
+    let abbreviated = casper_types::json_pretty_print(value).map_err(Error::InvalidJson)?;
+    serde_json::from_str(&abbreviated).map_err(Error::InvalidJson)
+}
+
+fn to_cell(value: &Value) -> String {
+    match value {
+        Value::String(string) => string.clone(),
+        Value::Null => String::new(),
+        _ => value.to_string(),
+    }
+}
+
+fn format_table(rows: &[Vec<String>]) -> String {
+    let column_count = rows.iter().map(Vec::len).max().unwrap_or_default();
+    let widths: Vec<usize> = (0..column_count)
+        .map(|column| {
+            rows.iter()
+                .filter_map(|row| row.get(column))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+    rows.iter()
+        .map(|row| {
+            let line = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{:width$}", cell, width = width))
+                .collect::<Vec<_>>()
+                .join(COLUMN_SEPARATOR);
+            line.trim_end().to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn to_table(value: &Value) -> String {
+    match value {
+        Value::Array(array) if array.iter().all(Value::is_object) && !array.is_empty() => {
+            let mut headers: Vec<&String> = vec![];
+            for map in array.iter().filter_map(Value::as_object) {
+                for key in map.keys() {
+                    if !headers.contains(&key) {
+                        headers.push(key);
+                    }
+                }
+            }
+            let mut rows = vec![headers
+                .iter()
+                .map(|header| header.to_uppercase())
+                .collect::<Vec<_>>()];
+            let empty = Map::new();
+            for map in array
+                .iter()
+                .map(|value| value.as_object().unwrap_or(&empty))
+            {
+                rows.push(
+                    headers
+                        .iter()
+                        .map(|header| map.get(*header).map(to_cell).unwrap_or_default())
+                        .collect(),
+                );
+            }
+            format_table(&rows)
+        }
+        Value::Array(array) => array.iter().map(to_cell).collect::<Vec<_>>().join("\n"),
+        Value::Object(map) => {
+            let rows: Vec<Vec<String>> = map
+                .iter()
+                .map(|(key, value)| vec![key.clone(), to_cell(value)])
+                .collect();
+            format_table(&rows)
+        }
+        _ => to_cell(value),
+    }
+}
+
+/// Formats `value` in the given format, one of [`FORMATS`].
+pub fn format(value: &Value, format: &str) -> Result<String> {
+    let json_error = Error::InvalidJson;
+    match format {
+        JSON => match value {
+            Value::String(string) => Ok(string.clone()),
+            _ => serde_json::to_string_pretty(value).map_err(json_error),
+        },
+        JSON_COMPACT => serde_json::to_string(value).map_err(json_error),
+        YAML => serde_yaml::to_string(value)
+            .map(|yaml| yaml.trim_end().to_string())
+            .map_err(|error| Error::InvalidArgument {
+                context: "output_format",
+                error: format!("failed to encode as YAML: {}", error),
+            }),
+        TABLE => Ok(to_table(value)),
+        NDJSON => match value {
+            Value::Array(array) => array
+                .iter()
+                .map(serde_json::to_string)
+                .collect::<serde_json::Result<Vec<_>>>()
+                .map(|lines| lines.join("\n"))
+                .map_err(json_error),
+            _ => serde_json::to_string(value).map_err(json_error),
+        },
+        _ => Err(Error::InvalidArgument {
+            context: "output_format",
+            error: format!("'{}' must be one of {:?}", format, FORMATS),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn response() -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": {
+                "api_version": "1.4.6",
+                "balance_value": "123456",
+                "validators": [
+                    {"public_key": "01aa", "weight": "10"},
+                    {"public_key": "01bb", "weight": "20", "name": "b"}
+                ],
+                "odd key": true
+            }
+        })
+    }
+
+    #[test]
+    fn should_query_fields() {
+        let response = response();
+        for expression in [
+            "result.balance_value",
+            ".result.balance_value",
+            "$.result.balance_value",
+            "result[\"balance_value\"]",
+        ] {
+            assert_eq!(query(&response, expression).unwrap(), json!("123456"));
+        }
+        assert_eq!(query(&response, "").unwrap(), response);
+        assert_eq!(query(&response, ".").unwrap(), response);
+        assert_eq!(
+            query(&response, "result[\"odd key\"]").unwrap(),
+            json!(true)
+        );
+        assert_eq!(
+            query(&response, "result.validators[1].weight").unwrap(),
+            json!("20")
+        );
+        assert_eq!(
+            query(&response, "result.validators.-1.public_key").unwrap(),
+            json!("01bb")
+        );
+        assert_eq!(
+            query(&response, "result.validators[*].public_key").unwrap(),
+            json!(["01aa", "01bb"])
+        );
+        assert_eq!(
+            query(&response, ".result.validators[].weight").unwrap(),
+            json!(["10", "20"])
+        );
+    }
+
+    #[test]
+    fn should_fail_invalid_query() {
+        let response = response();
+        for expression in [
+            "result.missing",
+            "result.validators[2]",
+            "result.balance_value.x",
+            "result.validators[*].name",
+            "result..x",
+            "result[0",
+        ] {
+            assert!(
+                matches!(
+                    query(&response, expression),
+                    Err(Error::InvalidArgument {
+                        context: "query",
+                        ..
+                    })
+                ),
+                "{}",
+                expression
+            );
+        }
+    }
+
+    #[test]
+    fn should_format_in_all_formats() {
+        let value = json!([{"a": 1, "b": "x"}, {"a": 22}]);
+        assert_eq!(
+            format(&value, JSON).unwrap(),
+            serde_json::to_string_pretty(&value).unwrap()
+        );
+        assert_eq!(
+            format(&value, JSON_COMPACT).unwrap(),
+            r#"[{"a":1,"b":"x"},{"a":22}]"#
+        );
+        assert_eq!(
+            format(&value, NDJSON).unwrap(),
+            "{\"a\":1,\"b\":\"x\"}\n{\"a\":22}"
+        );
+        assert_eq!(format(&value, YAML).unwrap(), "- a: 1\n  b: x\n- a: 22");
+        assert_eq!(format(&value, TABLE).unwrap(), "A   B\n1   x\n22");
+        assert_eq!(
+            format(&json!({"key": "value", "number": 5}), TABLE).unwrap(),
+            "key     value\nnumber  5"
+        );
+        assert_eq!(format(&json!("123"), JSON).unwrap(), "123");
+        assert_eq!(format(&json!("123"), JSON_COMPACT).unwrap(), "\"123\"");
+        assert!(format(&value, "xml").is_err());
+    }
+}
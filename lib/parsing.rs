@@ -1,26 +1,25 @@
 //! This module contains structs and helpers which are used by multiple subcommands related to
 //! creating deploys.
 
-use std::{convert::TryInto, fs, io, path::PathBuf, str::FromStr};
+use std::{fs, path::Path, str::FromStr};
 
 use serde::{self, Deserialize};
 
 use casper_execution_engine::core::engine_state::executable_deploy_item::ExecutableDeployItem;
 use casper_hashing::Digest;
-use casper_node::{
-    crypto::AsymmetricKeyExt,
-    types::{DeployHash, TimeDiff, Timestamp},
-};
+use casper_node::types::{DeployHash, TimeDiff, Timestamp};
 use casper_types::{
-    bytesrepr, AsymmetricType, CLType, CLValue, HashAddr, Key, NamedArg, PublicKey, RuntimeArgs,
-    SecretKey, UIntParseError, U512,
+    AsymmetricType, CLType, CLValue, HashAddr, Key, NamedArg, PublicKey, RuntimeArgs, SecretKey,
 };
 
 use crate::{
-    cl_type,
-    deploy::DeployParams,
+    arg_tokenizer, cl_type,
+    deploy::{DeployFormat, DeployParams},
+    deploy_limits::DeployLimits,
     error::{Error, Result},
-    help, map_hashing_error, PaymentStrParams, SessionStrParams,
+    help, keystore, map_hashing_error,
+    signer::{self, Signer},
+    units, PaymentStrParams, SessionStrParams,
 };
 
 pub(super) fn none_if_empty(value: &'_ str) -> Option<&'_ str> {
@@ -70,8 +69,6 @@ fn dependencies(values: &[&str]) -> Result<Vec<DeployHash>> {
 mod arg_simple {
     use super::*;
 
-    const ARG_VALUE_NAME: &str = r#""NAME:TYPE='VALUE'" OR "NAME:TYPE=null""#;
-
     pub(crate) mod session {
         use super::*;
 
@@ -98,49 +95,33 @@ mod arg_simple {
 
     fn get(values: &[&str]) -> Result<RuntimeArgs> {
         let mut runtime_args = RuntimeArgs::new();
-        for arg in values {
-            let parts = split_arg(arg)?;
-            parts_to_cl_value(parts, &mut runtime_args)?;
+        for value in values {
+            for arg in arg_tokenizer::tokenize(value)? {
+                let position = arg.value_position;
+                let cl_value = cl_type::parts_to_cl_value(arg.cl_type, &arg.value).map_err(
+                    |error| match error {
+                        Error::InvalidCLValue(error) => Error::InvalidCLValue(format!(
+                            "simple arg \"{}\" at character {}: {}",
+                            value, position, error
+                        )),
+                        other => other,
+                    },
+                )?;
+                runtime_args.insert_cl_value(arg.name, cl_value);
+            }
         }
         Ok(runtime_args)
     }
-
-    /// Splits a single arg of the form `NAME:TYPE='VALUE'` into its constituent parts.
-    fn split_arg(arg: &str) -> Result<(&str, CLType, &str)> {
-        let parts: Vec<_> = arg.splitn(3, &[':', '='][..]).collect();
-        if parts.len() != 3 {
-            return Err(Error::InvalidCLValue(format!(
-                "arg {} should be formatted as {}",
-                arg, ARG_VALUE_NAME
-            )));
-        }
-        let cl_type = cl_type::parse(parts[1]).map_err(|_| {
-            Error::InvalidCLValue(format!(
-                "unknown variant {}, expected one of {}",
-                parts[1],
-                help::supported_cl_type_list()
-            ))
-        })?;
-        Ok((parts[0], cl_type, parts[2]))
-    }
-
-    /// Insert a value built from a single arg which has been split into its constituent parts.
-    fn parts_to_cl_value(
-        parts: (&str, CLType, &str),
-        runtime_args: &mut RuntimeArgs,
-    ) -> Result<()> {
-        let (name, cl_type, value) = parts;
-        let cl_value = cl_type::parts_to_cl_value(cl_type, value)?;
-        runtime_args.insert_cl_value(name, cl_value);
-        Ok(())
-    }
 }
 
 /// Handles providing the arg for and retrieval of complex session and payment args. These are read
 /// in from a file.
 mod args_complex {
     use std::{
+        convert::TryFrom,
         fmt::{self, Formatter},
+        io::{self, Read},
+        path::Path,
         result::Result as StdResult,
     };
 
@@ -149,6 +130,7 @@ mod args_complex {
     use casper_types::checksummed_hex;
 
     use super::*;
+    use crate::cl_type::DecodeError;
 
     #[derive(Debug, Deserialize)]
     #[serde(rename_all = "snake_case")]
@@ -188,30 +170,95 @@ mod args_complex {
         deserializer.deserialize_str(HexStrVisitor)
     }
 
+    /// Parses a `CLType` given either in the grammar of simple args, e.g. "List<U512>", or as in a
+    /// deploy's JSON, e.g. `{"List": "U512"}`.
+    fn deserialize_cl_type<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> StdResult<Option<CLType>, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        if let Some(cl_type) = value.as_str().and_then(|name| cl_type::parse(name).ok()) {
+            return Ok(Some(cl_type));
+        }
+        serde_json::from_value(value.clone())
+            .map(Some)
+            .map_err(|_| {
+                SerdeError::custom(format!(
+                    "unknown type {}, expected one of {} or a CLType as in a deploy's JSON",
+                    value,
+                    help::supported_cl_type_list()
+                ))
+            })
+    }
+
     #[derive(Debug, Deserialize)]
     #[serde(rename_all = "snake_case")]
     struct DeployArg {
         /// Deploy argument's name.
         name: String,
-        value: DeployArgValue,
+        /// Deploy argument's type.  If given, the value is given in its typed JSON form, otherwise
+        /// it is a `DeployArgValue`.
+        #[serde(rename = "type", default, deserialize_with = "deserialize_cl_type")]
+        cl_type: Option<CLType>,
+        /// Deploy argument's value.  Defaults to `null`, which TOML can't express.
+        #[serde(default)]
+        value: serde_json::Value,
     }
 
-    impl From<DeployArgValue> for CLValue {
-        fn from(value: DeployArgValue) -> Self {
-            match value {
-                DeployArgValue::RawBytes(bytes) => bytesrepr::deserialize(bytes)
-                    .unwrap_or_else(|error| panic!("should deserialize deploy arg: {}", error)),
-            }
+    /// The key under which the list of args can be given in an args file, as is required in TOML.
+    const ARGS_KEY: &str = "args";
+
+    impl TryFrom<DeployArgValue> for CLValue {
+        type Error = DecodeError;
+
+        fn try_from(value: DeployArgValue) -> StdResult<Self, Self::Error> {
+            let DeployArgValue::RawBytes(bytes) = value;
+            cl_type::decode_cl_value(&bytes)
         }
     }
 
-    impl From<DeployArg> for NamedArg {
-        fn from(deploy_arg: DeployArg) -> Self {
-            let cl_value = deploy_arg
-                .value
-                .try_into()
-                .unwrap_or_else(|error| panic!("should serialize deploy arg: {}", error));
-            NamedArg::new(deploy_arg.name, cl_value)
+    impl DeployArg {
+        /// Returns the arg at `index` in the file as a `NamedArg`, with its value parsed as its
+        /// type if given, or else decoded from its raw bytes.  Files named in the value are read
+        /// relative to `base_dir`.
+        fn into_named_arg(self, index: usize, base_dir: &Path) -> Result<NamedArg> {
+            let DeployArg {
+                name,
+                cl_type,
+                value,
+            } = self;
+            let cl_value = match cl_type {
+                Some(cl_type) => {
+                    cl_type::json_to_cl_value(cl_type, &value, base_dir).map_err(|error| {
+                        Error::InvalidCLValue(format!(
+                            "arg '{}' at index {}: {}",
+                            name, index, error
+                        ))
+                    })?
+                }
+                None => {
+                    let raw_bytes =
+                        serde_json::from_value::<DeployArgValue>(value).map_err(|error| {
+                            Error::InvalidCLValue(format!(
+                                "arg '{}' at index {} should have a \"type\" or a value of the \
+                                form {{\"raw_bytes\": \"<hex>\"}}: {}",
+                                name, index, error
+                            ))
+                        })?;
+                    CLValue::try_from(raw_bytes).map_err(|decode_error| {
+                        Error::FailedToDecodeComplexArg {
+                            name: name.clone(),
+                            index,
+                            cl_type: decode_error.cl_type.map_or_else(
+                                || "unknown CLType".to_string(),
+                                |cl_type| format!("{:?}", cl_type),
+                            ),
+                            offset: decode_error.offset,
+                            error: decode_error.error,
+                        }
+                    })?
+                }
+            };
+            Ok(NamedArg::new(name, cl_value))
         }
     }
 
@@ -222,10 +269,7 @@ mod args_complex {
             if path.is_empty() {
                 return Ok(None);
             }
-            let runtime_args = get(path).map_err(|error| Error::IoError {
-                context: format!("error reading session file at '{}'", path),
-                error,
-            })?;
+            let runtime_args = get(path, "session")?;
             Ok(Some(runtime_args))
         }
     }
@@ -237,22 +281,163 @@ mod args_complex {
             if path.is_empty() {
                 return Ok(None);
             }
-            let runtime_args = get(path).map_err(|error| Error::IoError {
-                context: format!("error reading payment file at '{}'", path),
-                error,
-            })?;
+            let runtime_args = get(path, "payment")?;
             Ok(Some(runtime_args))
         }
     }
 
-    fn get(path: &str) -> io::Result<RuntimeArgs> {
-        let bytes = fs::read(path)?;
-        // Received structured args in JSON format.
-        let args: Vec<DeployArg> = serde_json::from_slice(&bytes)?;
-        // Convert JSON deploy args into vector of named args.
-        let mut named_args = Vec::with_capacity(args.len());
-        for arg in args {
-            named_args.push(arg.into());
+    /// The path which denotes reading the args file from stdin.
+    const STDIN_PATH: &str = "-";
+
+    /// Reads the contents of the args file at `path`, or from stdin if `path` is "-".
+    fn read(path: &str, kind: &str) -> Result<String> {
+        let io_error = |error| Error::IoError {
+            context: format!("error reading {} file at '{}'", kind, path),
+            error,
+        };
+        if path != STDIN_PATH {
+            return fs::read_to_string(path).map_err(io_error);
+        }
+        let mut contents = String::new();
+        io::stdin()
+            .read_to_string(&mut contents)
+            .map_err(io_error)?;
+        if contents.trim().is_empty() {
+            return Err(io_error(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "no args read from stdin; only one args file can be read from stdin",
+            )));
+        }
+        Ok(contents)
+    }
+
+    /// Parses the contents of an args file as JSON, TOML or YAML, detected from the file's
+    /// extension or else by trying each in turn.  On failure, returns the line of the error if
+    /// known.
+    fn parse_contents(
+        path: &str,
+        contents: &str,
+    ) -> StdResult<serde_json::Value, (Option<usize>, String)> {
+        let from_json = || {
+            serde_json::from_str(contents).map_err(|error| (Some(error.line()), error.to_string()))
+        };
+        let from_toml = || {
+            toml::from_str::<toml::Value>(contents)
+                .map_err(|error| {
+                    (
+                        error.line_col().map(|(line, _)| line + 1),
+                        error.to_string(),
+                    )
+                })
+                .and_then(|value| {
+                    serde_json::to_value(value).map_err(|error| (None, error.to_string()))
+                })
+        };
+        let from_yaml = || {
+            serde_yaml::from_str(contents).map_err(|error| {
+                (
+                    error.location().map(|location| location.line()),
+                    error.to_string(),
+                )
+            })
+        };
+        let extension = Path::new(path)
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_lowercase());
+        match extension.as_deref() {
+            Some("json") => from_json(),
+            Some("toml") => from_toml(),
+            Some("yaml") | Some("yml") => from_yaml(),
+            _ => from_json()
+                .or_else(|_| from_toml())
+                .or_else(|_| from_yaml())
+                .map_err(|(line, error)| {
+                    (
+                        line,
+                        format!("not valid JSON, TOML or YAML; as YAML: {}", error),
+                    )
+                }),
+        }
+    }
+
+    /// Returns the 1-based line numbers of the lines in `contents` holding an arg's `name` field,
+    /// or an empty vector if there isn't exactly one such line per arg.
+    fn arg_lines(contents: &str, arg_count: usize) -> Vec<usize> {
+        let lines: Vec<usize> = contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| {
+                let line = line.trim_start_matches(|char: char| {
+                    char.is_whitespace() || char == '-' || char == '{' || char == '['
+                });
+                let line = line.strip_prefix('"').unwrap_or(line);
+                line.strip_prefix("name").is_some_and(|rest| {
+                    let rest = rest.strip_prefix('"').unwrap_or(rest).trim_start();
+                    rest.starts_with(':') || rest.starts_with('=')
+                })
+            })
+            .map(|(index, _)| index + 1)
+            .collect();
+        if lines.len() == arg_count {
+            lines
+        } else {
+            vec![]
+        }
+    }
+
+    /// Reads the args file at `path`, which holds a list of args, either at the top level or under
+    /// the key "args", in JSON, TOML or YAML.
+    fn get(path: &str, kind: &str) -> Result<RuntimeArgs> {
+        let contents = read(path, kind)?;
+        let display_path = if path == STDIN_PATH { "<stdin>" } else { path };
+        let parse_error = |line: Option<usize>, error: String| Error::FailedToParseArgsFile {
+            context: match line {
+                Some(line) => format!("{}:{}", display_path, line),
+                None => display_path.to_string(),
+            },
+            error,
+        };
+
+        let value =
+            parse_contents(path, &contents).map_err(|(line, error)| parse_error(line, error))?;
+        let values = match value {
+            serde_json::Value::Object(mut map) if map.contains_key(ARGS_KEY) => {
+                map.remove(ARGS_KEY).unwrap_or_default()
+            }
+            value => value,
+        };
+        let values = match values {
+            serde_json::Value::Array(values) => values,
+            _ => {
+                return Err(parse_error(
+                    None,
+                    format!(
+                        "expected a list of args, or a list under the key '{}'",
+                        ARGS_KEY
+                    ),
+                ))
+            }
+        };
+
+        // Files named in the args are read relative to the args file, or to the current directory
+        // if the args are read from stdin.
+        let base_dir = match path {
+            STDIN_PATH => Path::new(""),
+            path => Path::new(path).parent().unwrap_or_else(|| Path::new("")),
+        };
+        let lines = arg_lines(&contents, values.len());
+        let mut named_args = Vec::with_capacity(values.len());
+        for (index, value) in values.into_iter().enumerate() {
+            let line = lines.get(index).copied();
+            let arg: DeployArg = serde_json::from_value(value)
+                .map_err(|error| parse_error(line, format!("arg at index {}: {}", index, error)))?;
+            let named_arg = arg
+                .into_named_arg(index, base_dir)
+                .map_err(|error| match error {
+                    Error::InvalidCLValue(message) => parse_error(line, message),
+                    error => error,
+                })?;
+            named_args.push(named_arg);
         }
         Ok(RuntimeArgs::from(named_args))
     }
@@ -263,21 +448,14 @@ fn standard_payment(value: &str) -> Result<RuntimeArgs> {
     if value.is_empty() {
         return Err(Error::InvalidCLValue(value.to_string()));
     }
-    let arg = U512::from_dec_str(value).map_err(|err| Error::FailedToParseUint {
-        context: "amount",
-        error: UIntParseError::FromDecStr(err),
-    })?;
+    let arg = units::parse_motes("amount", value)?;
     let mut runtime_args = RuntimeArgs::new();
     runtime_args.insert(STANDARD_PAYMENT_ARG_NAME, arg)?;
     Ok(runtime_args)
 }
 
 pub(crate) fn secret_key(value: &str) -> Result<SecretKey> {
-    let path = PathBuf::from(value);
-    SecretKey::from_file(path).map_err(|error| Error::CryptoError {
-        context: "secret_key",
-        error,
-    })
+    keystore::read_secret_key(value)
 }
 
 fn args_from_simple_or_complex(
@@ -383,16 +561,43 @@ macro_rules! check_exactly_one_not_empty {
     }}
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(super) fn parse_deploy_params(
     secret_key: &str,
+    signer: &str,
+    timestamp: &str,
+    ttl: &str,
+    gas_price: &str,
+    dependencies: &[&str],
+    chain_name: &str,
+    session_account: &str,
+    chainspec: &str,
+) -> Result<DeployParams> {
+    let signer = signer::from_args(secret_key, signer)?;
+    parse_deploy_params_with_signer(
+        signer,
+        timestamp,
+        ttl,
+        gas_price,
+        dependencies,
+        chain_name,
+        session_account,
+        chainspec,
+    )
+}
+
+/// Parses the deploy params other than the signer, which has already been constructed.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn parse_deploy_params_with_signer(
+    signer: Box<dyn Signer>,
     timestamp: &str,
     ttl: &str,
     gas_price: &str,
     dependencies: &[&str],
     chain_name: &str,
     session_account: &str,
+    chainspec: &str,
 ) -> Result<DeployParams> {
-    let secret_key = self::secret_key(secret_key)?;
     let timestamp = self::timestamp(timestamp)?;
     let ttl = self::ttl(ttl)?;
     let gas_price = self::gas_price(gas_price)?;
@@ -405,15 +610,17 @@ pub(super) fn parse_deploy_params(
     } else {
         None
     };
+    let deploy_limits = deploy_limits(chainspec)?;
 
     Ok(DeployParams {
-        secret_key,
+        signer,
         timestamp,
         ttl,
         gas_price,
         dependencies,
         chain_name,
         session_account,
+        deploy_limits,
     })
 }
 
@@ -554,10 +761,10 @@ pub(super) fn parse_payment_info(params: PaymentStrParams) -> Result<ExecutableD
         });
     }
 
-    if let Ok(payment_args) = standard_payment(payment_amount) {
+    if !payment_amount.is_empty() {
         return Ok(ExecutableDeployItem::ModuleBytes {
             module_bytes: vec![].into(),
-            args: payment_args,
+            args: standard_payment(payment_amount)?,
         });
     }
 
@@ -646,6 +853,39 @@ fn version(value: &str) -> Result<u32> {
         })
 }
 
+pub(crate) fn deploy_format(value: &str) -> Result<DeployFormat> {
+    match value.to_lowercase().as_str() {
+        "" | DeployFormat::JSON => Ok(DeployFormat::Json),
+        DeployFormat::BINARY => Ok(DeployFormat::Binary),
+        DeployFormat::BASE64 => Ok(DeployFormat::Base64),
+        _ => Err(Error::InvalidArgument {
+            context: "output_format",
+            error: format!(
+                "expected one of '{}', '{}' or '{}', got '{}'",
+                DeployFormat::JSON,
+                DeployFormat::BINARY,
+                DeployFormat::BASE64,
+                value
+            ),
+        }),
+    }
+}
+
+/// Returns the limits from the chainspec file at `value` if it exists, or else from the preset
+/// named `value`, so a file is never shadowed by a preset of the same name.
+pub(crate) fn deploy_limits(value: &str) -> Result<DeployLimits> {
+    if value.is_empty() {
+        return Ok(DeployLimits::default());
+    }
+    if Path::new(value).is_file() {
+        return DeployLimits::from_chainspec_file(value);
+    }
+    match DeployLimits::preset(value) {
+        Some(deploy_limits) => Ok(deploy_limits),
+        None => DeployLimits::from_chainspec_file(value),
+    }
+}
+
 pub(crate) fn transfer_id(value: &str) -> Result<u64> {
     value.parse().map_err(|error| Error::FailedToParseInt {
         context: "transfer-id",
@@ -656,10 +896,17 @@ pub(crate) fn transfer_id(value: &str) -> Result<u64> {
 #[cfg(test)]
 mod tests {
     use casper_types::{
-        account::AccountHash, bytesrepr::ToBytes, AccessRights, CLTyped, CLValue, NamedArg,
-        PublicKey, RuntimeArgs, URef, U128, U256, U512,
+        account::AccountHash,
+        bytesrepr::{self, Bytes, ToBytes},
+        AccessRights, CLTyped, CLValue, DeployHash, EraId, Key, NamedArg, PublicKey, RuntimeArgs,
+        TransferAddr, URef, U128, U256, U512,
+    };
+    use std::{
+        collections::BTreeMap,
+        convert::{TryFrom, TryInto},
+        io::Write,
+        result::Result as StdResult,
     };
-    use std::{convert::TryFrom, io::Write, result::Result as StdResult};
     use tempfile::tempdir;
 
     use crate::{PaymentStrParams, SessionStrParams};
@@ -754,18 +1001,18 @@ mod tests {
 
     #[test]
     fn should_parse_i32_via_args_simple() {
-        valid_simple_args_test("x:i32='2147483647'", i32::max_value());
+        valid_simple_args_test("x:i32='2147483647'", i32::MAX);
         valid_simple_args_test("x:i32='0'", 0_i32);
-        valid_simple_args_test("x:i32='-2147483648'", i32::min_value());
+        valid_simple_args_test("x:i32='-2147483648'", i32::MIN);
         valid_simple_args_test("x:opt_i32='-1'", Some(-1_i32));
         valid_simple_args_test::<Option<i32>>("x:opt_i32=null", None);
     }
 
     #[test]
     fn should_parse_i64_via_args_simple() {
-        valid_simple_args_test("x:i64='9223372036854775807'", i64::max_value());
+        valid_simple_args_test("x:i64='9223372036854775807'", i64::MAX);
         valid_simple_args_test("x:i64='0'", 0_i64);
-        valid_simple_args_test("x:i64='-9223372036854775808'", i64::min_value());
+        valid_simple_args_test("x:i64='-9223372036854775808'", i64::MIN);
         valid_simple_args_test("x:opt_i64='-1'", Some(-1_i64));
         valid_simple_args_test::<Option<i64>>("x:opt_i64=null", None);
     }
@@ -773,7 +1020,7 @@ mod tests {
     #[test]
     fn should_parse_u8_via_args_simple() {
         valid_simple_args_test("x:u8='0'", 0_u8);
-        valid_simple_args_test("x:u8='255'", u8::max_value());
+        valid_simple_args_test("x:u8='255'", u8::MAX);
         valid_simple_args_test("x:opt_u8='1'", Some(1_u8));
         valid_simple_args_test::<Option<u8>>("x:opt_u8=null", None);
     }
@@ -781,7 +1028,7 @@ mod tests {
     #[test]
     fn should_parse_u32_via_args_simple() {
         valid_simple_args_test("x:u32='0'", 0_u32);
-        valid_simple_args_test("x:u32='4294967295'", u32::max_value());
+        valid_simple_args_test("x:u32='4294967295'", u32::MAX);
         valid_simple_args_test("x:opt_u32='1'", Some(1_u32));
         valid_simple_args_test::<Option<u32>>("x:opt_u32=null", None);
     }
@@ -789,7 +1036,7 @@ mod tests {
     #[test]
     fn should_parse_u64_via_args_simple() {
         valid_simple_args_test("x:u64='0'", 0_u64);
-        valid_simple_args_test("x:u64='18446744073709551615'", u64::max_value());
+        valid_simple_args_test("x:u64='18446744073709551615'", u64::MAX);
         valid_simple_args_test("x:opt_u64='1'", Some(1_u64));
         valid_simple_args_test::<Option<u64>>("x:opt_u64=null", None);
     }
@@ -841,6 +1088,59 @@ mod tests {
         valid_simple_args_test(&format!("x:string='{}'", value), value.clone());
         valid_simple_args_test(&format!("x:opt_string='{}'", value), Some(value));
         valid_simple_args_test::<Option<String>>("x:opt_string=null", None);
+        valid_simple_args_test("x:string=''", String::new());
+        valid_simple_args_test("x:string='it's'", "it's".to_string());
+        valid_simple_args_test("x:string=''quoted''", "'quoted'".to_string());
+        valid_simple_args_test(r#"x:string='it\'s'"#, r#"it\'s"#.to_string());
+        valid_simple_args_test(
+            r#"x:string="{\"a\": \"it's\"}""#,
+            r#"{"a": "it's"}"#.to_string(),
+        );
+        valid_simple_args_test(r#"x:opt_string="a\tb""#, Some("a\tb".to_string()));
+    }
+
+    #[test]
+    fn should_parse_several_args_via_args_simple() {
+        let args = arg_simple::session::parse(&[
+            r#"a:u8="1", b:string="x, y", c:opt_u8=null"#,
+            "d:string='e'",
+        ])
+        .expect("should parse")
+        .expect("should have args");
+        let expected = RuntimeArgs::from(vec![
+            NamedArg::new("a".to_string(), CLValue::from_t(1_u8).unwrap()),
+            NamedArg::new(
+                "b".to_string(),
+                CLValue::from_t("x, y".to_string()).unwrap(),
+            ),
+            NamedArg::new(
+                "c".to_string(),
+                CLValue::from_t::<Option<u8>>(None).unwrap(),
+            ),
+            NamedArg::new("d".to_string(), CLValue::from_t("e".to_string()).unwrap()),
+        ]);
+        assert_eq!(args, expected);
+    }
+
+    #[test]
+    fn should_report_position_of_invalid_simple_arg() {
+        let position = |value| match arg_simple::session::parse(&[value]) {
+            Err(Error::InvalidSimpleArg { arg, position, .. }) => {
+                assert_eq!(arg, value);
+                position
+            }
+            other => panic!("unexpected result for {}: {:?}", value, other),
+        };
+        assert_eq!(position("x:u8"), 5);
+        assert_eq!(position("x:u8=1"), 6);
+        assert_eq!(position(r#"a:u8="1" b:u8="2""#), 10);
+
+        match arg_simple::session::parse(&[r#"a:u8="1", b:u8='256'"#]) {
+            Err(Error::InvalidCLValue(message)) => {
+                assert!(message.contains("at character 16"), "{}", message)
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
     }
 
     #[test]
@@ -851,8 +1151,27 @@ mod tests {
         let key_account = Key::Account(AccountHash::new(array));
         let key_hash = Key::Hash(array);
         let key_uref = Key::URef(URef::new(array, AccessRights::NONE));
-
-        for key in &[key_account, key_hash, key_uref] {
+        let key_transfer = Key::Transfer(TransferAddr::new(array));
+        let key_deploy = Key::DeployInfo(DeployHash::new(array));
+        let key_era = Key::EraInfo(EraId::new(1));
+        let key_balance = Key::Balance(array);
+        let key_bid = Key::Bid(AccountHash::new(array));
+        let key_withdraw = Key::Withdraw(AccountHash::new(array));
+        let key_dictionary = Key::Dictionary(array);
+
+        for key in &[
+            key_account,
+            key_hash,
+            key_uref,
+            key_transfer,
+            key_deploy,
+            key_era,
+            key_balance,
+            key_bid,
+            key_withdraw,
+            key_dictionary,
+            Key::SystemContractRegistry,
+        ] {
             valid_simple_args_test(&format!("x:key='{}'", key.to_formatted_string()), *key);
             valid_simple_args_test(
                 &format!("x:opt_key='{}'", key.to_formatted_string()),
@@ -900,6 +1219,146 @@ mod tests {
         valid_simple_args_test::<Option<PublicKey>>("x:opt_public_key=null", None);
     }
 
+    #[test]
+    fn should_parse_bytes_via_args_simple() {
+        let value = Bytes::from(vec![10_u8, 11, 12]);
+        let (_temp_dir, path) = args_file("blob", "\n\u{b}\u{c}");
+        valid_simple_args_test("x:bytes='0a0b0c'", value.clone());
+        valid_simple_args_test(&format!("x:bytes=@{}", path), value.clone());
+        valid_simple_args_test(&format!("x:bytes='@{}'", path), value.clone());
+        valid_simple_args_test("x:bytes=''", Bytes::new());
+        valid_simple_args_test(&format!("x:opt_bytes=@{}", path), Some(value.clone()));
+        valid_simple_args_test::<Option<Bytes>>("x:opt_bytes=null", None);
+        valid_simple_args_test("x:list<u8>='[10, 11, 12]'", value.clone());
+        valid_simple_args_test(&format!("x:byte_array_3=@{}", path), [10_u8, 11, 12]);
+        valid_simple_args_test(
+            &format!("x:list<bytes>='[0a, @{}]'", path),
+            vec![Bytes::from(vec![10_u8]), value],
+        );
+
+        invalid_simple_args_test("x:bytes=0a0b0c");
+        invalid_simple_args_test("x:string=@path");
+        invalid_simple_args_test(&format!("x:byte_array_4=@{}", path));
+        assert!(matches!(
+            arg_simple::session::parse(&["x:bytes=@/no/such/file"]),
+            Err(Error::IoError { .. })
+        ));
+    }
+
+    #[test]
+    fn should_parse_compound_types_via_args_simple() {
+        let bytes = (1..33).collect::<Vec<_>>();
+        let array = <[u8; 32]>::try_from(bytes.as_ref()).unwrap();
+        let key_account = Key::Account(AccountHash::new(array));
+        let key_hash = Key::Hash(array);
+
+        valid_simple_args_test("x:list<u64>='[1, 2, 3]'", vec![1_u64, 2, 3]);
+        valid_simple_args_test("x:list<u256>='[]'", Vec::<U256>::new());
+        valid_simple_args_test(
+            "x:list<list<u32>>='[[1], [], [2, 3]]'",
+            vec![vec![1_u32], vec![], vec![2, 3]],
+        );
+        valid_simple_args_test(
+            &format!(
+                "x:map<string,key>='{{alice: {}, \"bob, jr\": {}}}'",
+                key_account.to_formatted_string(),
+                key_hash.to_formatted_string()
+            ),
+            BTreeMap::from([
+                ("alice".to_string(), key_account),
+                ("bob, jr".to_string(), key_hash),
+            ]),
+        );
+        valid_simple_args_test(
+            &format!("x:byte_array_32='{}'", base16::encode_lower(&array)),
+            array,
+        );
+        valid_simple_args_test("x:byte_array_2='0aff'", [10_u8, 255]);
+        valid_simple_args_test(
+            "x:tuple2<u8,string>='(1, \"a, \\\"quoted\\\" value\")'",
+            (1_u8, "a, \"quoted\" value".to_string()),
+        );
+        valid_simple_args_test("x:tuple1<bool>='(true)'", (true,));
+        valid_simple_args_test(
+            "x:tuple3<i32,unit,string>='(-1, (), x)'",
+            (-1_i32, (), "x".to_string()),
+        );
+        valid_simple_args_test(
+            &format!("x:option<list<key>>='[{}]'", key_hash.to_formatted_string()),
+            Some(vec![key_hash]),
+        );
+        valid_simple_args_test::<Option<Vec<Key>>>("x:option<list<key>>=null", None);
+        valid_simple_args_test("x:list<option<i64>>='[1, null]'", vec![Some(1_i64), None]);
+        valid_simple_args_test("x:opt_u8='1'", Some(1_u8));
+        valid_simple_args_test("x:option<option<u8>>='null'", Some(None::<u8>));
+        valid_simple_args_test::<StdResult<u64, String>>("x:result<u64,string>='ok(5)'", Ok(5));
+        valid_simple_args_test::<StdResult<u64, String>>(
+            "x:Result<U64, String>='Err(failed)'",
+            Err("failed".to_string()),
+        );
+    }
+
+    #[test]
+    fn should_serialize_map_args_in_key_order() {
+        // The serialized keys sort differently to the keys themselves, e.g. due to the length
+        // prefix of a string or the little-endian encoding of an integer.
+        valid_simple_args_test(
+            "x:map<string,u8>='{b: 1, aa: 2, a: 3}'",
+            BTreeMap::from([
+                ("b".to_string(), 1_u8),
+                ("aa".to_string(), 2),
+                ("a".to_string(), 3),
+            ]),
+        );
+        valid_simple_args_test(
+            "x:map<u32,bool>='{256: true, 1: false}'",
+            BTreeMap::from([(256_u32, true), (1, false)]),
+        );
+        valid_simple_args_test(
+            "x:map<option<i32>,unit>='{1: (), -1: (), null: ()}'",
+            BTreeMap::from([(Some(1_i32), ()), (Some(-1), ()), (None, ())]),
+        );
+        valid_simple_args_test(
+            "x:map<tuple2<string,u64>,u8>='{(b, 1): 1, (a, 256): 2, (a, 2): 3}'",
+            BTreeMap::from([
+                (("b".to_string(), 1_u64), 1_u8),
+                (("a".to_string(), 256), 2),
+                (("a".to_string(), 2), 3),
+            ]),
+        );
+
+        let (_temp_dir, path) = args_file(
+            "args.json",
+            r#"[{"name": "x", "type": "map<u64,string>", "value": [
+                {"key": 256, "value": "a"}, {"key": 1, "value": "b"}
+            ]}]"#,
+        );
+        let expected = RuntimeArgs::from(vec![NamedArg::new(
+            "x".to_string(),
+            CLValue::from_t(BTreeMap::from([
+                (256_u64, "a".to_string()),
+                (1, "b".to_string()),
+            ]))
+            .unwrap(),
+        )]);
+        assert_eq!(args_complex::session::parse(&path).unwrap(), Some(expected));
+    }
+
+    #[test]
+    fn should_fail_to_parse_bad_compound_args() {
+        invalid_simple_args_test("x:list<u8>='1, 2'");
+        invalid_simple_args_test("x:list<u8>='[1, 256]'");
+        invalid_simple_args_test("x:list<u8,u8>='[1]'");
+        invalid_simple_args_test("x:list<wat>='[1]'");
+        invalid_simple_args_test("x:map<string,u8>='{a: 1, a: 2}'");
+        invalid_simple_args_test("x:map<string,u8>='{a 1}'");
+        invalid_simple_args_test("x:byte_array_2='0a'");
+        invalid_simple_args_test("x:byte_array_2='zzzz'");
+        invalid_simple_args_test("x:tuple2<u8,u8>='(1)'");
+        invalid_simple_args_test("x:result<u8,u8>='1'");
+        invalid_simple_args_test("x:tuple4<u8,u8,u8,u8>='(1, 2, 3, 4)'");
+    }
+
     #[test]
     fn should_fail_to_parse_bad_args() {
         invalid_simple_args_test(bad::ARG_BAD_TYPE);
@@ -994,6 +1453,28 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn should_fail_to_parse_malformed_payment_amount() {
+        for payment_amount in &["2.5cpsr", "2.5 motes", "1.0000000001cspr"] {
+            let result = parse_payment_info(PaymentStrParams::with_amount(payment_amount));
+            assert!(
+                matches!(
+                    result,
+                    Err(Error::FailedToParseUint {
+                        context: "amount",
+                        ..
+                    }) | Err(Error::InvalidArgument {
+                        context: "amount",
+                        ..
+                    })
+                ),
+                "{}: {:?}",
+                payment_amount,
+                result
+            );
+        }
+    }
+
     #[test]
     fn should_fail_to_parse_bad_session_args_complex() {
         let missing_file = "missing/file";
@@ -1040,6 +1521,264 @@ mod tests {
         ));
     }
 
+    fn args_file(file_name: &str, contents: &str) -> (tempfile::TempDir, String) {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join(file_name);
+        fs::write(&path, contents).unwrap();
+        (temp_dir, path.display().to_string())
+    }
+
+    #[test]
+    fn should_parse_typed_args_complex() {
+        let key = Key::Hash([1; 32]);
+        let expected = RuntimeArgs::from(vec![
+            NamedArg::new("a".to_string(), CLValue::from_t(U512::from(7)).unwrap()),
+            NamedArg::new(
+                "b".to_string(),
+                CLValue::from_t(vec![U512::from(1), U512::from(2)]).unwrap(),
+            ),
+            NamedArg::new(
+                "c".to_string(),
+                CLValue::from_t(BTreeMap::from([("x".to_string(), key)])).unwrap(),
+            ),
+            NamedArg::new(
+                "d".to_string(),
+                CLValue::from_t((1_u8, Some("y".to_string()))).unwrap(),
+            ),
+            NamedArg::new(
+                "e".to_string(),
+                CLValue::from_t(StdResult::<u64, String>::Err("z".to_string())).unwrap(),
+            ),
+            NamedArg::new("f".to_string(), CLValue::from_t(()).unwrap()),
+            NamedArg::new("g".to_string(), CLValue::from_t(7_u32).unwrap()),
+            NamedArg::new(
+                "h".to_string(),
+                CLValue::from_t(Bytes::from(vec![1_u8, 2])).unwrap(),
+            ),
+        ]);
+        let contents = format!(
+            r#"[
+                {{"name": "a", "type": "U512", "value": 7}},
+                {{"name": "b", "type": "List<U512>", "value": [1, "2"]}},
+                {{"name": "c", "type": "map<string,key>", "value": {{"x": "{}"}}}},
+                {{"name": "d", "type": {{"Tuple2": ["U8", {{"Option": "String"}}]}}, "value": [1, "y"]}},
+                {{"name": "e", "type": "Result<U64, String>", "value": {{"Err": "z"}}}},
+                {{"name": "f", "type": "Unit", "value": null}},
+                {{"name": "g", "value": {{"raw_bytes": "040000000700000004"}}}},
+                {{"name": "h", "type": "bytes", "value": "0102"}}
+            ]"#,
+            key.to_formatted_string()
+        );
+        let (_temp_dir, path) = args_file("args.json", &contents);
+        assert_eq!(
+            args_complex::session::parse(&path).unwrap(),
+            Some(expected.clone())
+        );
+
+        // The `cl_type` and `parsed` fields of each arg in a deploy's JSON can be passed back.
+        let round_tripped = expected
+            .named_args()
+            .map(|named_arg| {
+                let json = serde_json::to_value(named_arg.cl_value()).unwrap();
+                serde_json::json!({
+                    "name": named_arg.name(),
+                    "type": json["cl_type"],
+                    "value": json["parsed"],
+                })
+            })
+            .collect::<Vec<_>>();
+        let (_temp_dir, path) =
+            args_file("args.json", &serde_json::to_string(&round_tripped).unwrap());
+        assert_eq!(args_complex::payment::parse(&path).unwrap(), Some(expected));
+    }
+
+    #[test]
+    fn should_read_files_relative_to_args_file() {
+        let (temp_dir, path) = args_file(
+            "args.yaml",
+            "- {name: a, type: bytes, value: '@blob'}\n\
+             - {name: b, type: byte_array_2, value: '@nested/blob'}\n",
+        );
+        fs::write(temp_dir.path().join("blob"), [1_u8, 2]).unwrap();
+        fs::create_dir(temp_dir.path().join("nested")).unwrap();
+        fs::write(temp_dir.path().join("nested/blob"), [3_u8, 4]).unwrap();
+        let expected = RuntimeArgs::from(vec![
+            NamedArg::new(
+                "a".to_string(),
+                CLValue::from_t(Bytes::from(vec![1_u8, 2])).unwrap(),
+            ),
+            NamedArg::new("b".to_string(), CLValue::from_t([3_u8, 4]).unwrap()),
+        ]);
+        assert_eq!(args_complex::session::parse(&path).unwrap(), Some(expected));
+    }
+
+    #[test]
+    fn should_fail_to_parse_invalid_typed_args_complex() {
+        for contents in [
+            r#"[{"name": "a", "type": "U8", "value": 256}]"#,
+            r#"[{"name": "a", "type": "List<U8>", "value": 1}]"#,
+            r#"[{"name": "a", "type": "Tuple2<U8, U8>", "value": [1]}]"#,
+            r#"[{"name": "a", "type": "Result<U8, U8>", "value": {"Maybe": 1}}]"#,
+            r#"[{"name": "a", "value": 1}]"#,
+        ] {
+            let (_temp_dir, path) = args_file("args.json", contents);
+            assert!(
+                matches!(
+                    args_complex::session::parse(&path),
+                    Err(Error::FailedToParseArgsFile { error, .. }) if error.contains("arg 'a'")
+                ),
+                "{}",
+                contents
+            );
+        }
+
+        let (_temp_dir, path) =
+            args_file("args.json", r#"[{"name": "a", "type": "U9", "value": 1}]"#);
+        assert!(matches!(
+            args_complex::session::parse(&path),
+            Err(Error::FailedToParseArgsFile { .. })
+        ));
+    }
+
+    #[test]
+    fn should_parse_toml_and_yaml_args_files() {
+        let expected = Some(RuntimeArgs::from(vec![
+            NamedArg::new(
+                "amount".to_string(),
+                CLValue::from_t(U512::from(7)).unwrap(),
+            ),
+            NamedArg::new("ids".to_string(), CLValue::from_t(vec![1_u64, 2]).unwrap()),
+            NamedArg::new("none".to_string(), CLValue::from_t(None::<u8>).unwrap()),
+        ]));
+        const TOML: &str = r#"
+[[args]]
+name = "amount"
+type = "U512"
+value = "7"
+
+[[args]]
+name = "ids"
+type = "List<U64>"
+value = [1, 2]
+
+[[args]]
+name = "none"
+type = "Option<U8>"
+"#;
+        const YAML: &str = r#"
+- name: amount
+  type: U512
+  value: 7
+- name: ids
+  type: List<U64>
+  value: [1, 2]
+- name: none
+  type: Option<U8>
+  value: null
+"#;
+        for (file_name, contents) in [
+            ("args.toml", TOML),
+            ("args.yaml", YAML),
+            ("toml_args", TOML),
+            ("yaml_args", YAML),
+        ] {
+            let (_temp_dir, path) = args_file(file_name, contents);
+            assert_eq!(
+                args_complex::session::parse(&path).unwrap(),
+                expected,
+                "{}",
+                file_name
+            );
+        }
+        let (_temp_dir, path) = args_file("args.yml", &format!("args:\n{}", YAML));
+        assert_eq!(args_complex::payment::parse(&path).unwrap(), expected);
+    }
+
+    #[test]
+    fn should_report_line_of_invalid_arg_in_args_file() {
+        const TOML: &str = r#"[[args]]
+name = "a"
+type = "U8"
+value = 1
+
+[[args]]
+name = "b"
+type = "U8"
+value = 256
+"#;
+        const YAML: &str = "- name: a\n  type: U8\n  value: 1\n- name: b\n  type: U9\n  value: 1\n";
+        for (file_name, contents, expected_context) in [
+            ("args.toml", TOML, ":7"),
+            ("args.yaml", YAML, ":4"),
+            ("args.toml", "[[args]\n", ":1"),
+            ("args.yaml", "- name: a\n  type: [U8\n", ":3"),
+        ] {
+            let (_temp_dir, path) = args_file(file_name, contents);
+            match args_complex::session::parse(&path) {
+                Err(Error::FailedToParseArgsFile { context, .. }) => {
+                    assert_eq!(context, format!("{}{}", path, expected_context))
+                }
+                result => panic!("unexpected result for {}: {:?}", contents, result),
+            }
+        }
+    }
+
+    #[test]
+    fn should_fail_to_decode_malformed_raw_bytes() {
+        for (raw_bytes, expected_type, expected_offset, expected_error) in [
+            (
+                "0400000007000000",
+                "unknown CLType",
+                8,
+                bytesrepr::Error::EarlyEndOfStream,
+            ),
+            (
+                "040000000700000004ff",
+                "U32",
+                9,
+                bytesrepr::Error::LeftOverBytes,
+            ),
+            (
+                "0300000007000004",
+                "U32",
+                4,
+                bytesrepr::Error::EarlyEndOfStream,
+            ),
+            (
+                // A list of two u32s holding only one, followed by the type `List(U32)`.
+                "0800000002000000070000000e04",
+                "List(U32)",
+                12,
+                bytesrepr::Error::EarlyEndOfStream,
+            ),
+        ] {
+            let contents = format!(
+                r#"[
+                    {{"name": "ok", "value": {{"raw_bytes": "040000000700000004"}}}},
+                    {{"name": "bad", "value": {{"raw_bytes": "{}"}}}}
+                ]"#,
+                raw_bytes
+            );
+            let (_temp_dir, path) = args_file("args.json", &contents);
+            match args_complex::session::parse(&path) {
+                Err(Error::FailedToDecodeComplexArg {
+                    name,
+                    index,
+                    cl_type,
+                    offset,
+                    error,
+                }) => {
+                    assert_eq!(name, "bad");
+                    assert_eq!(index, 1);
+                    assert_eq!(cl_type, expected_type);
+                    assert_eq!(offset, expected_offset, "{}", raw_bytes);
+                    assert_eq!(error, expected_error);
+                }
+                result => panic!("unexpected result for {}: {:?}", raw_bytes, result),
+            }
+        }
+    }
+
     #[test]
     fn should_parse_valid_deploy_params() {
         // create secret key file in tempdir.
@@ -1057,12 +1796,14 @@ mod tests {
 
         let params = parse_deploy_params(
             secret_key_path_str,
+            "",
             timestamp,
             "2sec",
             "10000",
             &[happy::HASH],
             "test",
             "",
+            "",
         );
 
         assert!(params.is_ok());
@@ -1083,8 +1824,17 @@ mod tests {
         let timestamp = Timestamp::now().to_string();
         let timestamp = timestamp.as_str();
 
-        let result =
-            parse_deploy_params("bad file path", timestamp, "2sec", "10000", &[], "test", "");
+        let result = parse_deploy_params(
+            "bad file path",
+            "",
+            timestamp,
+            "2sec",
+            "10000",
+            &[],
+            "test",
+            "",
+            "",
+        );
 
         // failed to parse secret key file path.
         assert!(matches!(
@@ -1094,12 +1844,14 @@ mod tests {
 
         let result = parse_deploy_params(
             secret_key_path_str,
+            "",
             "bad timestamp",
             "2sec",
             "10000",
             &[],
             "test",
             "",
+            "",
         );
 
         // failed to parse timestamp.
@@ -1110,12 +1862,14 @@ mod tests {
 
         let result = parse_deploy_params(
             secret_key_path_str,
+            "",
             timestamp,
             "bad ttl",
             "10000",
             &[],
             "test",
             "",
+            "",
         );
 
         // failed to parse ttl.
@@ -1126,12 +1880,14 @@ mod tests {
 
         let result = parse_deploy_params(
             secret_key_path_str,
+            "",
             timestamp,
             "2sec",
             "bad gas price",
             &[],
             "test",
             "",
+            "",
         );
 
         // failed to parse gas price.
@@ -1142,12 +1898,14 @@ mod tests {
 
         let result = parse_deploy_params(
             secret_key_path_str,
+            "",
             timestamp,
             "2sec",
             "10000",
             &["bad deploy hash"],
             "test",
             "",
+            "",
         );
 
         // failed to parse deploy hash.
@@ -1266,7 +2024,7 @@ mod tests {
         /// ];
         /// ```
         /// This generates the following test module (with the fn name passed), with one test per line in `session_str_params[]`:
-        /// ```
+        /// ```ignore
         /// #[cfg(test)]
         /// mod session_str_params {
         ///     use super::*;
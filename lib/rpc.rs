@@ -32,8 +32,9 @@ use casper_types::{AsymmetricType, Key, PublicKey, URef};
 
 use crate::{
     deploy::{DeployExt, DeployParams, SendDeploy, Transfer},
+    deploy_limits::DeployLimits,
     error::{Error, Result},
-    validation, DictionaryItemStrParams, GlobalStateStrParams,
+    global_state_key, validation, DictionaryItemStrParams, GlobalStateStrParams,
 };
 
 /// Struct representing a single JSON-RPC call to the casper node.
@@ -94,15 +95,7 @@ impl RpcCall {
         let state_root_hash = Digest::from_hex(state_root_hash)
             .map_err(|error| map_hashing_error(error)("state_root_hash"))?;
 
-        let key = {
-            if let Ok(key) = Key::from_formatted_str(key) {
-                key
-            } else if let Ok(public_key) = PublicKey::from_hex(key) {
-                Key::Account(public_key.to_account_hash())
-            } else {
-                return Err(Error::FailedToParseKey);
-            }
-        };
+        let key = global_state_key::parse_query_key(key)?;
 
         let path = if path.is_empty() {
             vec![]
@@ -228,12 +221,16 @@ impl RpcCall {
         Transfer::request_with_map_params(self, params).await
     }
 
-    pub(crate) async fn send_deploy_file(self, input_path: &str) -> Result<JsonRpc> {
+    pub(crate) async fn send_deploy_file(
+        self,
+        input_path: &str,
+        deploy_limits: &DeployLimits,
+    ) -> Result<JsonRpc> {
         let input = File::open(input_path).map_err(|error| Error::IoError {
             context: format!("unable to read input file '{}'", input_path),
             error,
         })?;
-        let deploy = Deploy::read_deploy(input)?;
+        let deploy = Deploy::read_deploy(input, deploy_limits)?;
         let params = PutDeployParams { deploy };
         SendDeploy::request_with_map_params(self, params).await
     }
@@ -294,15 +291,7 @@ impl RpcCall {
     ) -> Result<JsonRpc> {
         let global_state_identifier: GlobalStateIdentifier = global_state_str_params.try_into()?;
 
-        let key = {
-            if let Ok(key) = Key::from_formatted_str(key) {
-                key
-            } else if let Ok(public_key) = PublicKey::from_hex(key) {
-                Key::Account(public_key.to_account_hash())
-            } else {
-                return Err(Error::FailedToParseKey);
-            }
-        };
+        let key = global_state_key::parse_query_key(key)?;
 
         let path = if path.is_empty() {
             vec![]
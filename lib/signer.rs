@@ -0,0 +1,401 @@
+//! Signing of `Deploy`s, either with a secret key loaded by the client or by an external program.
+
+use std::{
+    collections::BTreeSet,
+    io::Write,
+    path::PathBuf,
+    process::{Command, Stdio},
+    sync::Arc,
+};
+
+use serde::{Deserialize, Serialize};
+
+use casper_node::{
+    crypto::AsymmetricKeyExt,
+    types::{Approval, Deploy, DeployHash},
+};
+use casper_types::{
+    bytesrepr::{self, ToBytes},
+    AsymmetricType, PublicKey, SecretKey, Signature,
+};
+
+use crate::{
+    error::{Error, Result},
+    parsing,
+};
+
+/// The prefix of a signer specification naming a secret key PEM file.
+const PEM_PREFIX: &str = "pem:";
+/// The prefix of a signer specification naming an external signing program.
+const EXEC_PREFIX: &str = "exec:";
+
+/// A means of signing `Deploy`s.
+///
+/// Implement this to sign with keys which are held outside the client process, e.g. in a hardware
+/// security module or a remote key management service.
+pub trait Signer: Send + Sync {
+    /// Returns the public key corresponding to the key used for signing.
+    ///
+    /// This is used as the account of a new `Deploy` if no session account is specified.
+    fn public_key(&self) -> Result<PublicKey>;
+
+    /// Signs the given deploy hash, returning the signature and the public key of the signer.
+    fn sign(&self, deploy_hash: &DeployHash) -> Result<(Signature, PublicKey)>;
+}
+
+/// A shared [`Signer`], allowing one signer to be used for many `Deploy`s without being set up again
+/// for each, e.g. without re-reading an encrypted secret key file.
+impl<S: Signer + ?Sized> Signer for Arc<S> {
+    fn public_key(&self) -> Result<PublicKey> {
+        (**self).public_key()
+    }
+
+    fn sign(&self, deploy_hash: &DeployHash) -> Result<(Signature, PublicKey)> {
+        (**self).sign(deploy_hash)
+    }
+}
+
+/// A [`Signer`] using a secret key read from a PEM file.
+pub struct PemSigner {
+    secret_key: SecretKey,
+}
+
+impl PemSigner {
+    /// Constructs a signer using the given secret key.
+    pub fn new(secret_key: SecretKey) -> Self {
+        PemSigner { secret_key }
+    }
+
+    /// Reads the secret key from the PEM file at `path`.
+    pub fn from_file(path: &str) -> Result<Self> {
+        parsing::secret_key(path).map(PemSigner::new)
+    }
+}
+
+impl Signer for PemSigner {
+    fn public_key(&self) -> Result<PublicKey> {
+        Ok(PublicKey::from(&self.secret_key))
+    }
+
+    fn sign(&self, deploy_hash: &DeployHash) -> Result<(Signature, PublicKey)> {
+        let approval = Approval::create(deploy_hash, &self.secret_key);
+        Ok((*approval.signature(), approval.signer().clone()))
+    }
+}
+
+/// A request sent to an external signing program.
+#[derive(Serialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+enum ExecRequest {
+    PublicKey,
+    Sign { deploy_hash: String },
+}
+
+/// A response from an external signing program.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct ExecResponse {
+    public_key: Option<String>,
+    signature: Option<String>,
+    error: Option<String>,
+}
+
+/// A [`Signer`] which delegates to an external program, so that the secret key never enters the
+/// client process.
+///
+/// For each request, the program is run with no arguments and a single JSON object is written to
+/// its stdin.  It must write a single JSON object to its stdout and exit with status 0.
+///
+/// * `{"method": "public_key"}` requests the signer's public key, and must be answered with
+///   `{"public_key": "<HEX-ENCODED PUBLIC KEY>"}`.
+/// * `{"method": "sign", "deploy_hash": "<HEX-ENCODED DEPLOY HASH>"}` requests a signature of the
+///   deploy hash, and must be answered with
+///   `{"public_key": "<HEX-ENCODED PUBLIC KEY>", "signature": "<HEX-ENCODED SIGNATURE>"}`.
+///
+/// The public key and signature are hex-encoded with their leading algorithm tag byte, as in a
+/// `Deploy`'s approvals.  On failure the program may instead respond with `{"error": "<MESSAGE>"}`.
+pub struct ExecSigner {
+    program: PathBuf,
+}
+
+impl ExecSigner {
+    /// Constructs a signer which runs the program at `program`.
+    pub fn new<P: Into<PathBuf>>(program: P) -> Self {
+        ExecSigner {
+            program: program.into(),
+        }
+    }
+
+    fn error(&self, error: impl ToString) -> Error {
+        Error::SignerError {
+            context: self.program.display().to_string(),
+            error: error.to_string(),
+        }
+    }
+
+    fn request(&self, request: &ExecRequest) -> Result<ExecResponse> {
+        let mut child = Command::new(&self.program)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|error| self.error(format!("failed to run: {}", error)))?;
+
+        let request = serde_json::to_vec(request)?;
+        child
+            .stdin
+            .take()
+            .expect("should have piped stdin")
+            .write_all(&request)
+            .map_err(|error| self.error(format!("failed to write request: {}", error)))?;
+
+        let output = child
+            .wait_with_output()
+            .map_err(|error| self.error(format!("failed to read response: {}", error)))?;
+        if !output.status.success() {
+            return Err(self.error(format!(
+                "exited with {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        let response: ExecResponse = serde_json::from_slice(&output.stdout)
+            .map_err(|error| self.error(format!("invalid response: {}", error)))?;
+        match response.error {
+            Some(error) => Err(self.error(error)),
+            None => Ok(response),
+        }
+    }
+
+    fn public_key_from(&self, response: &ExecResponse) -> Result<PublicKey> {
+        let hex = response
+            .public_key
+            .as_ref()
+            .ok_or_else(|| self.error("response has no public_key"))?;
+        PublicKey::from_hex(hex)
+            .map_err(|error| self.error(format!("invalid public_key: {}", error)))
+    }
+}
+
+impl Signer for ExecSigner {
+    fn public_key(&self) -> Result<PublicKey> {
+        let response = self.request(&ExecRequest::PublicKey)?;
+        self.public_key_from(&response)
+    }
+
+    fn sign(&self, deploy_hash: &DeployHash) -> Result<(Signature, PublicKey)> {
+        let response = self.request(&ExecRequest::Sign {
+            deploy_hash: base16::encode_lower(deploy_hash),
+        })?;
+        let public_key = self.public_key_from(&response)?;
+        let hex = response
+            .signature
+            .as_ref()
+            .ok_or_else(|| self.error("response has no signature"))?;
+        let signature = Signature::from_hex(hex)
+            .map_err(|error| self.error(format!("invalid signature: {}", error)))?;
+        Ok((signature, public_key))
+    }
+}
+
+/// Parses a signer specification: `exec:PATH` for an [`ExecSigner`], or `pem:PATH` for a
+/// [`PemSigner`].
+pub(crate) fn parse(value: &str) -> Result<Box<dyn Signer>> {
+    if let Some(program) = value.strip_prefix(EXEC_PREFIX) {
+        return Ok(Box::new(ExecSigner::new(program)));
+    }
+    if let Some(path) = value.strip_prefix(PEM_PREFIX) {
+        return Ok(Box::new(PemSigner::from_file(path)?));
+    }
+    Err(Error::InvalidArgument {
+        context: "signer",
+        error: format!(
+            "expected '{}PATH' or '{}PATH', got '{}'",
+            EXEC_PREFIX, PEM_PREFIX, value
+        ),
+    })
+}
+
+/// Returns the signer given by the specification `signer`, or if that is empty, a [`PemSigner`]
+/// using the secret key file at `secret_key`.
+///
+/// Only one of `secret_key` and `signer` may be non-empty.
+pub(crate) fn from_args(secret_key: &str, signer: &str) -> Result<Box<dyn Signer>> {
+    if signer.is_empty() {
+        return Ok(Box::new(PemSigner::from_file(secret_key)?));
+    }
+    if !secret_key.is_empty() {
+        return Err(Error::ConflictingArguments {
+            context: "signer",
+            args: vec![
+                format!("secret_key={}", secret_key),
+                format!("signer={}", signer),
+            ],
+        });
+    }
+    parse(signer)
+}
+
+/// Rebuilds `deploy` with the given approvals in place of its existing ones.
+fn with_approvals(deploy: &Deploy, approvals: &BTreeSet<Approval>) -> Result<Deploy> {
+    let mut bytes = deploy.header().to_bytes()?;
+    bytes.extend(deploy.id().to_bytes()?);
+    bytes.extend(deploy.payment().to_bytes()?);
+    bytes.extend(deploy.session().to_bytes()?);
+    bytes.extend(approvals.to_bytes()?);
+    Ok(bytesrepr::deserialize(bytes)?)
+}
+
+/// Signs `deploy` using `signer`, adding the new approval to any existing ones.
+///
+/// The signature is checked before being added, so an external signer which signs the wrong hash
+/// or with a key other than the one it reports is caught here rather than by the network.
+pub(crate) fn sign(deploy: &Deploy, signer: &dyn Signer) -> Result<Deploy> {
+    let (signature, public_key) = signer.sign(deploy.id())?;
+    let mut approval_bytes = public_key.to_bytes()?;
+    approval_bytes.extend(signature.to_bytes()?);
+    let approval: Approval = bytesrepr::deserialize(approval_bytes)?;
+
+    let mut checked = with_approvals(deploy, &BTreeSet::from([approval.clone()]))?;
+    checked.is_valid().map_err(|error| Error::SignerError {
+        context: public_key.to_hex(),
+        error: format!("invalid approval: {}", error),
+    })?;
+
+    let mut approvals = deploy.approvals().clone();
+    approvals.insert(approval);
+    with_approvals(deploy, &approvals)
+}
+
+/// Returns a `Deploy` with no approvals, to be signed via [`sign`].
+///
+/// `Deploy::new` requires a secret key with which it signs the new `Deploy`, so an ephemeral key
+/// is used and its approval discarded.
+pub(crate) fn unsigned(build: impl FnOnce(&SecretKey) -> Deploy) -> Result<Deploy> {
+    let ephemeral_key = SecretKey::generate_ed25519().map_err(|error| Error::CryptoError {
+        context: "ephemeral_key",
+        error,
+    })?;
+    let deploy = build(&ephemeral_key);
+    with_approvals(&deploy, &BTreeSet::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A signer which signs with a different key from the one it reports.
+    struct MismatchedSigner {
+        signing_key: SecretKey,
+        reported_key: PublicKey,
+    }
+
+    impl Signer for MismatchedSigner {
+        fn public_key(&self) -> Result<PublicKey> {
+            Ok(self.reported_key.clone())
+        }
+
+        fn sign(&self, deploy_hash: &DeployHash) -> Result<(Signature, PublicKey)> {
+            let approval = Approval::create(deploy_hash, &self.signing_key);
+            Ok((*approval.signature(), self.reported_key.clone()))
+        }
+    }
+
+    fn sample_deploy() -> Deploy {
+        let deploy_params = crate::DeployStrParams {
+            secret_key: "resources/test.pem",
+            ttl: "10s",
+            chain_name: "casper-test-chain-name-1",
+            gas_price: "1",
+            ..Default::default()
+        };
+        let payment = crate::PaymentStrParams::with_amount("100");
+        let session = crate::SessionStrParams::with_transfer(
+            vec![
+                "amount:u512='100'",
+                "target:public_key='01522ef6c89038019cb7af05c340623804392dd2bb1f4dab5e4a9c3ab752fc0179'",
+            ],
+            "",
+        );
+        <Deploy as crate::deploy::DeployExt>::with_payment_and_session(
+            std::convert::TryInto::try_into(deploy_params).unwrap(),
+            std::convert::TryInto::try_into(payment).unwrap(),
+            std::convert::TryInto::try_into(session).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn should_parse_signer_specifications() {
+        let expected = PemSigner::from_file("resources/test.pem")
+            .unwrap()
+            .public_key()
+            .unwrap();
+        assert_eq!(
+            parse("pem:resources/test.pem")
+                .unwrap()
+                .public_key()
+                .unwrap(),
+            expected
+        );
+        assert!(parse("exec:/nonexistent/signer")
+            .unwrap()
+            .public_key()
+            .is_err());
+        assert!(matches!(
+            parse("resources/test.pem"),
+            Err(Error::InvalidArgument {
+                context: "signer",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn should_never_run_secret_key_as_program() {
+        // A secret key path is always read as a file, even if it looks like a signer
+        // specification.
+        assert!(matches!(
+            from_args("exec:/bin/true", ""),
+            Err(Error::CryptoError { .. })
+        ));
+        assert!(matches!(
+            from_args("resources/test.pem", "exec:/nonexistent/signer"),
+            Err(Error::ConflictingArguments { .. })
+        ));
+        assert!(from_args("resources/test.pem", "").is_ok());
+        assert!(from_args("", "pem:resources/test.pem").is_ok());
+    }
+
+    #[test]
+    fn should_add_approval() {
+        let deploy = sample_deploy();
+        let signer = PemSigner::new(SecretKey::generate_ed25519().unwrap());
+        let mut signed = sign(&deploy, &signer).unwrap();
+        assert_eq!(signed.approvals().len(), deploy.approvals().len() + 1);
+        assert_eq!(signed.id(), deploy.id());
+        assert!(signed.is_valid().is_ok());
+    }
+
+    #[test]
+    fn should_reject_invalid_signature() {
+        let deploy = sample_deploy();
+        let signer = MismatchedSigner {
+            signing_key: SecretKey::generate_ed25519().unwrap(),
+            reported_key: PublicKey::from(&SecretKey::generate_ed25519().unwrap()),
+        };
+        assert!(matches!(
+            sign(&deploy, &signer),
+            Err(Error::SignerError { .. })
+        ));
+    }
+
+    #[test]
+    fn should_build_unsigned_deploy() {
+        let deploy = sample_deploy();
+        let unsigned = unsigned(|_| deploy.clone()).unwrap();
+        assert!(unsigned.approvals().is_empty());
+        assert_eq!(unsigned.id(), deploy.id());
+    }
+}
@@ -0,0 +1,358 @@
+//! Support for creating `Deploy`s from template files containing `${NAME}` placeholders.
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{
+    error::{Error, Result},
+    DeployStrParams, PaymentStrParams, SessionStrParams,
+};
+
+/// The time to live used if neither the template nor the caller specifies one.
+const DEFAULT_TTL: &str = "30min";
+/// The gas price used if neither the template nor the caller specifies one.
+const DEFAULT_GAS_PRICE: &str = "1";
+
+/// The header defaults of a template, held in its `[deploy]` section.
+#[derive(Deserialize, Default, Debug)]
+#[serde(default, deny_unknown_fields)]
+struct HeaderTemplate {
+    ttl: String,
+    gas_price: String,
+    dependencies: Vec<String>,
+    chain_name: String,
+    session_account: String,
+    chainspec: String,
+}
+
+/// The session of a template, held in its `[session]` section.
+#[derive(Deserialize, Default, Debug)]
+#[serde(default, deny_unknown_fields)]
+struct SessionTemplate {
+    hash: String,
+    name: String,
+    package_hash: String,
+    package_name: String,
+    path: String,
+    args: Vec<String>,
+    args_complex: String,
+    version: String,
+    entry_point: String,
+    transfer: bool,
+}
+
+/// The payment of a template, held in its `[payment]` section.
+#[derive(Deserialize, Default, Debug)]
+#[serde(default, deny_unknown_fields)]
+struct PaymentTemplate {
+    amount: String,
+    hash: String,
+    name: String,
+    package_hash: String,
+    package_name: String,
+    path: String,
+    args: Vec<String>,
+    args_complex: String,
+    version: String,
+    entry_point: String,
+}
+
+/// A deploy template with all of its placeholders substituted.
+#[derive(Deserialize, Default, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct DeployTemplate {
+    deploy: HeaderTemplate,
+    session: SessionTemplate,
+    payment: PaymentTemplate,
+}
+
+fn template_error(path: &str, error: impl ToString) -> Error {
+    Error::FailedToParseTemplate {
+        context: path.to_string(),
+        error: error.to_string(),
+    }
+}
+
+/// Parses `NAME=VALUE` pairs into a map of template variables.
+fn parse_vars(vars: &[&str]) -> Result<BTreeMap<String, String>> {
+    vars.iter()
+        .map(|var| match var.split_once('=') {
+            Some((name, value)) if !name.trim().is_empty() => {
+                Ok((name.trim().to_string(), value.to_string()))
+            }
+            _ => Err(Error::InvalidArgument {
+                context: "template_vars",
+                error: format!("expected 'NAME=VALUE', got '{}'", var),
+            }),
+        })
+        .collect()
+}
+
+/// Replaces every `${NAME}` in `value` with the corresponding variable.  `$$` is replaced with a
+/// literal `$`.
+fn substitute(value: &str, vars: &BTreeMap<String, String>) -> std::result::Result<String, String> {
+    let mut output = String::with_capacity(value.len());
+    let mut remainder = value;
+    while let Some(index) = remainder.find('$') {
+        output.push_str(&remainder[..index]);
+        let after_dollar = &remainder[index + 1..];
+        if let Some(rest) = after_dollar.strip_prefix('$') {
+            output.push('$');
+            remainder = rest;
+        } else if let Some(rest) = after_dollar.strip_prefix('{') {
+            let end = rest
+                .find('}')
+                .ok_or_else(|| format!("unterminated placeholder in '{}'", value))?;
+            let name = &rest[..end];
+            let replacement = vars
+                .get(name)
+                .ok_or_else(|| format!("no value provided for variable '{}'", name))?;
+            output.push_str(replacement);
+            remainder = &rest[end + 1..];
+        } else {
+            output.push('$');
+            remainder = after_dollar;
+        }
+    }
+    output.push_str(remainder);
+    Ok(output)
+}
+
+/// Substitutes the placeholders in every string held in `value`.
+fn substitute_all(
+    value: &mut Value,
+    vars: &BTreeMap<String, String>,
+) -> std::result::Result<(), String> {
+    match value {
+        Value::String(string) => *string = substitute(string, vars)?,
+        Value::Array(values) => {
+            for value in values {
+                substitute_all(value, vars)?;
+            }
+        }
+        Value::Object(map) => {
+            for value in map.values_mut() {
+                substitute_all(value, vars)?;
+            }
+        }
+        Value::Null | Value::Bool(_) | Value::Number(_) => {}
+    }
+    Ok(())
+}
+
+impl DeployTemplate {
+    /// Reads the template at `path`, parsing it as JSON if it has a `.json` extension, otherwise
+    /// as TOML, and substitutes the given `NAME=VALUE` variables into its placeholders.
+    pub(crate) fn read(path: &str, vars: &[&str]) -> Result<Self> {
+        let vars = parse_vars(vars)?;
+        let contents = fs::read_to_string(path).map_err(|error| Error::IoError {
+            context: format!("unable to read template file at '{}'", path),
+            error,
+        })?;
+
+        let is_json = Path::new(path)
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+        let mut value: Value = if is_json {
+            serde_json::from_str(&contents).map_err(|error| template_error(path, error))?
+        } else {
+            let toml_value: toml::Value =
+                toml::from_str(&contents).map_err(|error| template_error(path, error))?;
+            serde_json::to_value(toml_value).map_err(|error| template_error(path, error))?
+        };
+
+        substitute_all(&mut value, &vars).map_err(|error| template_error(path, error))?;
+        serde_json::from_value(value).map_err(|error| template_error(path, error))
+    }
+
+    /// Returns the deploy params, where each non-empty field of `overrides` takes precedence over
+    /// the template's value.
+    pub(crate) fn deploy_str_params<'a>(
+        &'a self,
+        overrides: DeployStrParams<'a>,
+    ) -> DeployStrParams<'a> {
+        let or_template = |value: &'a str, template_value: &'a str| {
+            if value.is_empty() {
+                template_value
+            } else {
+                value
+            }
+        };
+        let header = &self.deploy;
+        DeployStrParams {
+            secret_key: overrides.secret_key,
+            signer: overrides.signer,
+            timestamp: overrides.timestamp,
+            ttl: or_template(overrides.ttl, or_template(&header.ttl, DEFAULT_TTL)),
+            gas_price: or_template(
+                overrides.gas_price,
+                or_template(&header.gas_price, DEFAULT_GAS_PRICE),
+            ),
+            dependencies: if overrides.dependencies.is_empty() {
+                header.dependencies.iter().map(String::as_str).collect()
+            } else {
+                overrides.dependencies
+            },
+            chain_name: or_template(overrides.chain_name, &header.chain_name),
+            session_account: or_template(overrides.session_account, &header.session_account),
+            chainspec: or_template(overrides.chainspec, &header.chainspec),
+        }
+    }
+
+    pub(crate) fn session_str_params(&self) -> SessionStrParams<'_> {
+        let session = &self.session;
+        SessionStrParams {
+            session_hash: &session.hash,
+            session_name: &session.name,
+            session_package_hash: &session.package_hash,
+            session_package_name: &session.package_name,
+            session_path: &session.path,
+            session_args_simple: session.args.iter().map(String::as_str).collect(),
+            session_args_complex: &session.args_complex,
+            session_version: &session.version,
+            session_entry_point: &session.entry_point,
+            is_session_transfer: session.transfer,
+        }
+    }
+
+    pub(crate) fn payment_str_params(&self) -> PaymentStrParams<'_> {
+        let payment = &self.payment;
+        PaymentStrParams {
+            payment_amount: &payment.amount,
+            payment_hash: &payment.hash,
+            payment_name: &payment.name,
+            payment_package_hash: &payment.package_hash,
+            payment_package_name: &payment.package_name,
+            payment_path: &payment.path,
+            payment_args_simple: payment.args.iter().map(String::as_str).collect(),
+            payment_args_complex: &payment.args_complex,
+            payment_version: &payment.version,
+            payment_entry_point: &payment.entry_point,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    const TOML_TEMPLATE: &str = r#"
+[deploy]
+chain_name = "casper-test"
+ttl = "1h"
+
+[session]
+name = "token"
+entry_point = "transfer"
+args = ["recipient:key='${recipient}'", "amount:u256='${amount}'"]
+
+[payment]
+amount = "${fee}"
+"#;
+
+    fn vars(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn should_substitute() {
+        let vars = vars(&[("a", "1"), ("b", "two")]);
+        assert_eq!(substitute("${a}-${b}", &vars).unwrap(), "1-two");
+        assert_eq!(
+            substitute("no placeholders", &vars).unwrap(),
+            "no placeholders"
+        );
+        assert_eq!(substitute("$$${a} $ ", &vars).unwrap(), "$1 $ ");
+        assert!(substitute("${c}", &vars).unwrap_err().contains("'c'"));
+        assert!(substitute("${a", &vars).is_err());
+    }
+
+    #[test]
+    fn should_parse_vars() {
+        let parsed = parse_vars(&["amount=100", "memo=a=b"]).unwrap();
+        assert_eq!(parsed["amount"], "100");
+        assert_eq!(parsed["memo"], "a=b");
+        assert!(parse_vars(&["amount"]).is_err());
+        assert!(parse_vars(&["=100"]).is_err());
+    }
+
+    #[test]
+    fn should_read_toml_template() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("template.toml");
+        fs::write(&path, TOML_TEMPLATE).unwrap();
+        let template = DeployTemplate::read(
+            path.to_str().unwrap(),
+            &[
+                "recipient=account-hash-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20",
+                "amount=100",
+                "fee=2500000000",
+            ],
+        )
+        .unwrap();
+
+        let session = template.session_str_params();
+        assert_eq!(session.session_name, "token");
+        assert_eq!(session.session_args_simple[1], "amount:u256='100'");
+        assert_eq!(template.payment_str_params().payment_amount, "2500000000");
+
+        let deploy = template.deploy_str_params(DeployStrParams {
+            gas_price: "2",
+            ..Default::default()
+        });
+        assert_eq!(deploy.chain_name, "casper-test");
+        assert_eq!(deploy.ttl, "1h");
+        assert_eq!(deploy.gas_price, "2");
+    }
+
+    #[test]
+    fn should_read_json_template() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("template.json");
+        fs::write(
+            &path,
+            r#"{"session": {"transfer": true, "args": ["target:public_key='${to}'"]}}"#,
+        )
+        .unwrap();
+        let template = DeployTemplate::read(path.to_str().unwrap(), &["to=01aa"]).unwrap();
+        let session = template.session_str_params();
+        assert!(session.is_session_transfer);
+        assert_eq!(
+            session.session_args_simple,
+            vec!["target:public_key='01aa'"]
+        );
+
+        let deploy = template.deploy_str_params(DeployStrParams::default());
+        assert_eq!(deploy.ttl, DEFAULT_TTL);
+        assert_eq!(deploy.gas_price, DEFAULT_GAS_PRICE);
+    }
+
+    #[test]
+    fn should_fail_to_read_template_with_missing_var() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("template.toml");
+        fs::write(&path, TOML_TEMPLATE).unwrap();
+        assert!(matches!(
+            DeployTemplate::read(path.to_str().unwrap(), &["amount=1", "fee=1"]),
+            Err(Error::FailedToParseTemplate { error, .. }) if error.contains("recipient")
+        ));
+    }
+
+    #[test]
+    fn should_fail_to_read_template_with_unknown_field() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("template.toml");
+        fs::write(&path, "[session]\nnmae = 'token'\n").unwrap();
+        assert!(matches!(
+            DeployTemplate::read(path.to_str().unwrap(), &[]),
+            Err(Error::FailedToParseTemplate { .. })
+        ));
+    }
+}
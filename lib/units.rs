@@ -0,0 +1,241 @@
+//! Parsing and display of amounts of motes, optionally denominated in CSPR.
+//!
+//! An amount can be given as a plain integer number of motes, e.g. `2500000000`, or with a unit
+//! suffix, e.g. `2500000000motes` or `2.5cspr`.  The suffix is case-insensitive and may be
+//! separated from the number by whitespace.  One CSPR is 10^9 motes, so a CSPR amount may have up
+//! to nine decimal places.  Amounts are parsed exactly, without any floating point arithmetic.
+
+use serde_json::Value;
+
+use casper_types::{UIntParseError, U512};
+
+use crate::error::{Error, Result};
+
+/// Amounts displayed as integer numbers of motes, as returned by the node.
+pub const MOTES: &str = "motes";
+/// Amounts displayed as decimal numbers of CSPR.
+pub const CSPR: &str = "cspr";
+/// The supported units in which to display amounts.
+pub const UNITS: [&str; 2] = [MOTES, CSPR];
+
+/// The number of decimal places of a CSPR amount, i.e. log10 of the number of motes per CSPR.
+const CSPR_DECIMAL_PLACES: usize = 9;
+
+/// The names of fields holding amounts of motes in responses from the node and in `Deploy`s.
+const AMOUNT_FIELDS: [&str; 5] = ["amount", "balance_value", "staked_amount", "weight", "cost"];
+
+/// Parses `value` as an amount of motes, with an optional unit suffix.
+pub(crate) fn parse_motes(context: &'static str, value: &str) -> Result<U512> {
+    let lowercase = value.trim().to_lowercase();
+    let from_dec_str = |digits: &str| {
+        if digits.is_empty() {
+            return Err(Error::InvalidArgument {
+                context,
+                error: format!("'{}' has no number", value.trim()),
+            });
+        }
+        U512::from_dec_str(digits).map_err(|error| Error::FailedToParseUint {
+            context,
+            error: UIntParseError::FromDecStr(error),
+        })
+    };
+
+    let cspr = match lowercase.strip_suffix(CSPR) {
+        Some(cspr) => cspr.trim_end(),
+        None => {
+            let motes = lowercase
+                .strip_suffix(MOTES)
+                .map_or(lowercase.as_str(), str::trim_end);
+            return from_dec_str(motes);
+        }
+    };
+    let (whole, fraction) = cspr.split_once('.').unwrap_or((cspr, ""));
+    if fraction.len() > CSPR_DECIMAL_PLACES {
+        return Err(Error::InvalidArgument {
+            context,
+            error: format!(
+                "'{}' has more than {} decimal places, so is a fraction of a mote",
+                value.trim(),
+                CSPR_DECIMAL_PLACES
+            ),
+        });
+    }
+    if whole.is_empty() && fraction.is_empty() {
+        return from_dec_str(whole);
+    }
+    let whole = if whole.is_empty() { "0" } else { whole };
+    from_dec_str(&format!(
+        "{}{:0<width$}",
+        whole,
+        fraction,
+        width = CSPR_DECIMAL_PLACES
+    ))
+}
+
+/// Returns `motes` as a decimal number of CSPR, without trailing zeros, e.g. "2.5".
+pub fn format_cspr(motes: U512) -> String {
+    // `U512`'s `Display` implementation ignores the width, so pad its output instead.
+    let digits = format!(
+        "{:0>width$}",
+        motes.to_string(),
+        width = CSPR_DECIMAL_PLACES + 1
+    );
+    let (whole, fraction) = digits.split_at(digits.len() - CSPR_DECIMAL_PLACES);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{}.{}", whole, fraction)
+    }
+}
+
+/// Returns `value` with its amounts of motes displayed in the given units, one of [`UNITS`].
+///
+/// With [`CSPR`], the string value of each field named as an amount, e.g. `balance_value` or
+/// `staked_amount`, is replaced by the amount in CSPR followed by " CSPR", as is the parsed value
+/// of each `U512` arg named `amount` in a `Deploy`.  Numeric fields, such as the weights of an
+/// account's associated keys, are never amounts of motes, so are left as they are.
+pub fn display_in_units(value: Value, units: &str) -> Result<Value> {
+    match units {
+        MOTES => Ok(value),
+        CSPR => Ok(to_cspr(value, false)),
+        _ => Err(Error::InvalidArgument {
+            context: "display_units",
+            error: format!("'{}' should be one of {}", units, UNITS.join(", ")),
+        }),
+    }
+}
+
+/// Converts the amounts in `value`, all of `value` being an amount if `is_amount` is true.
+fn to_cspr(value: Value, is_amount: bool) -> Value {
+    match value {
+        Value::String(string) if is_amount => match U512::from_dec_str(&string) {
+            Ok(motes) => Value::String(format!("{} CSPR", format_cspr(motes))),
+            Err(_) => Value::String(string),
+        },
+        Value::Object(object) => Value::Object(
+            object
+                .into_iter()
+                .map(|(key, value)| {
+                    let is_amount = AMOUNT_FIELDS.contains(&key.as_str());
+                    (key, to_cspr(value, is_amount))
+                })
+                .collect(),
+        ),
+        Value::Array(array) => {
+            // A `Deploy`'s args are each a `[name, CLValue]` pair.
+            if let [Value::String(name), Value::Object(cl_value)] = array.as_slice() {
+                if name == "amount" && cl_value.get("cl_type") == Some(&Value::from("U512")) {
+                    let mut cl_value = cl_value.clone();
+                    if let Some(parsed) = cl_value.remove("parsed") {
+                        cl_value.insert("parsed".to_string(), to_cspr(parsed, true));
+                    }
+                    return Value::Array(vec![
+                        Value::String(name.clone()),
+                        Value::Object(cl_value),
+                    ]);
+                }
+            }
+            Value::Array(
+                array
+                    .into_iter()
+                    .map(|element| to_cspr(element, is_amount))
+                    .collect(),
+            )
+        }
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn should_parse_amounts_with_units() {
+        let motes = |value| parse_motes("amount", value).unwrap();
+        assert_eq!(motes("2500000000"), U512::from(2_500_000_000_u64));
+        assert_eq!(motes("2500000000motes"), U512::from(2_500_000_000_u64));
+        assert_eq!(motes(" 2500000000 Motes "), U512::from(2_500_000_000_u64));
+        assert_eq!(motes("2.5cspr"), U512::from(2_500_000_000_u64));
+        assert_eq!(motes("2.5 CSPR"), U512::from(2_500_000_000_u64));
+        assert_eq!(motes("3cspr"), U512::from(3_000_000_000_u64));
+        assert_eq!(motes(".000000001cspr"), U512::one());
+        assert_eq!(motes("0.123456789cspr"), U512::from(123_456_789));
+        assert_eq!(
+            motes("123456789012345678901234567890.5cspr"),
+            U512::from_dec_str("123456789012345678901234567890500000000").unwrap()
+        );
+    }
+
+    #[test]
+    fn should_fail_to_parse_invalid_amounts() {
+        for value in ["1.5", "1.5motes", "-1cspr", "1.-5cspr", "1e9", "1.2.3cspr"] {
+            assert!(
+                matches!(
+                    parse_motes("amount", value),
+                    Err(Error::FailedToParseUint {
+                        context: "amount",
+                        ..
+                    })
+                ),
+                "{}",
+                value
+            );
+        }
+        for value in ["", "cspr", " motes", "0.0000000001cspr"] {
+            assert!(
+                matches!(
+                    parse_motes("amount", value),
+                    Err(Error::InvalidArgument {
+                        context: "amount",
+                        ..
+                    })
+                ),
+                "{}",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn should_format_cspr() {
+        assert_eq!(format_cspr(U512::zero()), "0");
+        assert_eq!(format_cspr(U512::one()), "0.000000001");
+        assert_eq!(format_cspr(U512::from(2_500_000_000_u64)), "2.5");
+        assert_eq!(format_cspr(U512::from(3_000_000_000_u64)), "3");
+        assert_eq!(
+            format_cspr(U512::from_dec_str("123456789012345678901234567890500000000").unwrap()),
+            "123456789012345678901234567890.5"
+        );
+    }
+
+    #[test]
+    fn should_display_amounts_in_cspr() {
+        let value = json!({
+            "balance_value": "2500000000",
+            "account": {"associated_keys": [{"account_hash": "account-hash-00", "weight": 1}]},
+            "bids": [{"bid": {"staked_amount": "1000000000", "delegators": []}}],
+            "validator_weights": [{"public_key": "01", "weight": "5"}],
+            "session": {"Transfer": {"args": [
+                ["amount", {"cl_type": "U512", "bytes": "0400f90295", "parsed": "2500000000"}],
+                ["id", {"cl_type": {"Option": "U64"}, "bytes": "00", "parsed": null}]
+            ]}}
+        });
+        let expected = json!({
+            "balance_value": "2.5 CSPR",
+            "account": {"associated_keys": [{"account_hash": "account-hash-00", "weight": 1}]},
+            "bids": [{"bid": {"staked_amount": "1 CSPR", "delegators": []}}],
+            "validator_weights": [{"public_key": "01", "weight": "0.000000005 CSPR"}],
+            "session": {"Transfer": {"args": [
+                ["amount", {"cl_type": "U512", "bytes": "0400f90295", "parsed": "2.5 CSPR"}],
+                ["id", {"cl_type": {"Option": "U64"}, "bytes": "00", "parsed": null}]
+            ]}}
+        });
+        assert_eq!(display_in_units(value.clone(), CSPR).unwrap(), expected);
+        assert_eq!(display_in_units(value.clone(), MOTES).unwrap(), value);
+        assert!(display_in_units(value, "wei").is_err());
+    }
+}
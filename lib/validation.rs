@@ -1,6 +1,7 @@
 use std::convert::TryFrom;
 
 use jsonrpc_lite::JsonRpc;
+use serde_json::{Map, Value};
 use thiserror::Error;
 
 use casper_execution_engine::{
@@ -131,17 +132,7 @@ pub(crate) fn validate_query_response(
         .as_object()
         .ok_or(ValidateResponseError::ValidateResponseFailedToParse)?;
 
-    let proofs: Vec<TrieMerkleProof<Key, StoredValue>> = {
-        let proof = object
-            .get(GET_ITEM_RESULT_MERKLE_PROOF)
-            .ok_or(ValidateResponseError::ValidateResponseFailedToParse)?;
-        let proof_str = proof
-            .as_str()
-            .ok_or(ValidateResponseError::ValidateResponseFailedToParse)?;
-        let proof_bytes = base16::decode(proof_str)
-            .map_err(|_| ValidateResponseError::ValidateResponseFailedToParse)?;
-        bytesrepr::deserialize(proof_bytes)?
-    };
+    let proofs = merkle_proofs(object)?;
 
     let proof_value: &StoredValue = {
         let last_proof = proofs
@@ -184,17 +175,7 @@ pub(crate) fn validate_query_global_state(
         .as_object()
         .ok_or(ValidateResponseError::ValidateResponseFailedToParse)?;
 
-    let proofs: Vec<TrieMerkleProof<Key, StoredValue>> = {
-        let proof = object
-            .get(GET_ITEM_RESULT_MERKLE_PROOF)
-            .ok_or(ValidateResponseError::ValidateResponseFailedToParse)?;
-        let proof_str = proof
-            .as_str()
-            .ok_or(ValidateResponseError::ValidateResponseFailedToParse)?;
-        let proof_bytes = base16::decode(proof_str)
-            .map_err(|_| ValidateResponseError::ValidateResponseFailedToParse)?;
-        bytesrepr::deserialize(proof_bytes)?
-    };
+    let proofs = merkle_proofs(object)?;
 
     let proof_value: &StoredValue = {
         let last_proof = proofs
@@ -225,6 +206,38 @@ pub(crate) fn validate_query_global_state(
         .map_err(Into::into)
 }
 
+/// Returns the stored value proven by the Merkle proofs of a `state_get_item` or
+/// `query_global_state` response.
+///
+/// The response should already have been validated.
+pub(crate) fn proven_stored_value(
+    response: &JsonRpc,
+) -> Result<StoredValue, ValidateResponseError> {
+    let object = response
+        .get_result()
+        .and_then(Value::as_object)
+        .ok_or(ValidateResponseError::ValidateResponseFailedToParse)?;
+    let proofs = merkle_proofs(object)?;
+    let last_proof = proofs
+        .last()
+        .ok_or(ValidateResponseError::ValidateResponseFailedToParse)?;
+    Ok(last_proof.value().clone())
+}
+
+fn merkle_proofs(
+    object: &Map<String, Value>,
+) -> Result<Vec<TrieMerkleProof<Key, StoredValue>>, ValidateResponseError> {
+    let proof = object
+        .get(GET_ITEM_RESULT_MERKLE_PROOF)
+        .ok_or(ValidateResponseError::ValidateResponseFailedToParse)?;
+    let proof_str = proof
+        .as_str()
+        .ok_or(ValidateResponseError::ValidateResponseFailedToParse)?;
+    let proof_bytes = base16::decode(proof_str)
+        .map_err(|_| ValidateResponseError::ValidateResponseFailedToParse)?;
+    Ok(bytesrepr::deserialize(proof_bytes)?)
+}
+
 pub(crate) fn validate_get_balance_response(
     response: &JsonRpc,
     state_root_hash: &Digest,
@@ -282,17 +295,14 @@ pub(crate) fn validate_get_block_response(
     let block = Block::from(json_block);
     block.verify()?;
     match maybe_block_identifier {
-        Some(BlockIdentifier::Hash(block_hash)) => {
-            if block_hash != block.hash() {
-                return Err(ValidateResponseError::UnexpectedBlockHash);
-            }
+        Some(BlockIdentifier::Hash(block_hash)) if block_hash != block.hash() => {
+            return Err(ValidateResponseError::UnexpectedBlockHash);
         }
-        Some(BlockIdentifier::Height(height)) => {
-            // More is necessary here to mitigate a MITM attack
-            if height != &block.height() {
-                return Err(ValidateResponseError::UnexpectedBlockHeight);
-            }
+        // More is necessary here to mitigate a MITM attack
+        Some(BlockIdentifier::Height(height)) if height != &block.height() => {
+            return Err(ValidateResponseError::UnexpectedBlockHeight);
         }
+        Some(_) => (),
         // More is necessary here to mitigate a MITM attack. In this case we would want to validate
         // `block.proofs()` to make sure that 1/3 of the validator weight signed the block, and we
         // would have to know the latest validators through some trustworthy means
@@ -0,0 +1,185 @@
+//! Searching for keys whose public key or account hash has a given hex prefix, using a thread per
+//! CPU core.
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        mpsc::{self, RecvTimeoutError},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use casper_types::{PublicKey, SecretKey};
+
+use crate::{
+    error::{Error, Result},
+    keygen::{self, VanityProgress, VanityTarget, SECP256K1},
+};
+
+/// The interval at which progress is reported while searching.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Returns the bytes whose hex encoding must start with the prefix.
+fn target_bytes(public_key: &PublicKey, target: VanityTarget) -> Vec<u8> {
+    match target {
+        VanityTarget::PublicKey => Vec::from(public_key),
+        VanityTarget::AccountHash => public_key.to_account_hash().value().to_vec(),
+    }
+}
+
+fn matches_prefix(secret_key: &SecretKey, prefix: &str, target: VanityTarget) -> bool {
+    let bytes = target_bytes(&PublicKey::from(secret_key), target);
+    let prefix_byte_count = prefix.len().div_ceil(2);
+    base16::encode_lower(&bytes[..prefix_byte_count]).starts_with(prefix)
+}
+
+/// Validates `prefix`, returning it lowercased along with the expected number of keys to try
+/// before finding a match.
+fn validate(algorithm: &str, prefix: &str, target: VanityTarget) -> Result<(String, u64)> {
+    let invalid_prefix = |error: String| Error::InvalidArgument {
+        context: "vanity_prefix",
+        error,
+    };
+    if prefix.is_empty() || !prefix.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return Err(invalid_prefix(format!(
+            "'{}' must be a non-empty hex string",
+            prefix
+        )));
+    }
+    let prefix = prefix.to_ascii_lowercase();
+
+    // Compressed secp256k1 public keys always start with 02 or 03 after the algorithm tag, so
+    // those two hex digits are not random.
+    let mut random_digits = prefix.len() as u32;
+    let mut multiplier = 1;
+    if target == VanityTarget::PublicKey && algorithm.eq_ignore_ascii_case(SECP256K1) {
+        let valid_first_bytes = ["02", "03"];
+        if !valid_first_bytes
+            .iter()
+            .any(|valid| valid.starts_with(&prefix[..prefix.len().min(2)]))
+        {
+            return Err(invalid_prefix(format!(
+                "secp256k1 public keys always start with {:?}, so '{}' can never match",
+                valid_first_bytes, prefix
+            )));
+        }
+        random_digits = random_digits.saturating_sub(2);
+        multiplier = if prefix.len() >= 2 { 2 } else { 1 };
+    }
+    let expected_attempts = 16_u64
+        .checked_pow(random_digits)
+        .and_then(|attempts| attempts.checked_mul(multiplier))
+        .ok_or_else(|| invalid_prefix(format!("'{}' is too long to ever be found", prefix)))?;
+    Ok((prefix, expected_attempts))
+}
+
+/// Generates random keys of the given algorithm on every CPU core until one matches `prefix`,
+/// calling `on_progress` periodically while searching.
+pub(crate) fn search(
+    algorithm: &str,
+    prefix: &str,
+    target: VanityTarget,
+    mut on_progress: impl FnMut(&VanityProgress),
+) -> Result<SecretKey> {
+    let (prefix, expected_attempts) = validate(algorithm, prefix, target)?;
+    // Fail fast on an unsupported algorithm rather than in every thread.
+    keygen::random_secret_key(algorithm)?;
+
+    let thread_count = thread::available_parallelism().map_or(1, |count| count.get());
+    let attempts = AtomicU64::new(0);
+    let found = AtomicBool::new(false);
+    let (sender, receiver) = mpsc::channel();
+    let start = Instant::now();
+
+    thread::scope(|scope| {
+        for _ in 0..thread_count {
+            let sender = sender.clone();
+            let (prefix, attempts, found) = (&prefix, &attempts, &found);
+            scope.spawn(move || {
+                while !found.load(Ordering::Relaxed) {
+                    let secret_key = match keygen::random_secret_key(algorithm) {
+                        Ok(secret_key) => secret_key,
+                        Err(_) => return,
+                    };
+                    attempts.fetch_add(1, Ordering::Relaxed);
+                    if matches_prefix(&secret_key, prefix, target) {
+                        found.store(true, Ordering::Relaxed);
+                        let _ = sender.send(secret_key);
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        let result = loop {
+            match receiver.recv_timeout(PROGRESS_INTERVAL) {
+                Ok(secret_key) => break Ok(secret_key),
+                Err(RecvTimeoutError::Timeout) => on_progress(&VanityProgress {
+                    attempts: attempts.load(Ordering::Relaxed),
+                    expected_attempts,
+                    elapsed: start.elapsed(),
+                }),
+                Err(RecvTimeoutError::Disconnected) => {
+                    break Err(Error::InvalidArgument {
+                        context: "vanity_prefix",
+                        error: "all search threads stopped without finding a match".to_string(),
+                    })
+                }
+            }
+        };
+        found.store(true, Ordering::Relaxed);
+        result
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::AsymmetricType;
+
+    use super::*;
+    use crate::keygen::ED25519;
+
+    #[test]
+    fn should_find_key_with_prefix() {
+        let secret_key = search(ED25519, "Ab", VanityTarget::PublicKey, |_| {}).unwrap();
+        assert!(PublicKey::from(&secret_key).to_hex().starts_with("01ab"));
+
+        let secret_key = search(SECP256K1, "0", VanityTarget::AccountHash, |_| {}).unwrap();
+        let account_hash = PublicKey::from(&secret_key).to_account_hash();
+        assert!(base16::encode_lower(&account_hash.value()).starts_with('0'));
+    }
+
+    #[test]
+    fn should_estimate_expected_attempts() {
+        assert_eq!(
+            validate(ED25519, "abc", VanityTarget::PublicKey).unwrap(),
+            ("abc".to_string(), 4096)
+        );
+        assert_eq!(
+            validate(SECP256K1, "03ab", VanityTarget::PublicKey)
+                .unwrap()
+                .1,
+            512
+        );
+        assert_eq!(
+            validate(SECP256K1, "ab", VanityTarget::AccountHash)
+                .unwrap()
+                .1,
+            256
+        );
+    }
+
+    #[test]
+    fn should_reject_invalid_prefix() {
+        for (algorithm, prefix) in [(ED25519, ""), (ED25519, "xyz"), (SECP256K1, "ab")] {
+            assert!(matches!(
+                validate(algorithm, prefix, VanityTarget::PublicKey),
+                Err(Error::InvalidArgument {
+                    context: "vanity_prefix",
+                    ..
+                })
+            ));
+        }
+    }
+}
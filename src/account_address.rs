@@ -30,6 +30,8 @@ impl<'a, 'b> ClientCommand<'a, 'b> for GenerateAccountHash {
     }
 
     async fn run(matches: &ArgMatches<'a>) -> Result<Success, Error> {
+        common::output_format::ensure_not_given(Self::NAME, matches)?;
+
         let hex_public_key = common::public_key::get(matches)?;
         let public_key = PublicKey::from_hex(&hex_public_key).map_err(|error| {
             eprintln!("Can't parse {} as a public key: {}", hex_public_key, error);
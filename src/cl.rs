@@ -0,0 +1,73 @@
+mod decode;
+mod encode;
+
+use async_trait::async_trait;
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+
+use casper_client::Error;
+
+use crate::{command::ClientCommand, Success};
+use decode::DecodeCLValue;
+use encode::EncodeCLValue;
+
+/// This struct defines the order in which the subcommands are shown in the help message.
+enum SubcommandOrder {
+    Encode,
+    Decode,
+}
+
+/// This struct defines the order in which the args are shown for the subcommands' help messages.
+enum DisplayOrder {
+    Type,
+    Value,
+    Hex,
+    IncludeType,
+}
+
+/// Handles providing the arg for and retrieval of the `CLType`.
+mod cl_type {
+    use super::*;
+
+    const ARG_NAME: &str = "type";
+    const ARG_SHORT: &str = "t";
+    const ARG_VALUE_NAME: &str = "TYPE";
+
+    pub(super) fn arg(help: &'static str, required: bool) -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required(required)
+            .value_name(ARG_VALUE_NAME)
+            .help(help)
+            .display_order(DisplayOrder::Type as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+}
+
+pub struct CL;
+
+#[async_trait]
+impl<'a, 'b> ClientCommand<'a, 'b> for CL {
+    const NAME: &'static str = "cl";
+    const ABOUT: &'static str = "Encodes and decodes the bytes of CLValues";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommand(EncodeCLValue::build(SubcommandOrder::Encode as usize))
+            .subcommand(DecodeCLValue::build(SubcommandOrder::Decode as usize))
+    }
+
+    async fn run(matches: &ArgMatches<'a>) -> Result<Success, Error> {
+        match matches.subcommand() {
+            (EncodeCLValue::NAME, Some(matches)) => EncodeCLValue::run(matches).await,
+            (DecodeCLValue::NAME, Some(matches)) => DecodeCLValue::run(matches).await,
+            (subcommand, _) => unreachable!("unknown cl subcommand '{}'", subcommand),
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use async_trait::async_trait;
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use casper_client::Error;
+
+use super::{cl_type, DisplayOrder};
+use crate::{command::ClientCommand, common, Success};
+
+const TYPE_HELP: &str =
+    "The type of the encoded value, written as for --session-arg, e.g. 'map<string,u512>'. If \
+    omitted, the bytes must be those of a whole CLValue, which carries its own type";
+
+/// Handles providing the arg for and retrieval of the bytes to decode.
+mod hex {
+    use super::*;
+
+    const ARG_NAME: &str = "hex";
+    const ARG_VALUE_NAME: &str = common::ARG_HEX_STRING;
+    const ARG_HELP: &str = "The hex-encoded bytes to decode";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(true)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Hex as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+    }
+}
+
+pub struct DecodeCLValue;
+
+#[async_trait]
+impl<'a, 'b> ClientCommand<'a, 'b> for DecodeCLValue {
+    const NAME: &'static str = "decode";
+    const ABOUT: &'static str =
+        "Prints hex-encoded bytes decoded as a CLValue, in the JSON form used in a deploy";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(cl_type::arg(TYPE_HELP, false))
+            .arg(hex::arg())
+    }
+
+    async fn run(matches: &ArgMatches<'a>) -> Result<Success, Error> {
+        let cl_type = cl_type::get(matches);
+        let hex = hex::get(matches);
+
+        casper_client::decode_cl_value(cl_type, hex).map(Success::Value)
+    }
+}
@@ -0,0 +1,88 @@
+use async_trait::async_trait;
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use casper_client::Error;
+
+use super::{cl_type, DisplayOrder};
+use crate::{command::ClientCommand, common, Success};
+
+const TYPE_HELP: &str =
+    "The type of the value, written as for --session-arg, e.g. 'map<string,u512>'. To see the \
+    supported types, run 'make-deploy --show-arg-examples'";
+
+/// Handles providing the arg for and retrieval of the value to encode.
+mod value {
+    use super::*;
+
+    const ARG_NAME: &str = "value";
+    const ARG_VALUE_NAME: &str = common::ARG_STRING;
+    const ARG_HELP: &str =
+        "The value, written as for --session-arg but without the surrounding single quotes, e.g. \
+        '{a: 1, b: 2}'";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(true)
+            .allow_hyphen_values(true)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Value as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+    }
+}
+
+/// Handles providing the arg for and retrieval of the flag to encode the whole `CLValue`.
+mod include_type {
+    use super::*;
+
+    const ARG_NAME: &str = "include-type";
+    const ARG_HELP: &str =
+        "If this flag is passed, the whole CLValue is encoded, i.e. the length-prefixed value \
+        followed by its type, as is needed for the 'raw_bytes' of an arg in a complex args file. \
+        Otherwise only the value itself is encoded";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::IncludeType as usize)
+    }
+
+    pub(super) fn get(matches: &ArgMatches) -> bool {
+        matches.is_present(ARG_NAME)
+    }
+}
+
+pub struct EncodeCLValue;
+
+#[async_trait]
+impl<'a, 'b> ClientCommand<'a, 'b> for EncodeCLValue {
+    const NAME: &'static str = "encode";
+    const ABOUT: &'static str = "Prints the hex-encoded bytes of a value of a given CLType";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(cl_type::arg(TYPE_HELP, true))
+            .arg(value::arg())
+            .arg(include_type::arg())
+    }
+
+    async fn run(matches: &ArgMatches<'a>) -> Result<Success, Error> {
+        common::output_format::ensure_not_given(Self::NAME, matches)?;
+
+        let cl_type = cl_type::get(matches);
+        let value = value::get(matches);
+        let include_type = include_type::get(matches);
+
+        casper_client::encode_cl_value(cl_type, value, include_type).map(Success::Output)
+    }
+}
@@ -10,6 +10,9 @@ pub enum Success {
     Response(JsonRpc),
     /// The output which should be presented to the user for non-RPC client commands.
     Output(String),
+    /// Structured output of non-RPC client commands, printed in the format given by
+    /// `--output-format`.
+    Value(serde_json::Value),
 }
 
 impl From<JsonRpc> for Success {
@@ -2,7 +2,7 @@ use std::fs;
 
 use clap::{Arg, ArgMatches};
 
-use casper_client::Error;
+use casper_client::{units, Error};
 use casper_types::PublicKey;
 
 pub const ARG_PATH: &str = "PATH";
@@ -45,11 +45,15 @@ pub mod node_address {
     const ARG_HELP: &str = "Hostname or IP and port of node on which HTTP service is running";
 
     pub fn arg(order: usize) -> Arg<'static, 'static> {
+        let default = crate::config::profile()
+            .node_address
+            .as_deref()
+            .unwrap_or(ARG_DEFAULT);
         Arg::with_name(ARG_NAME)
             .long(ARG_NAME)
             .short(ARG_SHORT)
             .required(true)
-            .default_value(ARG_DEFAULT)
+            .default_value(default)
             .value_name(ARG_VALUE_NAME)
             .help(ARG_HELP)
             .display_order(order)
@@ -90,25 +94,111 @@ pub mod rpc_id {
 pub mod secret_key {
     use super::*;
 
-    const ARG_NAME: &str = "secret-key";
+    pub const ARG_NAME: &str = "secret-key";
     const ARG_SHORT: &str = "k";
     const ARG_VALUE_NAME: &str = super::ARG_PATH;
-    const ARG_HELP: &str = "Path to secret key file";
+    const ARG_HELP: &str =
+        "Path to secret key file. This is either a PEM file or a password-encrypted keystore \
+        created by 'keygen --encrypt' or 'key encrypt'. The password of a keystore is taken from \
+        the CASPER_SECRET_KEY_PASSWORD environment variable, or from the file named by the \
+        CASPER_SECRET_KEY_PASSWORD_FILE environment variable, or else prompted for. If omitted, \
+        the secret key set by the selected profile is used";
+
+    /// Returns the arg, required unless --signer is provided or the selected profile sets a
+    /// secret key.
+    pub fn arg(order: usize) -> Arg<'static, 'static> {
+        arg_required_unless(order, &[])
+    }
+
+    /// As [`arg`], but also not required if any of `others` is provided.
+    pub fn arg_required_unless(order: usize, others: &[&'static str]) -> Arg<'static, 'static> {
+        let arg = Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(order);
+        // A default value would conflict with --signer, so the profile's secret key is applied in
+        // `get()` instead.
+        if crate::config::profile().secret_key.is_some() {
+            return arg.required(false);
+        }
+        others.iter().fold(
+            arg.required_unless(super::signer::ARG_NAME),
+            |arg, other| arg.required_unless(other),
+        )
+    }
+
+    /// Returns the secret key path if provided, or else the one set by the selected profile.
+    pub fn get<'a>(matches: &'a ArgMatches) -> Option<&'a str> {
+        matches
+            .value_of(ARG_NAME)
+            .or_else(|| crate::config::profile().secret_key.as_deref())
+    }
+
+    /// Returns the secret key path as for [`get`], or an empty string if --signer is provided, so
+    /// that a secret key set by the selected profile doesn't conflict with the signer.
+    pub fn get_unless_signer<'a>(matches: &'a ArgMatches) -> &'a str {
+        if matches.is_present(super::signer::ARG_NAME) {
+            return "";
+        }
+        get(matches).unwrap_or_default()
+    }
+}
+
+/// Handles providing the arg for and retrieval of the signer, used in place of a secret key file.
+pub mod signer {
+    use super::*;
+
+    pub const ARG_NAME: &str = "signer";
+    const ARG_VALUE_NAME: &str = "exec:PATH OR pem:PATH";
+    const ARG_HELP: &str =
+        "The signer to use instead of --secret-key. 'pem:' followed by a path uses the secret key \
+        file at that path. 'exec:' followed by a path runs the program at that path to sign, so \
+        that the secret key never enters the client: it is sent a JSON request on stdin such as \
+        {\"method\": \"sign\", \"deploy_hash\": \"<HEX>\"} and must reply on stdout with \
+        {\"public_key\": \"<HEX>\", \"signature\": \"<HEX>\"}, or with \
+        {\"public_key\": \"<HEX>\"} to a {\"method\": \"public_key\"} request";
 
     pub fn arg(order: usize) -> Arg<'static, 'static> {
         Arg::with_name(ARG_NAME)
             .long(ARG_NAME)
-            .short(ARG_SHORT)
-            .required(true)
+            .required(false)
+            .conflicts_with(super::secret_key::ARG_NAME)
             .value_name(ARG_VALUE_NAME)
             .help(ARG_HELP)
             .display_order(order)
     }
 
+    /// Returns the signer specification, or an empty string if not provided.
     pub fn get<'a>(matches: &'a ArgMatches) -> &'a str {
-        matches
-            .value_of(ARG_NAME)
-            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+}
+
+/// Handles providing the arg for and retrieval of the password file for encrypted secret keys.
+pub mod password_file {
+    use super::*;
+
+    const ARG_NAME: &str = "password-file";
+    const ARG_VALUE_NAME: &str = super::ARG_PATH;
+    const ARG_HELP: &str =
+        "Path to a file containing the password of the encrypted secret key. If not provided, the \
+        password is taken from the CASPER_SECRET_KEY_PASSWORD environment variable, or from the \
+        file named by the CASPER_SECRET_KEY_PASSWORD_FILE environment variable, or else prompted \
+        for";
+
+    pub fn arg(order: usize) -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(order)
+    }
+
+    pub fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
     }
 }
 
@@ -143,6 +233,142 @@ pub mod force {
     }
 }
 
+/// Handles providing the global arg for and retrieval of the format in which to print output.
+pub mod output_format {
+    use casper_client::output;
+
+    use super::*;
+
+    const ARG_NAME: &str = "output-format";
+    const ARG_VALUE_NAME: &str = "FORMAT";
+    const ARG_HELP: &str =
+        "The format in which to print the response or output: pretty-printed \"json\", \
+        \"json-compact\" on a single line, \"yaml\", a \"table\" with a row per array element \
+        or object field, or \"ndjson\" with a line per array element. With \"json\", a string \
+        selected by --query is printed without quotes. Subcommands which print plain text, such \
+        as 'keygen', reject this arg";
+
+    pub fn arg(order: usize) -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .global(true)
+            .required(false)
+            .possible_values(&output::FORMATS)
+            .default_value(output::JSON)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(order)
+    }
+
+    pub fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or(output::JSON)
+    }
+
+    /// Returns an error if --output-format or --query was given, for subcommands whose output is
+    /// plain text and so can't be formatted or queried.
+    ///
+    /// This must be called before the subcommand has any effect, so that it fails cleanly.
+    pub fn ensure_not_given(subcommand: &str, matches: &ArgMatches) -> Result<(), Error> {
+        if matches.occurrences_of(ARG_NAME) == 0 && super::query::get(matches).is_empty() {
+            return Ok(());
+        }
+        Err(Error::InvalidArgument {
+            context: "output_format",
+            error: format!(
+                "'{}' prints plain text, so doesn't support --{} or --query",
+                subcommand, ARG_NAME
+            ),
+        })
+    }
+}
+
+/// Handles providing the global arg for and retrieval of the query selecting fields to print.
+pub mod query {
+    use super::*;
+
+    const ARG_NAME: &str = "query";
+    const ARG_VALUE_NAME: &str = "EXPRESSION";
+    const ARG_HELP: &str =
+        "Prints only the fields selected by the expression, e.g. 'result.balance_value'. Fields \
+        are separated by '.', array elements are selected by index as in 'items[0]', and '[*]' \
+        selects every element, as in 'result.deploys[*].deploy_hash'. Field names can be quoted \
+        as in '[\"field name\"]'. Long hex strings are not abbreviated in the selected fields. \
+        Subcommands which print plain text, such as 'keygen', reject this arg";
+
+    pub fn arg(order: usize) -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .global(true)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(order)
+    }
+
+    pub fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+}
+
+/// Handles providing the global arg for and retrieval of the format in which to print errors.
+pub mod error_format {
+    use super::*;
+
+    pub(crate) const ARG_NAME: &str = "error-format";
+    const ARG_VALUE_NAME: &str = "FORMAT";
+    pub(crate) const TEXT: &str = "text";
+    pub(crate) const JSON: &str = "json";
+    const ARG_HELP: &str =
+        "The format in which to print an error: a \"text\" message, or \"json\" with fields \
+        \"kind\", \"context\", \"message\" and \"rpc_error_code\". Whichever format is used, \
+        the exit code identifies the category of error: 1 internal, 2 invalid argument, 3 file \
+        IO, 4 network, 5 RPC error returned by the node, 6 invalid response, 7 cryptographic";
+
+    pub fn arg(order: usize) -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .global(true)
+            .required(false)
+            .possible_values(&[TEXT, JSON])
+            .default_value(TEXT)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(order)
+    }
+
+    pub fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or(TEXT)
+    }
+}
+
+/// Handles providing the global arg for and retrieval of the units in which to print amounts.
+pub mod display_units {
+    use super::*;
+
+    const ARG_NAME: &str = "display-units";
+    const ARG_VALUE_NAME: &str = "UNITS";
+    const ARG_HELP: &str =
+        "The units in which to print amounts of motes held in the output, such as balances, bids, \
+        era validator weights, transfer amounts and deploy costs: \"motes\" as returned by the \
+        node, or \"cspr\", where for example \"2500000000\" is printed as \"2.5 CSPR\"";
+
+    pub fn arg(order: usize) -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .global(true)
+            .required(false)
+            .possible_values(&units::UNITS)
+            .default_value(units::MOTES)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(order)
+    }
+
+    pub fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or(units::MOTES)
+    }
+}
+
 /// Handles providing the arg for and retrieval of the state root hash.
 pub mod state_root_hash {
     use super::*;
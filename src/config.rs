@@ -0,0 +1,130 @@
+mod show;
+
+use std::env;
+
+use async_trait::async_trait;
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use once_cell::sync::OnceCell;
+
+use casper_client::{
+    config::{self, Profile},
+    Error,
+};
+
+use crate::{command::ClientCommand, Success};
+use show::ShowConfig;
+
+/// The config resolved before the command line is parsed, since it provides the args' defaults.
+static CONFIG: OnceCell<config::Config> = OnceCell::new();
+
+/// This struct defines the order in which the subcommands are shown in the help message.
+enum SubcommandOrder {
+    Show,
+}
+
+/// Handles providing the global arg for the config file path.
+pub mod config_file {
+    use super::*;
+
+    pub(super) const ARG_NAME: &str = "config";
+    const ARG_VALUE_NAME: &str = crate::common::ARG_PATH;
+    const ARG_HELP: &str =
+        "Path to the config file holding profiles of default arg values. If omitted, the path in \
+        the CASPER_CLIENT_CONFIG environment variable is used, or else \
+        ~/.config/casper-client/config.toml";
+
+    pub fn arg(order: usize) -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .global(true)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(order)
+    }
+}
+
+/// Handles providing the global arg for the profile name.
+pub mod profile_name {
+    use super::*;
+
+    pub(super) const ARG_NAME: &str = "profile";
+    const ARG_VALUE_NAME: &str = "NAME";
+    const ARG_HELP: &str =
+        "Name of the profile in the config file which provides default values for --node-address, \
+        --chain-name, --secret-key, --payment-amount and --ttl. Args passed explicitly always \
+        override the profile's values. If omitted, the profile in the CASPER_CLIENT_PROFILE \
+        environment variable is used, or else the config file's default_profile";
+
+    pub fn arg(order: usize) -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .global(true)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(order)
+    }
+}
+
+/// Returns the value of `--name VALUE` or `--name=VALUE` in `args`, or an empty string if absent.
+pub(crate) fn raw_arg_value<'a>(args: &'a [String], name: &str) -> &'a str {
+    let flag = format!("--{}", name);
+    let prefix = format!("--{}=", name);
+    args.iter()
+        .take_while(|arg| *arg != "--")
+        .enumerate()
+        .find_map(|(index, arg)| {
+            if *arg == flag {
+                args.get(index + 1).map(String::as_str)
+            } else {
+                arg.strip_prefix(&prefix)
+            }
+        })
+        .unwrap_or_default()
+}
+
+/// Reads the config file and selected profile named in the process's args, which must be done
+/// before the command line is parsed.
+pub(crate) fn init() -> Result<(), Error> {
+    let args: Vec<String> = env::args().collect();
+    let config = config::load(
+        raw_arg_value(&args, config_file::ARG_NAME),
+        raw_arg_value(&args, profile_name::ARG_NAME),
+    )?;
+    let _ = CONFIG.set(config);
+    Ok(())
+}
+
+/// Returns the resolved config.
+pub(crate) fn get() -> &'static config::Config {
+    CONFIG.get_or_init(config::Config::default)
+}
+
+/// Returns the default arg values set by the selected profile.
+pub(crate) fn profile() -> &'static Profile {
+    &get().settings
+}
+
+pub struct Config;
+
+#[async_trait]
+impl<'a, 'b> ClientCommand<'a, 'b> for Config {
+    const NAME: &'static str = "config";
+    const ABOUT: &'static str = "Inspects the config file and profiles of default arg values";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommand(ShowConfig::build(SubcommandOrder::Show as usize))
+    }
+
+    async fn run(matches: &ArgMatches<'a>) -> Result<Success, Error> {
+        match matches.subcommand() {
+            (ShowConfig::NAME, Some(matches)) => ShowConfig::run(matches).await,
+            (subcommand, _) => unreachable!("unknown config subcommand '{}'", subcommand),
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+use clap::{App, ArgMatches, SubCommand};
+
+use casper_client::Error;
+
+use crate::{command::ClientCommand, Success};
+
+pub struct ShowConfig;
+
+#[async_trait]
+impl<'a, 'b> ClientCommand<'a, 'b> for ShowConfig {
+    const NAME: &'static str = "show";
+    const ABOUT: &'static str =
+        "Prints the config file path, the selected profile and the default arg values it sets. \
+        Values which are null are not set by the profile, so the args' built-in defaults apply";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+    }
+
+    async fn run(_matches: &ArgMatches<'a>) -> Result<Success, Error> {
+        Ok(Success::Value(
+            serde_json::to_value(super::get()).expect("should encode"),
+        ))
+    }
+}
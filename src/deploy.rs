@@ -1,16 +1,20 @@
+mod batch_transfer;
 mod creation_common;
 mod get;
 mod list;
 mod make;
 mod make_transfer;
 mod put;
+mod put_deploys;
 mod send;
 mod sign;
 mod transfer;
 
+pub use batch_transfer::BatchTransfer;
 pub use list::ListDeploys;
 pub use make::MakeDeploy;
 pub use make_transfer::MakeTransfer;
+pub use put_deploys::PutDeploys;
 pub use send::SendDeploy;
 pub use sign::SignDeploy;
 pub use transfer::Transfer;
@@ -0,0 +1,269 @@
+use async_trait::async_trait;
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use casper_client::{BatchTransferSummary, DeployStrParams, Error};
+
+use super::creation_common::{self, DisplayOrder};
+use crate::{command::ClientCommand, common, Success};
+
+const MAX_CONCURRENCY_DEFAULT: &str = "1";
+const MAX_CONCURRENCY_HELP: &str =
+    "The maximum number of transfers being sent and tracked at any one time. With the default of \
+    1, the transfers are sent sequentially in the order given";
+
+/// Handles providing the arg for and retrieval of the transfers CSV file path.
+mod transfers {
+    use super::*;
+
+    const ARG_NAME: &str = "transfers";
+    const ARG_SHORT: &str = "t";
+    const ARG_VALUE_NAME: &str = common::ARG_PATH;
+    const ARG_HELP: &str =
+        "Path to a CSV file with the header row 'target,amount,transfer_id' and a row per \
+        transfer. Each target is the account hash, uref or hex-encoded public key of the account \
+        to receive the funds, each amount is the number of motes to transfer, optionally with a \
+        unit suffix as for --amount, and each transfer_id is a 64-bit integer permanently \
+        associated with the transfer";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required_unless(creation_common::show_arg_examples::ARG_NAME)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::TransfersFile as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+    }
+}
+
+/// Handles providing the arg for and retrieval of the reconciliation report path.
+mod report {
+    use super::*;
+
+    const ARG_NAME: &str = "report";
+    const ARG_SHORT: &str = "r";
+    const ARG_VALUE_NAME: &str = common::ARG_PATH;
+    const ARG_HELP: &str =
+        "Path to which the reconciliation report is written as a CSV file. A row is written as \
+        each transfer completes, holding the input fields and line number, the status (one of \
+        invalid, written, rejected, unconfirmed, pending, succeeded or failed), and where known \
+        the deploy hash, the block hash and cost of its execution, and an error. A transfer is \
+        unconfirmed if it was sent but no valid response was received. Before each transfer is \
+        sent, a row holding its deploy hash and the status sending is written";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required_unless(creation_common::show_arg_examples::ARG_NAME)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Report as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+    }
+}
+
+/// Handles providing the arg for and retrieval of the completion timeout.
+mod completion_timeout {
+    use super::*;
+
+    const ARG_NAME: &str = "completion-timeout";
+    const ARG_VALUE_NAME: &str = "DURATION";
+    const ARG_DEFAULT: &str = "5min";
+    const ARG_HELP: &str =
+        "The maximum time to wait for each sent transfer to be executed, e.g. '90s' or '10min'. \
+        Transfers not executed within this time are reported as pending. Pass an empty string to \
+        report all sent transfers as pending without waiting";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .default_value(ARG_DEFAULT)
+            .empty_values(true)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::CompletionTimeout as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or(ARG_DEFAULT)
+    }
+}
+
+/// Handles providing the arg for and retrieval of the dry-run output directory.
+mod dry_run {
+    use super::*;
+
+    const ARG_NAME: &str = "dry-run";
+    const ARG_VALUE_NAME: &str = "DIR";
+    const ARG_HELP: &str =
+        "If this arg is passed, no transfers are sent. Instead each signed transfer is written to \
+        this directory in the encoding given by --format, as '<DEPLOY HASH>.json', '.bin' or \
+        '.b64', ready to be sent later via 'send-deploy'";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::DryRun as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+}
+
+/// Handles the arg for whether to resume a previous run.
+mod resume {
+    use super::*;
+
+    const ARG_NAME: &str = "resume";
+    const ARG_HELP: &str =
+        "If this flag is passed, transfers recorded as written, pending or succeeded in an \
+        existing report are skipped and new rows are appended to it. Use this to retry failed \
+        transfers or to continue an interrupted run. Unconfirmed transfers, and those which were \
+        being sent when a run was interrupted, are also skipped, as the node may have accepted \
+        them; check their deploy hashes with 'get-deploy'";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .conflicts_with(common::force::ARG_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Resume as usize)
+    }
+
+    pub(super) fn get(matches: &ArgMatches) -> bool {
+        matches.is_present(ARG_NAME)
+    }
+}
+
+pub struct BatchTransfer {}
+
+#[async_trait]
+impl<'a, 'b> ClientCommand<'a, 'b> for BatchTransfer {
+    const NAME: &'static str = "batch-transfer";
+    const ABOUT: &'static str =
+        "Creates and signs a transfer for each row of a CSV file, then sends them to the network \
+        and tracks their execution, or writes them to a directory. A reconciliation report is \
+        written as a CSV file";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        let subcommand = SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(common::verbose::arg(DisplayOrder::Verbose as usize))
+            .arg(transfers::arg())
+            .arg(report::arg())
+            .arg(creation_common::max_concurrency::arg(
+                MAX_CONCURRENCY_DEFAULT,
+                MAX_CONCURRENCY_HELP,
+            ))
+            .arg(completion_timeout::arg())
+            .arg(dry_run::arg())
+            .arg(creation_common::output_format::arg())
+            .arg(resume::arg())
+            .arg(common::force::arg(DisplayOrder::Force as usize, true));
+        let subcommand = creation_common::apply_common_payment_options(subcommand);
+        creation_common::apply_common_creation_options(subcommand, true)
+    }
+
+    async fn run(matches: &ArgMatches<'a>) -> Result<Success, Error> {
+        common::output_format::ensure_not_given(Self::NAME, matches)?;
+
+        creation_common::show_arg_examples_and_exit_if_required(matches);
+
+        let node_address = common::node_address::get(matches);
+        let verbosity_level = common::verbose::get(matches);
+
+        let transfers_path = transfers::get(matches);
+        let report_path = report::get(matches);
+        let max_concurrency = creation_common::max_concurrency::get(matches)?;
+        let completion_timeout = completion_timeout::get(matches);
+        let dry_run_dir = dry_run::get(matches);
+        let dry_run_format = creation_common::output_format::get(matches);
+        let resume = resume::get(matches);
+        let force = common::force::get(matches);
+
+        let secret_key = common::secret_key::get_unless_signer(matches);
+        let signer = common::signer::get(matches);
+        let timestamp = creation_common::timestamp::get(matches);
+        let ttl = creation_common::ttl::get(matches);
+        let gas_price = creation_common::gas_price::get(matches);
+        let dependencies = creation_common::dependencies::get(matches);
+        let chain_name = creation_common::chain_name::get(matches);
+        let chainspec = creation_common::chainspec::get(matches);
+        let session_account = common::session_account::get(matches)?;
+
+        let payment_str_params = creation_common::payment_str_params(matches);
+
+        casper_client::batch_transfer(
+            node_address,
+            verbosity_level,
+            transfers_path,
+            report_path,
+            max_concurrency,
+            completion_timeout,
+            dry_run_dir,
+            dry_run_format,
+            resume,
+            force,
+            DeployStrParams {
+                secret_key,
+                signer,
+                timestamp,
+                ttl,
+                gas_price,
+                dependencies,
+                chain_name,
+                session_account: &session_account,
+                chainspec,
+            },
+            payment_str_params,
+        )
+        .await
+        .map(
+            |BatchTransferSummary {
+                 written,
+                 succeeded,
+                 failed,
+                 pending,
+                 skipped,
+                 unconfirmed,
+             }| {
+                let outcome = if dry_run_dir.is_empty() {
+                    format!(
+                        "{} transfers succeeded, {} failed and {} are pending",
+                        succeeded, failed, pending
+                    )
+                } else {
+                    format!(
+                        "Wrote {} transfers to {} and {} failed",
+                        written, dry_run_dir, failed
+                    )
+                };
+                Success::Output(format!(
+                    "{}, {} skipped as already done and {} unconfirmed, which may have been \
+                    accepted so are never resent. Report written to {}, where the deploy hashes \
+                    of unconfirmed transfers can be checked with 'get-deploy'",
+                    outcome, skipped, unconfirmed, report_path
+                ))
+            },
+        )
+    }
+}
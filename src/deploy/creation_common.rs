@@ -5,7 +5,7 @@ use std::process;
 
 use clap::{App, AppSettings, Arg, ArgGroup, ArgMatches};
 
-use casper_client::{help, PaymentStrParams, SessionStrParams};
+use casper_client::{help, Error, PaymentStrParams, SessionStrParams};
 
 use crate::common;
 
@@ -16,16 +16,29 @@ pub(super) enum DisplayOrder {
     NodeAddress,
     RpcId,
     SecretKey,
+    Signer,
     Input,
     Output,
+    OutputFormat,
+    Template,
+    TemplateVar,
     TransferAmount,
     TransferTargetAccount,
     TransferId,
+    Manifest,
+    Results,
+    MaxConcurrency,
+    Resume,
+    TransfersFile,
+    Report,
+    CompletionTimeout,
+    DryRun,
     Timestamp,
     Ttl,
     GasPrice,
     Dependencies,
     ChainName,
+    Chainspec,
     SessionCode,
     SessionArgSimple,
     SessionArgsComplex,
@@ -34,6 +47,7 @@ pub(super) enum DisplayOrder {
     SessionPackageHash,
     SessionPackageName,
     SessionEntryPoint,
+    CheckEntryPoint,
     SessionVersion,
     SessionTransfer,
     SessionAccount,
@@ -76,6 +90,11 @@ pub(super) mod show_arg_examples {
 
         println!("Examples for passing values via --session-arg or --payment-arg:");
         println!("{}", help::supported_cl_type_examples());
+        println!(
+            "Example of a JSON file for passing values via --session-args-file or \
+            --payment-args-file:"
+        );
+        println!("{}", help::complex_arg_examples());
 
         true
     }
@@ -182,6 +201,9 @@ pub(super) fn payment_str_params<'a>(matches: &'a ArgMatches) -> PaymentStrParam
             payment_args_complex,
         );
     }
+    if let Some(payment_amount) = crate::config::profile().payment_amount.as_deref() {
+        return PaymentStrParams::with_amount(payment_amount);
+    }
     unreachable!("clap arg groups and parsing should prevent this")
 }
 
@@ -225,11 +247,15 @@ pub(super) mod ttl {
         https://docs.rs/humantime/latest/humantime/fn.parse_duration.html";
 
     pub(in crate::deploy) fn arg() -> Arg<'static, 'static> {
+        let default = crate::config::profile()
+            .ttl
+            .as_deref()
+            .unwrap_or(ARG_DEFAULT);
         Arg::with_name(ARG_NAME)
             .long(ARG_NAME)
             .required(false)
             .value_name(ARG_VALUE_NAME)
-            .default_value(ARG_DEFAULT)
+            .default_value(default)
             .help(ARG_HELP)
             .display_order(DisplayOrder::Ttl as usize)
     }
@@ -237,6 +263,15 @@ pub(super) mod ttl {
     pub(in crate::deploy) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
         matches.value_of(ARG_NAME).unwrap_or_default()
     }
+
+    /// Returns the value only if explicitly provided, so the default doesn't override a value
+    /// taken from a deploy template.
+    pub(in crate::deploy) fn get_if_provided<'a>(matches: &'a ArgMatches) -> &'a str {
+        if matches.occurrences_of(ARG_NAME) == 0 {
+            return "";
+        }
+        get(matches)
+    }
 }
 
 /// Handles providing the arg for and retrieval of the gas price.
@@ -262,6 +297,41 @@ pub(super) mod gas_price {
     pub(in crate::deploy) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
         matches.value_of(ARG_NAME).unwrap_or_default()
     }
+
+    /// Returns the value only if explicitly provided, so the default doesn't override a value
+    /// taken from a deploy template.
+    pub(in crate::deploy) fn get_if_provided<'a>(matches: &'a ArgMatches) -> &'a str {
+        if matches.occurrences_of(ARG_NAME) == 0 {
+            return "";
+        }
+        get(matches)
+    }
+}
+
+/// Handles providing the arg for and retrieval of the network limits a deploy must satisfy.
+pub(super) mod chainspec {
+    use super::*;
+
+    const ARG_NAME: &str = "chainspec";
+    const ARG_VALUE_NAME: &str = "PRESET OR PATH";
+    const ARG_HELP: &str =
+        "The network whose deploy limits (max deploy size, max TTL, max dependencies, block gas \
+        limit and payment args length) the deploy must satisfy. Either the path to the network's \
+        chainspec.toml file or the name of a preset (only 'mainnet'). An existing file takes \
+        precedence over a preset of the same name. If not provided, the mainnet limits are used";
+
+    pub(in crate::deploy) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Chainspec as usize)
+    }
+
+    pub(in crate::deploy) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
 }
 
 /// Handles providing the arg for and retrieval of the deploy dependencies.
@@ -306,18 +376,28 @@ pub(super) mod chain_name {
         a different chain";
 
     pub(in crate::deploy) fn arg() -> Arg<'static, 'static> {
-        Arg::with_name(ARG_NAME)
+        let arg = Arg::with_name(ARG_NAME)
             .long(ARG_NAME)
-            .required_unless(show_arg_examples::ARG_NAME)
             .value_name(ARG_VALUE_NAME)
             .help(ARG_HELP)
-            .display_order(DisplayOrder::ChainName as usize)
+            .display_order(DisplayOrder::ChainName as usize);
+        match crate::config::profile().chain_name.as_deref() {
+            Some(chain_name) => arg.required(false).default_value(chain_name),
+            None => arg.required_unless_one(&[show_arg_examples::ARG_NAME, template::ARG_NAME]),
+        }
     }
 
     pub(in crate::deploy) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
-        matches
-            .value_of(ARG_NAME)
-            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+
+    /// Returns the value only if explicitly provided, so a profile's default doesn't override a
+    /// value taken from a deploy template.
+    pub(in crate::deploy) fn get_if_provided<'a>(matches: &'a ArgMatches) -> &'a str {
+        if matches.occurrences_of(ARG_NAME) == 0 {
+            return "";
+        }
+        get(matches)
     }
 }
 
@@ -355,8 +435,13 @@ pub(super) mod arg_simple {
     static ARG_HELP: Lazy<String> = Lazy::new(|| {
         format!(
             "For simple CLTypes, a named and typed arg which is passed to the Wasm code. To see \
-            an example for each type, run '--{}'. This arg can be repeated to pass multiple named, \
-            typed args, but can only be used for the following types: {}",
+            an example for each type, run '--{}'. The value may instead be surrounded by double \
+            quotes, inside which \\\", \\\\, \\n, \\r and \\t are escape sequences, e.g. \
+            'msg:string=\"{{\\\"a\\\": 1}}\"'. A single-quoted value runs verbatim to the \
+            last ' in the value, so needs no escaping. Several args may be given in one value, \
+            separated by commas after a double-quoted or null value, e.g. \
+            'a:u8=\"1\", b:string=\"x\"'. This arg can be repeated to pass multiple named, typed \
+            args, but can only be used for the following types: {}",
             super::show_arg_examples::ARG_NAME,
             help::supported_cl_type_list()
         )
@@ -412,7 +497,7 @@ pub(super) mod arg_simple {
             .required(false)
             .multiple(true)
             .value_name(ARG_VALUE_NAME)
-            .help(&*ARG_HELP)
+            .help(&ARG_HELP)
             .display_order(order)
     }
 }
@@ -424,16 +509,21 @@ pub(super) mod args_complex {
 
     const ARG_VALUE_NAME: &str = common::ARG_PATH;
     const ARG_HELP: &str =
-        "Path to file containing 'ToBytes'-encoded named and typed args for passing to the Wasm \
-        code";
+        "Path to a JSON, TOML or YAML file containing named and typed args for passing to the Wasm \
+        code, with values either in typed form or 'ToBytes'-encoded. The format is detected from \
+        the file extension, and a path of '-' reads the args from stdin. A bytes value of the form \
+        '@path/to/file' is replaced by the contents of that file, read relative to the args file. \
+        To see an example, run '--show-arg-examples'";
 
     pub(in crate::deploy) mod session {
         use super::*;
 
-        pub const ARG_NAME: &str = "session-args-complex";
+        pub const ARG_NAME: &str = "session-args-file";
+        const ARG_ALIAS: &str = "session-args-complex";
 
         pub fn arg() -> Arg<'static, 'static> {
             super::arg(ARG_NAME, DisplayOrder::SessionArgsComplex as usize)
+                .visible_alias(ARG_ALIAS)
                 .requires(super::session::ARG_NAME)
         }
 
@@ -445,10 +535,12 @@ pub(super) mod args_complex {
     pub(in crate::deploy) mod payment {
         use super::*;
 
-        pub const ARG_NAME: &str = "payment-args-complex";
+        pub const ARG_NAME: &str = "payment-args-file";
+        const ARG_ALIAS: &str = "payment-args-complex";
 
         pub fn arg() -> Arg<'static, 'static> {
             super::arg(ARG_NAME, DisplayOrder::PaymentArgsComplex as usize)
+                .visible_alias(ARG_ALIAS)
                 .requires(super::payment::ARG_NAME)
         }
 
@@ -498,8 +590,10 @@ pub(super) mod standard_payment_amount {
     const ARG_SHORT: &str = "p";
     const ARG_HELP: &str =
         "If provided, uses the standard-payment system contract rather than custom payment Wasm. \
-        The value is the 'amount' arg of the standard-payment contract. This arg is incompatible \
-        with all other --payment-xxx args";
+        The value is the 'amount' arg of the standard-payment contract, a number of motes \
+        optionally with a unit suffix, e.g. '2500000000motes' or '2.5cspr'. This arg is \
+        incompatible with all other --payment-xxx args. If no payment is specified, the payment \
+        amount set by the selected profile is used";
 
     pub(in crate::deploy) fn arg() -> Arg<'static, 'static> {
         Arg::with_name(ARG_NAME)
@@ -532,15 +626,17 @@ pub(super) fn apply_common_creation_options<'a, 'b>(
     }
 
     subcommand = subcommand
-        .arg(
-            common::secret_key::arg(DisplayOrder::SecretKey as usize)
-                .required_unless(show_arg_examples::ARG_NAME),
-        )
+        .arg(common::secret_key::arg_required_unless(
+            DisplayOrder::SecretKey as usize,
+            &[show_arg_examples::ARG_NAME],
+        ))
+        .arg(common::signer::arg(DisplayOrder::Signer as usize))
         .arg(timestamp::arg())
         .arg(ttl::arg())
         .arg(gas_price::arg())
         .arg(dependencies::arg())
         .arg(chain_name::arg())
+        .arg(chainspec::arg())
         .arg(common::session_account::arg(
             DisplayOrder::SessionAccount as usize,
         ));
@@ -609,7 +705,9 @@ pub(crate) fn apply_common_payment_options(
                 .arg(payment_hash::ARG_NAME)
                 .arg(payment_name::ARG_NAME)
                 .arg(show_arg_examples::ARG_NAME)
-                .required(true),
+                // A profile's payment amount is applied in `payment_str_params()` if no other
+                // payment option is provided.
+                .required(crate::config::profile().payment_amount.is_none()),
         )
 }
 
@@ -643,6 +741,155 @@ pub(super) mod output {
     }
 }
 
+/// Handles providing the arg for and retrieval of the maximum number of deploys being sent at any
+/// one time by the batch subcommands.
+pub(super) mod max_concurrency {
+    use super::*;
+
+    const ARG_NAME: &str = "max-concurrency";
+    const ARG_VALUE_NAME: &str = common::ARG_INTEGER;
+
+    pub(in crate::deploy) fn arg(
+        default: &'static str,
+        help: &'static str,
+    ) -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .default_value(default)
+            .help(help)
+            .display_order(DisplayOrder::MaxConcurrency as usize)
+    }
+
+    pub(in crate::deploy) fn get(matches: &ArgMatches) -> Result<usize, Error> {
+        let value = matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME));
+        value.parse().map_err(|error| Error::FailedToParseInt {
+            context: "max-concurrency",
+            error,
+        })
+    }
+}
+
+/// Handles providing the arg for and retrieval of the encoding of the output deploy.
+pub(super) mod output_format {
+    use super::*;
+
+    const ARG_NAME: &str = "format";
+    const ARG_VALUE_NAME: &str = "FORMAT";
+    const ARG_HELP: &str =
+        "Encoding of the output deploy. 'json' is human-readable, 'binary' is the canonical \
+        serialized form used by the node and other Casper SDKs, and 'base64' is that binary form \
+        base64-encoded. The encoding of an input deploy is detected automatically";
+    const JSON: &str = "json";
+    const BINARY: &str = "binary";
+    const BASE64: &str = "base64";
+
+    pub fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .required(false)
+            .long(ARG_NAME)
+            .value_name(ARG_VALUE_NAME)
+            .possible_values(&[JSON, BINARY, BASE64])
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::OutputFormat as usize)
+    }
+
+    pub fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+}
+
+/// Handles providing the arg for and retrieval of the deploy template path.
+pub(super) mod template {
+    use super::*;
+
+    pub(in crate::deploy) const ARG_NAME: &str = "template";
+    const ARG_VALUE_NAME: &str = common::ARG_PATH;
+    const ARG_HELP: &str =
+        "Path to a deploy template, used instead of the session and payment options. The template \
+        is a TOML file, or JSON if it has a '.json' extension, with optional [deploy], [session] \
+        and [payment] sections. [deploy] holds header defaults: chain_name, ttl, gas_price, \
+        dependencies, session_account and chainspec, any of which are overridden by the \
+        corresponding option if passed. [session] and [payment] hold hash, name, package_hash, \
+        package_name, path, version, entry_point, args_complex and args, a list of simple args in \
+        the form \"NAME:TYPE='VALUE'\". [session] may also hold transfer = true, and [payment] \
+        may hold amount for standard payment. Any string may contain ${NAME} placeholders, filled \
+        in using --var";
+
+    pub(in crate::deploy) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            // Satisfies the requirement for a session and a payment, while conflicting with the
+            // other members of those groups.  Don't add a `conflicts_with()` to the arg, as clap
+            // then applies it whenever either group is present, even without this arg.
+            .groups(&["session", "payment"])
+            .display_order(DisplayOrder::Template as usize)
+    }
+
+    /// Returns the template path if provided, or an error if it is combined with any session or
+    /// payment args.
+    pub(in crate::deploy) fn get<'a>(matches: &'a ArgMatches) -> Result<Option<&'a str>, Error> {
+        let template_path = match matches.value_of(ARG_NAME) {
+            Some(template_path) => template_path,
+            None => return Ok(None),
+        };
+        let conflicting: Vec<String> = [
+            arg_simple::session::ARG_NAME,
+            args_complex::session::ARG_NAME,
+            session_entry_point::ARG_NAME,
+            session_version::ARG_NAME,
+            arg_simple::payment::ARG_NAME,
+            args_complex::payment::ARG_NAME,
+            payment_entry_point::ARG_NAME,
+            payment_version::ARG_NAME,
+        ]
+        .iter()
+        .filter(|name| matches.is_present(name))
+        .map(|name| format!("--{}", name))
+        .collect();
+        if !conflicting.is_empty() {
+            return Err(Error::ConflictingArguments {
+                context: "template",
+                args: conflicting,
+            });
+        }
+        Ok(Some(template_path))
+    }
+}
+
+/// Handles providing the arg for and retrieval of the deploy template variables.
+pub(super) mod template_var {
+    use super::*;
+
+    const ARG_NAME: &str = "var";
+    const ARG_VALUE_NAME: &str = "NAME=VALUE";
+    const ARG_HELP: &str =
+        "A value for the ${NAME} placeholders in the deploy template. This arg can be repeated to \
+        pass multiple variables";
+
+    pub(in crate::deploy) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .multiple(true)
+            .number_of_values(1)
+            .value_name(ARG_VALUE_NAME)
+            .requires(template::ARG_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::TemplateVar as usize)
+    }
+
+    pub(in crate::deploy) fn get<'a>(matches: &'a ArgMatches) -> Vec<&'a str> {
+        matches.values_of(ARG_NAME).into_iter().flatten().collect()
+    }
+}
+
 pub(super) mod input {
     use super::*;
 
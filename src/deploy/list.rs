@@ -52,7 +52,7 @@ impl<'a, 'b> ClientCommand<'a, 'b> for ListDeploys {
             let get_block_result =
                 serde_json::from_value::<GetBlockResult>(response_value).expect("should parse");
             let list = ListDeploysResult::from(get_block_result);
-            Success::Output(serde_json::to_string_pretty(&list).expect("should encode"))
+            Success::Value(serde_json::to_value(&list).expect("should encode"))
         })
     }
 }
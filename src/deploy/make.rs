@@ -20,6 +20,7 @@ impl<'a, 'b> ClientCommand<'a, 'b> for MakeDeploy {
         let subcommand = SubCommand::with_name(Self::NAME)
             .about(Self::ABOUT)
             .arg(creation_common::output::arg())
+            .arg(creation_common::output_format::arg())
             .arg(common::force::arg(
                 creation_common::DisplayOrder::Force as usize,
                 true,
@@ -27,43 +28,81 @@ impl<'a, 'b> ClientCommand<'a, 'b> for MakeDeploy {
             .display_order(display_order);
         let subcommand = creation_common::apply_common_session_options(subcommand);
         let subcommand = creation_common::apply_common_payment_options(subcommand);
+        // The template arg joins the session and payment groups, so must be added after them.
+        let subcommand = subcommand
+            .arg(creation_common::template::arg())
+            .arg(creation_common::template_var::arg());
         creation_common::apply_common_creation_options(subcommand, false)
     }
 
     async fn run(matches: &ArgMatches<'a>) -> Result<Success, Error> {
+        common::output_format::ensure_not_given(Self::NAME, matches)?;
+
         creation_common::show_arg_examples_and_exit_if_required(matches);
 
-        let secret_key = common::secret_key::get(matches);
+        let maybe_template_path = creation_common::template::get(matches)?;
+
+        let secret_key = common::secret_key::get_unless_signer(matches);
+        let signer = common::signer::get(matches);
         let timestamp = creation_common::timestamp::get(matches);
-        let ttl = creation_common::ttl::get(matches);
-        let gas_price = creation_common::gas_price::get(matches);
+        // When using a template, only explicitly-provided values override the template's values.
+        let (ttl, gas_price) = if maybe_template_path.is_some() {
+            (
+                creation_common::ttl::get_if_provided(matches),
+                creation_common::gas_price::get_if_provided(matches),
+            )
+        } else {
+            (
+                creation_common::ttl::get(matches),
+                creation_common::gas_price::get(matches),
+            )
+        };
         let dependencies = creation_common::dependencies::get(matches);
-        let chain_name = creation_common::chain_name::get(matches);
-
-        let session_str_params = creation_common::session_str_params(matches);
-        let payment_str_params = creation_common::payment_str_params(matches);
+        let chain_name = if maybe_template_path.is_some() {
+            creation_common::chain_name::get_if_provided(matches)
+        } else {
+            creation_common::chain_name::get(matches)
+        };
+        let chainspec = creation_common::chainspec::get(matches);
 
         let maybe_output_path = creation_common::output::get(matches).unwrap_or_default();
+        let output_format = creation_common::output_format::get(matches);
         let session_account = common::session_account::get(matches).unwrap_or_default();
 
         let force = common::force::get(matches);
 
-        casper_client::make_deploy(
-            maybe_output_path,
-            DeployStrParams {
-                secret_key,
-                timestamp,
-                ttl,
-                gas_price,
-                dependencies,
-                chain_name,
-                session_account: &session_account,
-            },
-            session_str_params,
-            payment_str_params,
-            force,
-        )
-        .map(|_| {
+        let deploy_str_params = DeployStrParams {
+            secret_key,
+            signer,
+            timestamp,
+            ttl,
+            gas_price,
+            dependencies,
+            chain_name,
+            session_account: &session_account,
+            chainspec,
+        };
+
+        let result = match maybe_template_path {
+            Some(template_path) => casper_client::make_deploy_from_template(
+                maybe_output_path,
+                output_format,
+                template_path,
+                creation_common::template_var::get(matches),
+                deploy_str_params,
+                force,
+            ),
+            None => casper_client::make_deploy(
+                maybe_output_path,
+                output_format,
+                deploy_str_params,
+                creation_common::session_str_params(matches),
+                creation_common::payment_str_params(matches),
+                force,
+            ),
+        };
+
+        result.map(|_| {
             Success::Output(if maybe_output_path.is_empty() {
                 String::new()
             } else {
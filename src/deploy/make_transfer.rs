@@ -21,6 +21,7 @@ impl<'a, 'b> ClientCommand<'a, 'b> for MakeTransfer {
             .about(Self::ABOUT)
             .display_order(display_order)
             .arg(creation_common::output::arg())
+            .arg(creation_common::output_format::arg())
             .arg(transfer::amount::arg())
             .arg(transfer::target_account::arg())
             .arg(transfer::transfer_id::arg())
@@ -33,38 +34,46 @@ impl<'a, 'b> ClientCommand<'a, 'b> for MakeTransfer {
     }
 
     async fn run(matches: &ArgMatches<'a>) -> Result<Success, Error> {
+        common::output_format::ensure_not_given(Self::NAME, matches)?;
+
         creation_common::show_arg_examples_and_exit_if_required(matches);
 
         let amount = transfer::amount::get(matches);
         let target_account = transfer::target_account::get(matches);
         let transfer_id = transfer::transfer_id::get(matches);
 
-        let secret_key = common::secret_key::get(matches);
+        let secret_key = common::secret_key::get_unless_signer(matches);
+        let signer = common::signer::get(matches);
         let timestamp = creation_common::timestamp::get(matches);
         let ttl = creation_common::ttl::get(matches);
         let gas_price = creation_common::gas_price::get(matches);
         let dependencies = creation_common::dependencies::get(matches);
         let chain_name = creation_common::chain_name::get(matches);
+        let chainspec = creation_common::chainspec::get(matches);
 
         let payment_str_params = creation_common::payment_str_params(matches);
 
         let maybe_output_path = creation_common::output::get(matches).unwrap_or_default();
+        let output_format = creation_common::output_format::get(matches);
         let session_account = common::session_account::get(matches)?;
         let force = common::force::get(matches);
 
         casper_client::make_transfer(
             maybe_output_path,
+            output_format,
             amount,
             target_account,
             transfer_id,
             DeployStrParams {
                 secret_key,
+                signer,
                 timestamp,
                 ttl,
                 gas_price,
                 dependencies,
                 chain_name,
                 session_account: &session_account,
+                chainspec,
             },
             payment_str_params,
             force,
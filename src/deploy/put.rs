@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use clap::{App, ArgMatches, SubCommand};
+use clap::{App, Arg, ArgMatches, SubCommand};
 
 use casper_client::{DeployStrParams, Error};
 use casper_node::rpcs::account::PutDeploy;
@@ -7,6 +7,31 @@ use casper_node::rpcs::account::PutDeploy;
 use super::creation_common::{self, DisplayOrder};
 use crate::{command::ClientCommand, common, Success};
 
+/// Handles providing the arg for and retrieval of the check-entry-point flag.
+mod check_entry_point {
+    use super::*;
+
+    const ARG_NAME: &str = "check-entry-point";
+    const ARG_HELP: &str =
+        "If passed, the stored contract called as the session is looked up on the node before \
+        the deploy is sent, and the session args are checked against the parameters of its entry \
+        point. Integer args are converted to the parameter's type where the value fits, e.g. u64 \
+        to u512. The deploy is not sent if any arg is unexpected, missing or of the wrong type";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .requires(creation_common::session_entry_point::ARG_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::CheckEntryPoint as usize)
+    }
+
+    pub(super) fn get(matches: &ArgMatches) -> bool {
+        matches.is_present(ARG_NAME)
+    }
+}
+
 #[async_trait]
 impl<'a, 'b> ClientCommand<'a, 'b> for PutDeploy {
     const NAME: &'static str = "put-deploy";
@@ -18,7 +43,8 @@ impl<'a, 'b> ClientCommand<'a, 'b> for PutDeploy {
             .display_order(display_order)
             .arg(common::verbose::arg(DisplayOrder::Verbose as usize))
             .arg(common::rpc_id::arg(DisplayOrder::RpcId as usize));
-        let subcommand = creation_common::apply_common_session_options(subcommand);
+        let subcommand =
+            creation_common::apply_common_session_options(subcommand).arg(check_entry_point::arg());
         let subcommand = creation_common::apply_common_payment_options(subcommand);
         creation_common::apply_common_creation_options(subcommand, true)
     }
@@ -30,16 +56,19 @@ impl<'a, 'b> ClientCommand<'a, 'b> for PutDeploy {
         let node_address = common::node_address::get(matches);
         let verbosity_level = common::verbose::get(matches);
 
-        let secret_key = common::secret_key::get(matches);
+        let secret_key = common::secret_key::get_unless_signer(matches);
+        let signer = common::signer::get(matches);
         let timestamp = creation_common::timestamp::get(matches);
         let ttl = creation_common::ttl::get(matches);
         let gas_price = creation_common::gas_price::get(matches);
         let dependencies = creation_common::dependencies::get(matches);
         let chain_name = creation_common::chain_name::get(matches);
+        let chainspec = creation_common::chainspec::get(matches);
         let session_account = common::session_account::get(matches)?;
 
         let session_str_params = creation_common::session_str_params(matches);
         let payment_str_params = creation_common::payment_str_params(matches);
+        let check_entry_point = check_entry_point::get(matches);
 
         casper_client::put_deploy(
             maybe_rpc_id,
@@ -47,15 +76,18 @@ impl<'a, 'b> ClientCommand<'a, 'b> for PutDeploy {
             verbosity_level,
             DeployStrParams {
                 secret_key,
+                signer,
                 timestamp,
                 ttl,
                 gas_price,
                 dependencies,
                 chain_name,
                 session_account: &session_account,
+                chainspec,
             },
             session_str_params,
             payment_str_params,
+            check_entry_point,
         )
         .await
         .map(Success::from)
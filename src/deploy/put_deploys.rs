@@ -0,0 +1,189 @@
+use async_trait::async_trait;
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use casper_client::{DeployStrParams, Error, PutDeploysSummary};
+
+use super::creation_common::{self, DisplayOrder};
+use crate::{command::ClientCommand, common, Success};
+
+const MAX_CONCURRENCY_DEFAULT: &str = "8";
+const MAX_CONCURRENCY_HELP: &str = "The maximum number of deploys being sent at any one time";
+
+/// Handles providing the arg for and retrieval of the manifest path.
+mod manifest {
+    use super::*;
+
+    const ARG_NAME: &str = "manifest";
+    const ARG_SHORT: &str = "m";
+    const ARG_VALUE_NAME: &str = common::ARG_PATH;
+    const ARG_HELP: &str =
+        "Path to the manifest describing the deploys to send. A '.csv' file must have a header row \
+        naming the fields, and multiple simple args in one field must be separated by ';', with \
+        any ';' or '\\' within an arg escaped as '\\;' or '\\\\' respectively. Any \
+        other file must be a JSON array of objects. The fields of each entry are the same as the \
+        session and payment options of 'put-deploy' with '-' replaced by '_', e.g. \
+        'session_entry_point' or 'payment_amount'; 'session_arg' and 'payment_arg' are \
+        'session_args_simple' and 'payment_args_simple', 'session_args_complex' and \
+        'payment_args_complex' hold paths, and 'is_session_transfer' is a boolean. An optional \
+        'id' field identifies the entry in the results file, defaulting to its zero-based position";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required_unless(creation_common::show_arg_examples::ARG_NAME)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Manifest as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+    }
+}
+
+/// Handles providing the arg for and retrieval of the results file path.
+mod results {
+    use super::*;
+
+    const ARG_NAME: &str = "results";
+    const ARG_SHORT: &str = "r";
+    const ARG_VALUE_NAME: &str = common::ARG_PATH;
+    const ARG_HELP: &str =
+        "Path to the results file. A JSON object is written per line as each manifest entry \
+        completes, holding the entry's 'id' and its 'deploy_hash' and/or an 'error'. Before each \
+        deploy is sent, a line holding its 'deploy_hash' and '\"sending\": true' is written. A \
+        deploy which failed to send also has '\"rejected\": true' if the node definitely didn't \
+        accept it; otherwise it is unconfirmed, as the node may have accepted it";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required_unless(creation_common::show_arg_examples::ARG_NAME)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Results as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+    }
+}
+
+/// Handles the arg for whether to resume a previous run.
+mod resume {
+    use super::*;
+
+    const ARG_NAME: &str = "resume";
+    const ARG_HELP: &str =
+        "If this flag is passed, entries recorded as successfully sent in an existing results \
+        file are skipped and new results are appended to it. Use this to retry failed entries or \
+        to continue an interrupted run. Entries which were being sent when a run was interrupted, \
+        or which are unconfirmed, are also skipped, as the node may have accepted them; check \
+        their deploy hashes with 'get-deploy'";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .conflicts_with(common::force::ARG_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Resume as usize)
+    }
+
+    pub(super) fn get(matches: &ArgMatches) -> bool {
+        matches.is_present(ARG_NAME)
+    }
+}
+
+pub struct PutDeploys;
+
+#[async_trait]
+impl<'a, 'b> ClientCommand<'a, 'b> for PutDeploys {
+    const NAME: &'static str = "put-deploys";
+    const ABOUT: &'static str =
+        "Creates a deploy for each entry of a manifest file and sends them to the network for \
+        execution, recording each entry's deploy hash or error in a results file";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        let subcommand = SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(common::verbose::arg(DisplayOrder::Verbose as usize))
+            .arg(manifest::arg())
+            .arg(results::arg())
+            .arg(creation_common::max_concurrency::arg(
+                MAX_CONCURRENCY_DEFAULT,
+                MAX_CONCURRENCY_HELP,
+            ))
+            .arg(resume::arg())
+            .arg(common::force::arg(DisplayOrder::Force as usize, true));
+        creation_common::apply_common_creation_options(subcommand, true)
+    }
+
+    async fn run(matches: &ArgMatches<'a>) -> Result<Success, Error> {
+        common::output_format::ensure_not_given(Self::NAME, matches)?;
+
+        creation_common::show_arg_examples_and_exit_if_required(matches);
+
+        let node_address = common::node_address::get(matches);
+        let verbosity_level = common::verbose::get(matches);
+
+        let manifest_path = manifest::get(matches);
+        let results_path = results::get(matches);
+        let max_concurrency = creation_common::max_concurrency::get(matches)?;
+        let resume = resume::get(matches);
+        let force = common::force::get(matches);
+
+        let secret_key = common::secret_key::get_unless_signer(matches);
+        let signer = common::signer::get(matches);
+        let timestamp = creation_common::timestamp::get(matches);
+        let ttl = creation_common::ttl::get(matches);
+        let gas_price = creation_common::gas_price::get(matches);
+        let dependencies = creation_common::dependencies::get(matches);
+        let chain_name = creation_common::chain_name::get(matches);
+        let chainspec = creation_common::chainspec::get(matches);
+        let session_account = common::session_account::get(matches)?;
+
+        casper_client::put_deploys(
+            node_address,
+            verbosity_level,
+            manifest_path,
+            results_path,
+            max_concurrency,
+            resume,
+            force,
+            DeployStrParams {
+                secret_key,
+                signer,
+                timestamp,
+                ttl,
+                gas_price,
+                dependencies,
+                chain_name,
+                session_account: &session_account,
+                chainspec,
+            },
+        )
+        .await
+        .map(
+            |PutDeploysSummary {
+                 succeeded,
+                 failed,
+                 skipped,
+                 unconfirmed,
+             }| {
+                Success::Output(format!(
+                    "Sent {} deploys, {} failed, {} skipped as already sent and {} \
+                    unconfirmed. Results written to {}",
+                    succeeded, failed, skipped, unconfirmed, results_path
+                ))
+            },
+        )
+    }
+}
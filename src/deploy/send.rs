@@ -24,6 +24,7 @@ impl<'a, 'b> ClientCommand<'a, 'b> for SendDeploy {
             ))
             .arg(common::rpc_id::arg(DisplayOrder::RpcId as usize))
             .arg(creation_common::input::arg())
+            .arg(creation_common::chainspec::arg())
     }
 
     async fn run(matches: &ArgMatches<'a>) -> Result<Success, Error> {
@@ -31,9 +32,16 @@ impl<'a, 'b> ClientCommand<'a, 'b> for SendDeploy {
         let node_address = common::node_address::get(matches);
         let verbosity_level = common::verbose::get(matches);
         let input_path = creation_common::input::get(matches);
+        let chainspec = creation_common::chainspec::get(matches);
 
-        casper_client::send_deploy_file(maybe_rpc_id, node_address, verbosity_level, input_path)
-            .await
-            .map(Success::from)
+        casper_client::send_deploy_file(
+            maybe_rpc_id,
+            node_address,
+            verbosity_level,
+            input_path,
+            chainspec,
+        )
+        .await
+        .map(Success::from)
     }
 }
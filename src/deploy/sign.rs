@@ -22,8 +22,13 @@ impl<'a, 'b> ClientCommand<'a, 'b> for SignDeploy {
             .arg(common::secret_key::arg(
                 creation_common::DisplayOrder::SecretKey as usize,
             ))
+            .arg(common::signer::arg(
+                creation_common::DisplayOrder::Signer as usize,
+            ))
             .arg(creation_common::input::arg())
             .arg(creation_common::output::arg())
+            .arg(creation_common::output_format::arg())
+            .arg(creation_common::chainspec::arg())
             .arg(common::force::arg(
                 creation_common::DisplayOrder::Force as usize,
                 true,
@@ -31,21 +36,33 @@ impl<'a, 'b> ClientCommand<'a, 'b> for SignDeploy {
     }
 
     async fn run(matches: &ArgMatches<'a>) -> Result<Success, Error> {
+        common::output_format::ensure_not_given(Self::NAME, matches)?;
+
         let input_path = creation_common::input::get(matches);
-        let secret_key = common::secret_key::get(matches);
+        let secret_key = common::secret_key::get_unless_signer(matches);
+        let signer = common::signer::get(matches);
         let maybe_output_path = creation_common::output::get(matches).unwrap_or_default();
+        let output_format = creation_common::output_format::get(matches);
+        let chainspec = creation_common::chainspec::get(matches);
         let force = common::force::get(matches);
-        casper_client::sign_deploy_file(input_path, secret_key, maybe_output_path, force).map(
-            |_| {
-                Success::Output(if maybe_output_path.is_empty() {
-                    String::new()
-                } else {
-                    format!(
-                        "Signed the deploy at {} and wrote to {}",
-                        input_path, maybe_output_path
-                    )
-                })
-            },
+        casper_client::sign_deploy_file(
+            input_path,
+            secret_key,
+            signer,
+            maybe_output_path,
+            output_format,
+            chainspec,
+            force,
         )
+        .map(|_| {
+            Success::Output(if maybe_output_path.is_empty() {
+                String::new()
+            } else {
+                format!(
+                    "Signed the deploy at {} and wrote to {}",
+                    input_path, maybe_output_path
+                )
+            })
+        })
     }
 }
@@ -13,7 +13,9 @@ pub(super) mod amount {
     const ARG_NAME: &str = "amount";
     const ARG_SHORT: &str = "a";
     const ARG_VALUE_NAME: &str = "512-BIT INTEGER";
-    const ARG_HELP: &str = "The number of motes to transfer";
+    const ARG_HELP: &str =
+        "The number of motes to transfer, optionally with a unit suffix, e.g. '2500000000motes' \
+        or '2.5cspr'. A CSPR amount may have up to nine decimal places";
 
     pub(in crate::deploy) fn arg() -> Arg<'static, 'static> {
         Arg::with_name(ARG_NAME)
@@ -115,12 +117,14 @@ impl<'a, 'b> ClientCommand<'a, 'b> for Transfer {
         let node_address = common::node_address::get(matches);
         let verbosity_level = common::verbose::get(matches);
 
-        let secret_key = common::secret_key::get(matches);
+        let secret_key = common::secret_key::get_unless_signer(matches);
+        let signer = common::signer::get(matches);
         let timestamp = creation_common::timestamp::get(matches);
         let ttl = creation_common::ttl::get(matches);
         let gas_price = creation_common::gas_price::get(matches);
         let dependencies = creation_common::dependencies::get(matches);
         let chain_name = creation_common::chain_name::get(matches);
+        let chainspec = creation_common::chainspec::get(matches);
         let session_account = common::session_account::get(matches)?;
 
         let payment_str_params = creation_common::payment_str_params(matches);
@@ -134,12 +138,14 @@ impl<'a, 'b> ClientCommand<'a, 'b> for Transfer {
             transfer_id,
             DeployStrParams {
                 secret_key,
+                signer,
                 timestamp,
                 ttl,
                 gas_price,
                 dependencies,
                 chain_name,
                 session_account: &session_account,
+                chainspec,
             },
             payment_str_params,
         )
@@ -34,7 +34,7 @@ mod output_file {
             .long(ARG_NAME)
             .short(ARG_NAME_SHORT)
             .required(false)
-            .default_value(&*ARG_DEFAULT)
+            .default_value(&ARG_DEFAULT)
             .value_name(ARG_VALUE_NAME)
             .help(ARG_HELP)
             .display_order(DisplayOrder::OutputFile as usize)
@@ -95,6 +95,8 @@ impl<'a, 'b> ClientCommand<'a, 'b> for GenerateCompletion {
     }
 
     async fn run(matches: &ArgMatches<'a>) -> Result<Success, Error> {
+        common::output_format::ensure_not_given(Self::NAME, matches)?;
+
         let output_path = output_file::get(matches);
         let force = common::force::get(matches);
         let shell = shell::get(matches);
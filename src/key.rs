@@ -0,0 +1,147 @@
+mod convert;
+mod decrypt;
+mod encrypt;
+mod parse;
+mod public_from_secret;
+mod show;
+
+use async_trait::async_trait;
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+
+use casper_client::Error;
+
+use crate::{command::ClientCommand, common, Success};
+use convert::ConvertKey;
+use decrypt::DecryptKey;
+use encrypt::EncryptKey;
+use parse::ParseKey;
+use public_from_secret::PublicFromSecret;
+use show::ShowKey;
+
+/// This struct defines the order in which the subcommands are shown in the help message.
+enum SubcommandOrder {
+    Show,
+    Convert,
+    PublicFromSecret,
+    Encrypt,
+    Decrypt,
+    Parse,
+}
+
+/// This struct defines the order in which the args are shown for the subcommands' help messages.
+enum DisplayOrder {
+    Input,
+    Output,
+    Format,
+    HexIsSecret,
+    PasswordFile,
+    Force,
+    Key,
+}
+
+/// Handles providing the arg for and retrieval of the input key file.
+mod input {
+    use super::*;
+
+    const ARG_NAME: &str = "input";
+    const ARG_SHORT: &str = "i";
+    const ARG_VALUE_NAME: &str = common::ARG_PATH;
+
+    pub(super) fn arg(help: &'static str) -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required(true)
+            .value_name(ARG_VALUE_NAME)
+            .help(help)
+            .display_order(DisplayOrder::Input as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+    }
+}
+
+/// Handles providing the arg for and retrieval of the output key file.
+mod output {
+    use super::*;
+
+    const ARG_NAME: &str = "output";
+    const ARG_SHORT: &str = "o";
+    const ARG_VALUE_NAME: &str = common::ARG_PATH;
+
+    pub(super) fn arg(help: &'static str) -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .help(help)
+            .display_order(DisplayOrder::Output as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+}
+
+/// Handles providing the arg for and retrieval of the flag to read hex keys as secret keys.
+mod hex_is_secret {
+    use super::*;
+
+    const ARG_NAME: &str = "secret";
+    const ARG_HELP: &str =
+        "If this flag is passed, a hex-encoded input key is read as a secret key rather than as a \
+        public key. PEM, DER and base64-encoded keys identify the kind of key they hold, so this \
+        flag has no effect on them";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::HexIsSecret as usize)
+    }
+
+    pub(super) fn get(matches: &ArgMatches) -> bool {
+        matches.is_present(ARG_NAME)
+    }
+}
+
+pub struct Key;
+
+#[async_trait]
+impl<'a, 'b> ClientCommand<'a, 'b> for Key {
+    const NAME: &'static str = "key";
+    const ABOUT: &'static str =
+        "Inspects, converts and manages key files, and parses the keys of global state";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommand(ShowKey::build(SubcommandOrder::Show as usize))
+            .subcommand(ConvertKey::build(SubcommandOrder::Convert as usize))
+            .subcommand(PublicFromSecret::build(
+                SubcommandOrder::PublicFromSecret as usize,
+            ))
+            .subcommand(EncryptKey::build(SubcommandOrder::Encrypt as usize))
+            .subcommand(DecryptKey::build(SubcommandOrder::Decrypt as usize))
+            .subcommand(ParseKey::build(SubcommandOrder::Parse as usize))
+    }
+
+    async fn run(matches: &ArgMatches<'a>) -> Result<Success, Error> {
+        match matches.subcommand() {
+            (ShowKey::NAME, Some(matches)) => ShowKey::run(matches).await,
+            (ConvertKey::NAME, Some(matches)) => ConvertKey::run(matches).await,
+            (PublicFromSecret::NAME, Some(matches)) => PublicFromSecret::run(matches).await,
+            (EncryptKey::NAME, Some(matches)) => EncryptKey::run(matches).await,
+            (DecryptKey::NAME, Some(matches)) => DecryptKey::run(matches).await,
+            (ParseKey::NAME, Some(matches)) => ParseKey::run(matches).await,
+            (subcommand, _) => unreachable!("unknown key subcommand '{}'", subcommand),
+        }
+    }
+}
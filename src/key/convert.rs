@@ -0,0 +1,78 @@
+use async_trait::async_trait;
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use casper_client::{key_format, Error};
+
+use super::{hex_is_secret, input, output, DisplayOrder};
+use crate::{command::ClientCommand, common, Success};
+
+const INPUT_HELP: &str =
+    "Path to a secret or public key file, or the key itself as PEM, base64 or hex. Secret key \
+    files may be keystores";
+const OUTPUT_HELP: &str = "Path to the key file to write. If omitted, the key is written to stdout";
+
+/// Handles providing the arg for and retrieval of the output encoding.
+mod format {
+    use super::*;
+
+    const ARG_NAME: &str = "to";
+    const ARG_VALUE_NAME: &str = "FORMAT";
+    const ARG_HELP: &str =
+        "The encoding of the key to write. \"base64\" is the base64-encoded DER, as in the body \
+        of a PEM file, and \"hex\" is the hex-encoded algorithm tag followed by the key bytes, as \
+        in \"public_key_hex\"";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(true)
+            .possible_values(&key_format::FORMATS)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Format as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+    }
+}
+
+pub struct ConvertKey;
+
+#[async_trait]
+impl<'a, 'b> ClientCommand<'a, 'b> for ConvertKey {
+    const NAME: &'static str = "convert";
+    const ABOUT: &'static str =
+        "Converts a secret or public key between PEM, DER, base64 and hex encodings";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(input::arg(INPUT_HELP))
+            .arg(output::arg(OUTPUT_HELP))
+            .arg(format::arg())
+            .arg(hex_is_secret::arg())
+            .arg(common::force::arg(DisplayOrder::Force as usize, true))
+    }
+
+    async fn run(matches: &ArgMatches<'a>) -> Result<Success, Error> {
+        common::output_format::ensure_not_given(Self::NAME, matches)?;
+
+        let input = input::get(matches);
+        let maybe_output_path = output::get(matches);
+        let format = format::get(matches);
+        let hex_is_secret = hex_is_secret::get(matches);
+        let force = common::force::get(matches);
+
+        key_format::convert(input, format, hex_is_secret, maybe_output_path, force).map(|_| {
+            Success::Output(if maybe_output_path.is_empty() {
+                String::new()
+            } else {
+                format!("Wrote {} key to {}", format, maybe_output_path)
+            })
+        })
+    }
+}
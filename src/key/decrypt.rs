@@ -0,0 +1,51 @@
+use async_trait::async_trait;
+use clap::{App, ArgMatches, SubCommand};
+
+use casper_client::{keystore, Error};
+
+use super::{input, output, DisplayOrder};
+use crate::{command::ClientCommand, common, Success};
+
+const INPUT_HELP: &str = "Path to the keystore file to decrypt";
+const OUTPUT_HELP: &str =
+    "Path to the PEM-encoded secret key file to write. If omitted, the secret key is written to \
+    stdout";
+
+pub struct DecryptKey;
+
+#[async_trait]
+impl<'a, 'b> ClientCommand<'a, 'b> for DecryptKey {
+    const NAME: &'static str = "decrypt";
+    const ABOUT: &'static str =
+        "Decrypts a keystore, writing the secret key as an unencrypted PEM file";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(input::arg(INPUT_HELP))
+            .arg(output::arg(OUTPUT_HELP))
+            .arg(common::password_file::arg(
+                DisplayOrder::PasswordFile as usize,
+            ))
+            .arg(common::force::arg(DisplayOrder::Force as usize, true))
+    }
+
+    async fn run(matches: &ArgMatches<'a>) -> Result<Success, Error> {
+        common::output_format::ensure_not_given(Self::NAME, matches)?;
+
+        let input_path = input::get(matches);
+        let maybe_output_path = output::get(matches);
+        let password_file = common::password_file::get(matches);
+        let force = common::force::get(matches);
+
+        let password = keystore::read_password(password_file, "Password: ", false)?;
+        keystore::decrypt_file(input_path, maybe_output_path, &password, force).map(|_| {
+            Success::Output(if maybe_output_path.is_empty() {
+                String::new()
+            } else {
+                format!("Wrote decrypted key to {}", maybe_output_path)
+            })
+        })
+    }
+}
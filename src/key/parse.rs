@@ -0,0 +1,69 @@
+use async_trait::async_trait;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use once_cell::sync::Lazy;
+
+use casper_client::{global_state_key, Error};
+
+use super::DisplayOrder;
+use crate::{command::ClientCommand, Success};
+
+static AFTER_HELP: Lazy<String> = Lazy::new(|| {
+    format!(
+        "The supported forms of key are:\n{}",
+        global_state_key::supported_key_formats()
+    )
+});
+
+/// Handles providing the arg for and retrieval of the formatted key.
+mod formatted_key {
+    use super::*;
+
+    const ARG_NAME: &str = "key";
+    const ARG_SHORT: &str = "k";
+    const ARG_VALUE_NAME: &str = "FORMATTED STRING";
+    const ARG_HELP: &str =
+        "The key to parse, formatted as for the --key arg of 'query-global-state', e.g. \
+        'uref-<HEX STRING>-007' or 'era-1', or a hex-encoded public key";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required(true)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Key as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+    }
+}
+
+pub struct ParseKey;
+
+#[async_trait]
+impl<'a, 'b> ClientCommand<'a, 'b> for ParseKey {
+    const NAME: &'static str = "parse";
+    const ABOUT: &'static str =
+        "Prints the variant, hex-encoded address and any access rights of a key under which \
+        values are stored in global state";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .after_help(AFTER_HELP.as_str())
+            .display_order(display_order)
+            .arg(formatted_key::arg())
+    }
+
+    async fn run(matches: &ArgMatches<'a>) -> Result<Success, Error> {
+        let key = formatted_key::get(matches);
+
+        global_state_key::parse(key).map(|description| {
+            Success::Value(serde_json::to_value(&description).expect("should encode"))
+        })
+    }
+}
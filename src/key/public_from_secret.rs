@@ -0,0 +1,77 @@
+use async_trait::async_trait;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use once_cell::sync::Lazy;
+
+use casper_client::{
+    keygen::{self, PUBLIC_KEY_HEX, PUBLIC_KEY_PEM},
+    Error,
+};
+
+use super::{input, DisplayOrder};
+use crate::{command::ClientCommand, common, Success};
+
+const INPUT_HELP: &str = "Path to the secret key file, which may be a keystore";
+
+static MORE_ABOUT: Lazy<String> = Lazy::new(|| {
+    format!(
+        "{}. Creates \"{}\" and \"{}\" in the given directory",
+        PublicFromSecret::ABOUT,
+        PUBLIC_KEY_PEM,
+        PUBLIC_KEY_HEX
+    )
+});
+
+/// Handles providing the arg for and retrieval of the output directory.
+mod output_dir {
+    use super::*;
+
+    const ARG_NAME: &str = "output-dir";
+    const ARG_SHORT: &str = "o";
+    const ARG_VALUE_NAME: &str = common::ARG_PATH;
+    const ARG_HELP: &str =
+        "Path to output directory where the public key files will be created. If the path \
+        doesn't exist, it will be created. If not set, the current working directory will be used";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Output as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or(".")
+    }
+}
+
+pub struct PublicFromSecret;
+
+#[async_trait]
+impl<'a, 'b> ClientCommand<'a, 'b> for PublicFromSecret {
+    const NAME: &'static str = "public-from-secret";
+    const ABOUT: &'static str = "Writes the public key files corresponding to a secret key";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .long_about(MORE_ABOUT.as_str())
+            .display_order(display_order)
+            .arg(input::arg(INPUT_HELP))
+            .arg(output_dir::arg())
+            .arg(common::force::arg(DisplayOrder::Force as usize, false))
+    }
+
+    async fn run(matches: &ArgMatches<'a>) -> Result<Success, Error> {
+        common::output_format::ensure_not_given(Self::NAME, matches)?;
+
+        let input_path = input::get(matches);
+        let output_dir = output_dir::get(matches);
+        let force = common::force::get(matches);
+
+        keygen::generate_public_key_files(input_path, output_dir, force)
+            .map(|_| Success::Output(format!("Wrote files to {}", output_dir)))
+    }
+}
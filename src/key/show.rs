@@ -0,0 +1,37 @@
+use async_trait::async_trait;
+use clap::{App, ArgMatches, SubCommand};
+
+use casper_client::{key_format, Error};
+
+use super::{hex_is_secret, input};
+use crate::{command::ClientCommand, Success};
+
+const INPUT_HELP: &str =
+    "Path to a secret or public key file, or the key itself as PEM, base64 or hex. Secret key \
+    files may be keystores";
+
+pub struct ShowKey;
+
+#[async_trait]
+impl<'a, 'b> ClientCommand<'a, 'b> for ShowKey {
+    const NAME: &'static str = "show";
+    const ABOUT: &'static str =
+        "Prints the algorithm, public key, checksummed public key and account hash of a secret or \
+        public key";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(input::arg(INPUT_HELP))
+            .arg(hex_is_secret::arg())
+    }
+
+    async fn run(matches: &ArgMatches<'a>) -> Result<Success, Error> {
+        let input = input::get(matches);
+        let hex_is_secret = hex_is_secret::get(matches);
+
+        key_format::show(input, hex_is_secret)
+            .map(|info| Success::Value(serde_json::to_value(&info).expect("should encode")))
+    }
+}
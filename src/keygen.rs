@@ -1,21 +1,37 @@
+use std::{path::Path, time::Duration};
+
 use async_trait::async_trait;
-use clap::{App, Arg, ArgMatches, SubCommand};
+use clap::{App, Arg, ArgGroup, ArgMatches, SubCommand};
 use once_cell::sync::Lazy;
+use zeroize::Zeroizing;
 
 use casper_client::{
-    keygen::{self, FILES, PUBLIC_KEY_HEX},
-    Error,
+    keygen::{
+        self, VanityProgress, VanityTarget, FILES, INDEX_CSV, PUBLIC_KEY_HEX, SECRET_KEY_JSON,
+        SECRET_KEY_PEM,
+    },
+    keystore, Error,
 };
+use casper_types::AsymmetricType;
 
 use crate::{command::ClientCommand, common, Success};
 
 static MORE_ABOUT: Lazy<String> = Lazy::new(|| {
     format!(
         "{}. Creates {:?}. \"{}\" contains the hex-encoded key's bytes with the hex-encoded \
-        algorithm tag prefixed",
+        algorithm tag prefixed. With --encrypt, \"{}\" is created in place of \"{}\". With \
+        --mnemonic or --from-mnemonic, the keys are derived from a BIP-39 mnemonic: secp256k1 \
+        keys via BIP-32 using the path \"{}\" by default, and Ed25519 keys via SLIP-10 using \
+        the path \"{}\" by default. With --count, many key pairs are written to numbered \
+        subdirectories, and with --vanity, key pairs are generated until one is found with the \
+        given prefix",
         Keygen::ABOUT,
         FILES,
-        PUBLIC_KEY_HEX
+        PUBLIC_KEY_HEX,
+        SECRET_KEY_JSON,
+        SECRET_KEY_PEM,
+        keygen::DEFAULT_SECP256K1_PATH,
+        keygen::DEFAULT_ED25519_PATH
     )
 });
 
@@ -24,8 +40,20 @@ enum DisplayOrder {
     OutputDir,
     Force,
     Algorithm,
+    Encrypt,
+    PasswordFile,
+    Mnemonic,
+    Words,
+    FromMnemonic,
+    Path,
+    Count,
+    Vanity,
+    VanityTarget,
 }
 
+/// The name of the group of args providing a mnemonic.
+const MNEMONIC_GROUP: &str = "mnemonic-source";
+
 /// Handles providing the arg for and retrieval of the output directory.
 mod output_dir {
     use super::*;
@@ -78,6 +106,233 @@ mod algorithm {
     }
 }
 
+/// Handles providing the arg for and retrieval of the encrypt flag.
+mod encrypt {
+    use super::*;
+
+    pub(super) const ARG_NAME: &str = "encrypt";
+    const ARG_HELP: &str =
+        "If this flag is passed, the secret key is encrypted with a password and written as a \
+        keystore to \"secret_key.json\" rather than to \"secret_key.pem\"";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Encrypt as usize)
+    }
+
+    pub(super) fn get(matches: &ArgMatches) -> bool {
+        matches.is_present(ARG_NAME)
+    }
+}
+
+/// Handles providing the arg for and retrieval of the flag to generate a new mnemonic.
+mod mnemonic {
+    use super::*;
+
+    pub(super) const ARG_NAME: &str = "mnemonic";
+    const ARG_HELP: &str =
+        "If this flag is passed, a new BIP-39 mnemonic is generated and printed, and the keys are \
+        derived from it. Store the mnemonic securely: the keys can be restored from it using \
+        --from-mnemonic";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Mnemonic as usize)
+    }
+
+    pub(super) fn get(matches: &ArgMatches) -> bool {
+        matches.is_present(ARG_NAME)
+    }
+}
+
+/// Handles providing the arg for and retrieval of the number of words in a new mnemonic.
+mod words {
+    use super::*;
+
+    const ARG_NAME: &str = "words";
+    const ARG_VALUE_NAME: &str = common::ARG_INTEGER;
+    const ARG_HELP: &str = "The number of words in the generated mnemonic";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .requires(mnemonic::ARG_NAME)
+            .possible_values(&["12", "15", "18", "21", "24"])
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Words as usize)
+    }
+
+    pub(super) fn get(matches: &ArgMatches) -> usize {
+        matches
+            .value_of(ARG_NAME)
+            .map(|value| value.parse().expect("should be a possible value"))
+            .unwrap_or(keygen::DEFAULT_MNEMONIC_WORD_COUNT)
+    }
+}
+
+/// Handles providing the arg for and retrieval of an existing mnemonic.
+mod from_mnemonic {
+    use super::*;
+
+    pub(super) const ARG_NAME: &str = "from-mnemonic";
+    const ARG_VALUE_NAME: &str = "WORDS";
+    const ARG_HELP: &str =
+        "A BIP-39 mnemonic from which to restore the keys, as a single space-separated string";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::FromMnemonic as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> Option<&'a str> {
+        matches.value_of(ARG_NAME)
+    }
+}
+
+/// Handles providing the arg for and retrieval of the derivation path.
+mod path {
+    use super::*;
+
+    const ARG_NAME: &str = "path";
+    const ARG_VALUE_NAME: &str = "DERIVATION PATH";
+    const ARG_HELP: &str =
+        "The path along which the keys are derived from the mnemonic, e.g. \"m/44'/506'/0'/0/1\". \
+        Every level must be hardened for Ed25519 keys. If not provided, the default path for the \
+        algorithm is used";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .requires(MNEMONIC_GROUP)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Path as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+}
+
+/// Handles providing the arg for and retrieval of the number of key pairs to generate.
+mod count {
+    use super::*;
+
+    pub(super) const ARG_NAME: &str = "count";
+    const ARG_VALUE_NAME: &str = common::ARG_INTEGER;
+    const ARG_HELP: &str =
+        "The number of key pairs to generate. Each is written to a numbered subdirectory of the \
+        output directory, and their public keys and account hashes are listed in \"index.csv\" \
+        in the output directory";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .conflicts_with(MNEMONIC_GROUP)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Count as usize)
+    }
+
+    pub(super) fn get(matches: &ArgMatches) -> Result<Option<usize>, Error> {
+        matches
+            .value_of(ARG_NAME)
+            .map(|value| {
+                value.parse().map_err(|error| Error::FailedToParseInt {
+                    context: "count",
+                    error,
+                })
+            })
+            .transpose()
+    }
+}
+
+/// Handles providing the arg for and retrieval of the vanity prefix.
+mod vanity {
+    use super::*;
+
+    pub(super) const ARG_NAME: &str = "vanity";
+    const ARG_VALUE_NAME: &str = "PREFIX";
+    const ARG_HELP: &str =
+        "Searches using all CPU cores for a key pair whose public key (following the algorithm \
+        tag) or account hash starts with this case-insensitive hex prefix. Each extra hex digit \
+        makes the search take 16 times longer";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .conflicts_with(MNEMONIC_GROUP)
+            .conflicts_with(count::ARG_NAME)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Vanity as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> Option<&'a str> {
+        matches.value_of(ARG_NAME)
+    }
+}
+
+/// Handles providing the arg for and retrieval of what the vanity prefix is matched against.
+mod vanity_target {
+    use super::*;
+
+    const ARG_NAME: &str = "vanity-target";
+    const ARG_VALUE_NAME: &str = common::ARG_STRING;
+    const ARG_HELP: &str = "What the vanity prefix is matched against";
+    const PUBLIC_KEY: &str = "public-key";
+    const ACCOUNT_HASH: &str = "account-hash";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .requires(vanity::ARG_NAME)
+            .possible_values(&[PUBLIC_KEY, ACCOUNT_HASH])
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::VanityTarget as usize)
+    }
+
+    pub(super) fn get(matches: &ArgMatches) -> VanityTarget {
+        match matches.value_of(ARG_NAME) {
+            Some(ACCOUNT_HASH) => VanityTarget::AccountHash,
+            _ => VanityTarget::PublicKey,
+        }
+    }
+}
+
+/// Prints the progress of a vanity search to stderr, overwriting the previous progress.
+///
+/// As every key tried is equally likely to match, the expected time to a match doesn't decrease as
+/// the search goes on, so it is shown as an expected interval between matches.
+fn print_progress(progress: &VanityProgress) {
+    let rate = progress.attempts as f64 / progress.elapsed.as_secs_f64().max(f64::EPSILON);
+    let interval = Duration::from_secs((progress.expected_attempts as f64 / rate.max(1.0)) as u64);
+    eprint!(
+        "\rTried {} keys at {:.0} keys/s, expecting a match every ~{} keys (~{})   ",
+        progress.attempts,
+        rate,
+        progress.expected_attempts,
+        humantime::format_duration(interval)
+    );
+}
+
 pub struct Keygen {}
 
 #[async_trait]
@@ -93,14 +348,106 @@ impl<'a, 'b> ClientCommand<'a, 'b> for Keygen {
             .arg(output_dir::arg())
             .arg(common::force::arg(DisplayOrder::Force as usize, false))
             .arg(algorithm::arg())
+            .arg(encrypt::arg())
+            .arg(
+                common::password_file::arg(DisplayOrder::PasswordFile as usize)
+                    .requires(encrypt::ARG_NAME),
+            )
+            .arg(mnemonic::arg())
+            .arg(words::arg())
+            .arg(from_mnemonic::arg())
+            .arg(path::arg())
+            .arg(count::arg())
+            .arg(vanity::arg())
+            .arg(vanity_target::arg())
+            .group(
+                ArgGroup::with_name(MNEMONIC_GROUP)
+                    .arg(mnemonic::ARG_NAME)
+                    .arg(from_mnemonic::ARG_NAME)
+                    .required(false),
+            )
     }
 
     async fn run(matches: &ArgMatches<'a>) -> Result<Success, Error> {
+        common::output_format::ensure_not_given(Self::NAME, matches)?;
+
         let output_dir = output_dir::get(matches);
         let algorithm = algorithm::get(matches);
         let force = common::force::get(matches);
 
-        keygen::generate_files(&output_dir, algorithm, force)
-            .map(|_| Success::Output(format!("Wrote files to {}", output_dir)))
+        let password = if encrypt::get(matches) {
+            let password_file = common::password_file::get(matches);
+            let password = keystore::read_password(password_file, "New password: ", true)?;
+            // An empty password means no encryption to the library, so must be rejected here.
+            if password.is_empty() {
+                return Err(Error::InvalidArgument {
+                    context: "password",
+                    error: "must not be empty".to_string(),
+                });
+            }
+            password
+        } else {
+            Zeroizing::default()
+        };
+
+        let maybe_new_mnemonic = if mnemonic::get(matches) {
+            Some(keygen::generate_mnemonic(words::get(matches))?)
+        } else {
+            None
+        };
+        let maybe_mnemonic = maybe_new_mnemonic
+            .as_deref()
+            .or_else(|| from_mnemonic::get(matches));
+
+        if let Some(count) = count::get(matches)? {
+            keygen::generate_numbered_files(&output_dir, algorithm, count, &password, force)?;
+            return Ok(Success::Output(format!(
+                "Wrote {} key pairs to {}, listed in {}",
+                count,
+                output_dir,
+                Path::new(&output_dir).join(INDEX_CSV).display()
+            )));
+        }
+
+        if let Some(prefix) = vanity::get(matches) {
+            let public_key = keygen::generate_vanity_files(
+                &output_dir,
+                algorithm,
+                prefix,
+                vanity_target::get(matches),
+                &password,
+                force,
+                print_progress,
+            )?;
+            eprintln!();
+            return Ok(Success::Output(format!(
+                "Found public key {} with account hash {}\nWrote files to {}",
+                public_key.to_hex(),
+                public_key.to_account_hash().to_formatted_string(),
+                output_dir
+            )));
+        }
+
+        match maybe_mnemonic {
+            Some(mnemonic) => keygen::generate_files_from_mnemonic(
+                &output_dir,
+                algorithm,
+                mnemonic,
+                path::get(matches),
+                &password,
+                force,
+            )?,
+            None if password.is_empty() => keygen::generate_files(&output_dir, algorithm, force)?,
+            None => keygen::generate_encrypted_files(&output_dir, algorithm, &password, force)?,
+        }
+
+        let mut output = format!("Wrote files to {}", output_dir);
+        if let Some(mnemonic) = maybe_new_mnemonic {
+            output = format!(
+                "{}\n\nMnemonic (store this securely, as it can be used to restore the keys):\n{}",
+                output, mnemonic
+            );
+        }
+        Ok(Success::Output(output))
     }
 }
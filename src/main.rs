@@ -1,7 +1,9 @@
 mod account_address;
 mod block;
+mod cl;
 mod command;
 mod common;
+mod config;
 mod deploy;
 mod docs;
 mod generate_completion;
@@ -12,15 +14,20 @@ mod get_dictionary_item;
 mod get_era_info_by_switch_block;
 mod get_state_hash;
 mod get_validator_changes;
+mod key;
 mod keygen;
+mod make_genesis_accounts;
+mod message;
 mod query_global_state;
 
-use std::process;
+use std::{env, process};
 
 use clap::{crate_version, App};
 use once_cell::sync::Lazy;
+use serde::Serialize;
+use serde_json::json;
 
-use casper_client::Error;
+use casper_client::{keystore, output, units, Error, ErrorCategory};
 use casper_node::rpcs::{
     account::PutDeploy,
     chain::{GetBlock, GetBlockTransfers, GetEraInfoBySwitchBlock, GetStateRootHash},
@@ -30,10 +37,18 @@ use casper_node::rpcs::{
 };
 
 use account_address::GenerateAccountHash as AccountAddress;
+use cl::CL;
 use command::{ClientCommand, Success};
-use deploy::{ListDeploys, MakeDeploy, MakeTransfer, SendDeploy, SignDeploy, Transfer};
+use config::Config;
+use deploy::{
+    BatchTransfer, ListDeploys, MakeDeploy, MakeTransfer, PutDeploys, SendDeploy, SignDeploy,
+    Transfer,
+};
 use generate_completion::GenerateCompletion;
+use key::Key;
 use keygen::Keygen;
+use make_genesis_accounts::MakeGenesisAccounts;
+use message::{SignMessage, VerifyMessage};
 
 const APP_NAME: &str = "Casper client";
 
@@ -54,10 +69,12 @@ static VERSION: Lazy<String> =
 /// This struct defines the order in which the subcommands are shown in the app's help message.
 enum DisplayOrder {
     PutDeploy,
+    PutDeploys,
     MakeDeploy,
     SignDeploy,
     SendDeploy,
     Transfer,
+    BatchTransfer,
     MakeTransfer,
     GetDeploy,
     GetBlock,
@@ -72,6 +89,12 @@ enum DisplayOrder {
     GetAuctionInfo,
     GetValidatorChanges,
     Keygen,
+    Key,
+    SignMessage,
+    VerifyMessage,
+    CL,
+    MakeGenesisAccounts,
+    Config,
     GenerateCompletion,
     GetRpcs,
     AccountAddress,
@@ -81,11 +104,19 @@ fn cli<'a, 'b>() -> App<'a, 'b> {
     App::new(APP_NAME)
         .version(VERSION.as_str())
         .about("A client for interacting with the Casper network")
+        .arg(config::profile_name::arg(0))
+        .arg(config::config_file::arg(1))
+        .arg(common::output_format::arg(2))
+        .arg(common::query::arg(3))
+        .arg(common::error_format::arg(4))
+        .arg(common::display_units::arg(5))
         .subcommand(PutDeploy::build(DisplayOrder::PutDeploy as usize))
+        .subcommand(PutDeploys::build(DisplayOrder::PutDeploys as usize))
         .subcommand(MakeDeploy::build(DisplayOrder::MakeDeploy as usize))
         .subcommand(SignDeploy::build(DisplayOrder::SignDeploy as usize))
         .subcommand(SendDeploy::build(DisplayOrder::SendDeploy as usize))
         .subcommand(Transfer::build(DisplayOrder::Transfer as usize))
+        .subcommand(BatchTransfer::build(DisplayOrder::BatchTransfer as usize))
         .subcommand(MakeTransfer::build(DisplayOrder::MakeTransfer as usize))
         .subcommand(GetDeploy::build(DisplayOrder::GetDeploy as usize))
         .subcommand(GetBlock::build(DisplayOrder::GetBlock as usize))
@@ -106,6 +137,14 @@ fn cli<'a, 'b>() -> App<'a, 'b> {
             DisplayOrder::GetValidatorChanges as usize,
         ))
         .subcommand(Keygen::build(DisplayOrder::Keygen as usize))
+        .subcommand(Key::build(DisplayOrder::Key as usize))
+        .subcommand(SignMessage::build(DisplayOrder::SignMessage as usize))
+        .subcommand(VerifyMessage::build(DisplayOrder::VerifyMessage as usize))
+        .subcommand(CL::build(DisplayOrder::CL as usize))
+        .subcommand(MakeGenesisAccounts::build(
+            DisplayOrder::MakeGenesisAccounts as usize,
+        ))
+        .subcommand(Config::build(DisplayOrder::Config as usize))
         .subcommand(GenerateCompletion::build(
             DisplayOrder::GenerateCompletion as usize,
         ))
@@ -121,13 +160,24 @@ fn cli<'a, 'b>() -> App<'a, 'b> {
 
 #[tokio::main]
 async fn main() {
-    let arg_matches = cli().get_matches();
+    // The config must be read before building the command line, as it sets the args' defaults.
+    if let Err(error) = config::init() {
+        exit_with_error(&error, &error_format_from_args(), 1, output::JSON);
+    }
+    // Only the command line client prompts for passwords; the library itself never does.
+    keystore::set_password_prompt(|prompt| rpassword::prompt_password(prompt));
+    let arg_matches = match cli().get_matches_safe() {
+        Ok(arg_matches) => arg_matches,
+        Err(error) => exit_with_clap_error(error),
+    };
     let (result, matches) = match arg_matches.subcommand() {
         (PutDeploy::NAME, Some(matches)) => (PutDeploy::run(matches).await, matches),
+        (PutDeploys::NAME, Some(matches)) => (PutDeploys::run(matches).await, matches),
         (MakeDeploy::NAME, Some(matches)) => (MakeDeploy::run(matches).await, matches),
         (SignDeploy::NAME, Some(matches)) => (SignDeploy::run(matches).await, matches),
         (SendDeploy::NAME, Some(matches)) => (SendDeploy::run(matches).await, matches),
         (Transfer::NAME, Some(matches)) => (Transfer::run(matches).await, matches),
+        (BatchTransfer::NAME, Some(matches)) => (BatchTransfer::run(matches).await, matches),
         (MakeTransfer::NAME, Some(matches)) => (MakeTransfer::run(matches).await, matches),
         (GetDeploy::NAME, Some(matches)) => (GetDeploy::run(matches).await, matches),
         (GetBlock::NAME, Some(matches)) => (GetBlock::run(matches).await, matches),
@@ -146,6 +196,14 @@ async fn main() {
             (GetValidatorChanges::run(matches).await, matches)
         }
         (Keygen::NAME, Some(matches)) => (Keygen::run(matches).await, matches),
+        (Key::NAME, Some(matches)) => (Key::run(matches).await, matches),
+        (SignMessage::NAME, Some(matches)) => (SignMessage::run(matches).await, matches),
+        (VerifyMessage::NAME, Some(matches)) => (VerifyMessage::run(matches).await, matches),
+        (CL::NAME, Some(matches)) => (CL::run(matches).await, matches),
+        (MakeGenesisAccounts::NAME, Some(matches)) => {
+            (MakeGenesisAccounts::run(matches).await, matches)
+        }
+        (Config::NAME, Some(matches)) => (Config::run(matches).await, matches),
         (GenerateCompletion::NAME, Some(matches)) => {
             (GenerateCompletion::run(matches).await, matches)
         }
@@ -159,7 +217,7 @@ async fn main() {
         _ => {
             let _ = cli().print_long_help();
             println!();
-            process::exit(1);
+            process::exit(ErrorCategory::InvalidArgument.exit_code());
         }
     };
 
@@ -168,18 +226,112 @@ async fn main() {
         verbosity_level += 1
     }
 
-    match &result {
-        Ok(Success::Response(response)) => {
-            casper_client::pretty_print_at_level(&response, verbosity_level)
-        }
-        Ok(Success::Output(output)) => println!("{}", output),
-        Err(Error::ResponseIsError(error)) => {
-            casper_client::pretty_print_at_level(&error, verbosity_level);
-            process::exit(1);
-        }
-        Err(error) => {
-            println!("{}", error);
-            process::exit(1);
+    let output_format = common::output_format::get(matches);
+    let query = common::query::get(matches);
+    let error_format = common::error_format::get(matches);
+    let display_units = common::display_units::get(matches);
+
+    let printed = match &result {
+        Ok(Success::Response(response)) => print_value(
+            response,
+            verbosity_level,
+            output_format,
+            query,
+            display_units,
+        ),
+        Ok(Success::Output(output)) => {
+            println!("{}", output);
+            Ok(())
         }
+        // Values of non-RPC commands are short, so are never abbreviated.
+        Ok(Success::Value(value)) => print_value(value, 2, output_format, query, display_units),
+        Err(error) => exit_with_error(error, error_format, verbosity_level, output_format),
+    };
+    if let Err(error) = printed {
+        exit_with_error(&error, error_format, verbosity_level, output_format);
+    }
+}
+
+/// Returns the value of the `--error-format` arg, read from the process's args since the command
+/// line may not have been parsed.
+fn error_format_from_args() -> String {
+    let args: Vec<String> = env::args().collect();
+    match config::raw_arg_value(&args, common::error_format::ARG_NAME) {
+        common::error_format::JSON => common::error_format::JSON.to_string(),
+        _ => common::error_format::TEXT.to_string(),
     }
 }
+
+/// Prints `error` in the given error format and exits with the exit code of its category.
+fn exit_with_error(
+    error: &Error,
+    error_format: &str,
+    verbosity_level: u64,
+    output_format: &str,
+) -> ! {
+    if error_format == common::error_format::JSON {
+        let json_error = json!({
+            "kind": error.code(),
+            "context": error.context(),
+            "message": error.to_string(),
+            "rpc_error_code": error.rpc_error_code(),
+        });
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&json_error).expect("should encode")
+        );
+    } else if let Error::ResponseIsError(rpc_error) = error {
+        // The query applies to successful responses, so isn't applied to the error.
+        let _ = print_value(rpc_error, verbosity_level, output_format, "", units::MOTES);
+    } else {
+        println!("{}", error);
+    }
+    process::exit(error.category().exit_code())
+}
+
+/// Handles a failure to parse the command line, exiting with the exit code for an invalid
+/// argument unless help or version info was requested.
+fn exit_with_clap_error(error: clap::Error) -> ! {
+    if !error.use_stderr() {
+        error.exit();
+    }
+    if error_format_from_args() != common::error_format::JSON {
+        eprintln!("{}", error.message);
+        process::exit(ErrorCategory::InvalidArgument.exit_code());
+    }
+    let message = error.message.lines().next().unwrap_or_default();
+    let error = Error::InvalidArgument {
+        context: "command_line",
+        error: message.trim_start_matches("error: ").to_string(),
+    };
+    exit_with_error(&error, common::error_format::JSON, 1, output::JSON)
+}
+
+/// Prints the fields of `value` selected by `query` in the given output format, with amounts of
+/// motes in the given units.
+///
+/// Long hex strings are abbreviated when `verbosity_level` is `1`, unless `query` selects fields.
+fn print_value<T: ?Sized + Serialize>(
+    value: &T,
+    verbosity_level: u64,
+    output_format: &str,
+    query: &str,
+    display_units: &str,
+) -> Result<(), Error> {
+    if display_units != units::MOTES {
+        let value = units::display_in_units(serde_json::to_value(value)?, display_units)?;
+        return print_value(&value, verbosity_level, output_format, query, units::MOTES);
+    }
+    if output_format == output::JSON && query.is_empty() {
+        casper_client::pretty_print_at_level(value, verbosity_level);
+        return Ok(());
+    }
+    let value = if verbosity_level == 1 && query.is_empty() {
+        output::to_abbreviated_json(value)?
+    } else {
+        serde_json::to_value(value)?
+    };
+    let selected = output::query(&value, query)?;
+    println!("{}", output::format(&selected, output_format)?);
+    Ok(())
+}
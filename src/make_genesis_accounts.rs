@@ -0,0 +1,238 @@
+use async_trait::async_trait;
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use casper_client::Error;
+
+use crate::{command::ClientCommand, common, Success};
+
+const MORE_ABOUT: &str =
+    "Writes an accounts.toml file defining the accounts, validators and delegators which exist at \
+    genesis, for use alongside a chainspec when starting a network. In each specification, KEY is \
+    a directory written by 'keygen', a public key file, or a hex-encoded public key, and amounts \
+    are in motes unless given with a unit suffix, e.g. '2.5cspr'. The accounts are checked as \
+    casper-node checks them at genesis, so at least one validator is required";
+
+/// This struct defines the order in which the args are shown for this subcommand's help message.
+enum DisplayOrder {
+    Validator,
+    Account,
+    Delegator,
+    GenerateValidators,
+    KeysDir,
+    Output,
+    Force,
+}
+
+/// Handles providing the arg for and retrieval of the genesis validators.
+mod validator {
+    use super::*;
+
+    const ARG_NAME: &str = "validator";
+    const ARG_VALUE_NAME: &str = "KEY,BALANCE,BONDED_AMOUNT[,DELEGATION_RATE]";
+    const ARG_HELP: &str =
+        "A genesis validator, bonded with the given amount. The delegation rate is a percentage \
+        from 0 to 100, defaulting to 0. This arg can be repeated to add multiple validators";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required_unless(generate_validators::ARG_NAME)
+            .multiple(true)
+            .number_of_values(1)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Validator as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> Vec<&'a str> {
+        matches
+            .values_of(ARG_NAME)
+            .map(Iterator::collect)
+            .unwrap_or_default()
+    }
+}
+
+/// Handles providing the arg for and retrieval of the funded genesis accounts.
+mod account {
+    use super::*;
+
+    const ARG_NAME: &str = "account";
+    const ARG_VALUE_NAME: &str = "KEY,BALANCE";
+    const ARG_HELP: &str =
+        "A funded account which is not a validator. This arg can be repeated to add multiple \
+        accounts";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .multiple(true)
+            .number_of_values(1)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Account as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> Vec<&'a str> {
+        matches
+            .values_of(ARG_NAME)
+            .map(Iterator::collect)
+            .unwrap_or_default()
+    }
+}
+
+/// Handles providing the arg for and retrieval of the genesis delegators.
+mod delegator {
+    use super::*;
+
+    const ARG_NAME: &str = "delegator";
+    const ARG_VALUE_NAME: &str = "KEY,VALIDATOR_KEY,BALANCE,DELEGATED_AMOUNT";
+    const ARG_HELP: &str =
+        "A funded account delegating the given amount to a genesis validator. This arg can be \
+        repeated to add multiple delegators";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .multiple(true)
+            .number_of_values(1)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Delegator as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> Vec<&'a str> {
+        matches
+            .values_of(ARG_NAME)
+            .map(Iterator::collect)
+            .unwrap_or_default()
+    }
+}
+
+/// Handles providing the arg for and retrieval of the validators to generate keys for.
+mod generate_validators {
+    use super::*;
+
+    pub(super) const ARG_NAME: &str = "generate-validators";
+    const ARG_VALUE_NAME: &str = "COUNT,BALANCE,BONDED_AMOUNT[,DELEGATION_RATE]";
+    const ARG_HELP: &str =
+        "Generates Ed25519 keys for the given number of further validators, as 'keygen --count' \
+        does, in the directory specified by --keys-dir";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .requires(keys_dir::ARG_NAME)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::GenerateValidators as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+}
+
+/// Handles providing the arg for and retrieval of the directory for generated validator keys.
+mod keys_dir {
+    use super::*;
+
+    pub(super) const ARG_NAME: &str = "keys-dir";
+    const ARG_VALUE_NAME: &str = common::ARG_PATH;
+    const ARG_HELP: &str =
+        "Path to the directory in which to write the keys of the generated validators";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .requires(generate_validators::ARG_NAME)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::KeysDir as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+}
+
+/// Handles providing the arg for and retrieval of the output file.
+mod output {
+    use super::*;
+
+    const ARG_NAME: &str = "output";
+    const ARG_SHORT: &str = "o";
+    const ARG_VALUE_NAME: &str = common::ARG_PATH;
+    const ARG_HELP: &str =
+        "Path to the accounts.toml file to write. If omitted, the file contents are written to \
+        stdout";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Output as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+}
+
+pub struct MakeGenesisAccounts;
+
+#[async_trait]
+impl<'a, 'b> ClientCommand<'a, 'b> for MakeGenesisAccounts {
+    const NAME: &'static str = "make-genesis-accounts";
+    const ABOUT: &'static str =
+        "Creates the accounts.toml file defining the genesis accounts and validators of a network";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .long_about(MORE_ABOUT)
+            .display_order(display_order)
+            .arg(validator::arg())
+            .arg(account::arg())
+            .arg(delegator::arg())
+            .arg(generate_validators::arg())
+            .arg(keys_dir::arg())
+            .arg(output::arg())
+            .arg(common::force::arg(DisplayOrder::Force as usize, false))
+    }
+
+    async fn run(matches: &ArgMatches<'a>) -> Result<Success, Error> {
+        common::output_format::ensure_not_given(Self::NAME, matches)?;
+
+        let validators = validator::get(matches);
+        let accounts = account::get(matches);
+        let delegators = delegator::get(matches);
+        let generated_validators = generate_validators::get(matches);
+        let keys_dir = keys_dir::get(matches);
+        let maybe_output_path = output::get(matches);
+        let force = common::force::get(matches);
+
+        casper_client::make_genesis_accounts(
+            &accounts,
+            &validators,
+            &delegators,
+            generated_validators,
+            keys_dir,
+            maybe_output_path,
+            force,
+        )
+        .map(|_| {
+            Success::Output(if maybe_output_path.is_empty() {
+                String::new()
+            } else {
+                format!("Wrote genesis accounts to {}", maybe_output_path)
+            })
+        })
+    }
+}
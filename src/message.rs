@@ -0,0 +1,86 @@
+mod sign;
+mod verify;
+
+use std::fs;
+
+use clap::{Arg, ArgGroup, ArgMatches};
+
+use casper_client::Error;
+
+use crate::common;
+
+pub use sign::SignMessage;
+pub use verify::VerifyMessage;
+
+/// The name of the group of args providing the message.
+const MESSAGE_GROUP: &str = "message-source";
+
+/// Returns the group requiring exactly one of the message args.
+fn message_group() -> ArgGroup<'static> {
+    ArgGroup::with_name(MESSAGE_GROUP)
+        .arg(message_text::ARG_NAME)
+        .arg(message_file::ARG_NAME)
+        .required(true)
+}
+
+/// Returns the message to be signed or verified, read from whichever of the message args was
+/// provided.
+fn get_message(matches: &ArgMatches) -> Result<Vec<u8>, Error> {
+    if let Some(message) = message_text::get(matches) {
+        return Ok(message.as_bytes().to_vec());
+    }
+    let path =
+        message_file::get(matches).unwrap_or_else(|| panic!("should have {} arg", MESSAGE_GROUP));
+    fs::read(path).map_err(|error| Error::IoError {
+        context: format!("unable to read message file at '{}'", path),
+        error,
+    })
+}
+
+/// Handles providing the arg for and retrieval of the message as a string.
+mod message_text {
+    use super::*;
+
+    pub(super) const ARG_NAME: &str = "message";
+    const ARG_SHORT: &str = "m";
+    const ARG_VALUE_NAME: &str = common::ARG_STRING;
+    const ARG_HELP: &str = "The message, as a UTF-8 string";
+
+    pub(super) fn arg(order: usize) -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(order)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> Option<&'a str> {
+        matches.value_of(ARG_NAME)
+    }
+}
+
+/// Handles providing the arg for and retrieval of the path to a file holding the message.
+mod message_file {
+    use super::*;
+
+    pub(super) const ARG_NAME: &str = "message-file";
+    const ARG_VALUE_NAME: &str = common::ARG_PATH;
+    const ARG_HELP: &str =
+        "Path to a file holding the message. The file's contents are used as-is, so may be \
+        arbitrary bytes";
+
+    pub(super) fn arg(order: usize) -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(order)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> Option<&'a str> {
+        matches.value_of(ARG_NAME)
+    }
+}
@@ -0,0 +1,54 @@
+use async_trait::async_trait;
+use clap::{App, ArgMatches, SubCommand};
+use once_cell::sync::Lazy;
+
+use casper_client::Error;
+
+use super::{message_file, message_text};
+use crate::{command::ClientCommand, common, Success};
+
+static MORE_ABOUT: Lazy<String> = Lazy::new(|| {
+    format!(
+        "{}. The message is prefixed with {:?} before signing, so that the signature can't be \
+        used to approve a deploy. The signature is printed as hex with the algorithm tag prefixed",
+        SignMessage::ABOUT,
+        String::from_utf8_lossy(casper_client::MESSAGE_PREFIX)
+    )
+});
+
+/// This struct defines the order in which the args are shown for this subcommand's help message.
+enum DisplayOrder {
+    Message,
+    MessageFile,
+    SecretKey,
+}
+
+pub struct SignMessage;
+
+#[async_trait]
+impl<'a, 'b> ClientCommand<'a, 'b> for SignMessage {
+    const NAME: &'static str = "sign-message";
+    const ABOUT: &'static str =
+        "Signs an arbitrary message with a secret key, e.g. to prove ownership of an account";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .long_about(MORE_ABOUT.as_str())
+            .display_order(display_order)
+            .arg(message_text::arg(DisplayOrder::Message as usize))
+            .arg(message_file::arg(DisplayOrder::MessageFile as usize))
+            .group(super::message_group())
+            .arg(common::secret_key::arg(DisplayOrder::SecretKey as usize))
+    }
+
+    async fn run(matches: &ArgMatches<'a>) -> Result<Success, Error> {
+        common::output_format::ensure_not_given(Self::NAME, matches)?;
+
+        let message = super::get_message(matches)?;
+        let secret_key = common::secret_key::get(matches)
+            .unwrap_or_else(|| panic!("should have {} arg", common::secret_key::ARG_NAME));
+
+        casper_client::sign_message(&message, secret_key).map(Success::Output)
+    }
+}
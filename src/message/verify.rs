@@ -0,0 +1,73 @@
+use async_trait::async_trait;
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use casper_client::Error;
+
+use super::{message_file, message_text};
+use crate::{command::ClientCommand, common, Success};
+
+/// This struct defines the order in which the args are shown for this subcommand's help message.
+enum DisplayOrder {
+    Message,
+    MessageFile,
+    Signature,
+    PublicKey,
+}
+
+/// Handles providing the arg for and retrieval of the signature.
+mod signature {
+    use super::*;
+
+    const ARG_NAME: &str = "signature";
+    const ARG_SHORT: &str = "s";
+    const ARG_VALUE_NAME: &str = common::ARG_HEX_STRING;
+    const ARG_HELP: &str =
+        "The signature produced by 'sign-message', as hex with the algorithm tag prefixed";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required(true)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Signature as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+    }
+}
+
+pub struct VerifyMessage;
+
+#[async_trait]
+impl<'a, 'b> ClientCommand<'a, 'b> for VerifyMessage {
+    const NAME: &'static str = "verify-message";
+    const ABOUT: &'static str =
+        "Verifies the signature of a message produced by 'sign-message' against a public key";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(message_text::arg(DisplayOrder::Message as usize))
+            .arg(message_file::arg(DisplayOrder::MessageFile as usize))
+            .group(super::message_group())
+            .arg(signature::arg())
+            .arg(common::public_key::arg(DisplayOrder::PublicKey as usize))
+    }
+
+    async fn run(matches: &ArgMatches<'a>) -> Result<Success, Error> {
+        common::output_format::ensure_not_given(Self::NAME, matches)?;
+
+        let message = super::get_message(matches)?;
+        let signature = signature::get(matches);
+        let public_key = common::public_key::get(matches)?;
+
+        casper_client::verify_message(&message, signature, &public_key)
+            .map(|_| Success::Output("Signature is valid".to_string()))
+    }
+}
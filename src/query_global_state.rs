@@ -79,14 +79,16 @@ mod key {
     const ARG_VALUE_NAME: &str = "FORMATTED STRING or PATH";
     const ARG_HELP: &str =
         "The base key for the query. This must be a properly formatted public key, account hash, \
-        contract address hash, URef, transfer hash, deploy-info hash,era-info number, bid, withdraw \
-        or dictionary address. The format for each respectively is \"<HEX STRING>\", \
-        \"account-hash-<HEX STRING>\", \"hash-<HEX STRING>\", \
-        \"uref-<HEX STRING>-<THREE DIGIT INTEGER>\", \"transfer-<HEX-STRING>\", \
-        \"deploy-<HEX-STRING>\", \"era-<u64>\", \"bid-<HEX-STRING>\",\
-        \"withdraw-<HEX-STRING>\" or \"dictionary-<HEX-STRING>\". \
+        contract address hash, URef, transfer hash, deploy-info hash, era-info number, purse \
+        balance, bid, withdraw or dictionary address. The format for each respectively is \
+        \"<HEX STRING>\", \"account-hash-<HEX STRING>\", \"hash-<HEX STRING>\", \
+        \"uref-<HEX STRING>-<THREE DIGIT INTEGER>\", \"transfer-<HEX STRING>\", \
+        \"deploy-<HEX STRING>\", \"era-<u64>\", \"balance-<HEX STRING>\", \
+        \"bid-<HEX STRING>\", \"withdraw-<HEX STRING>\" or \"dictionary-<HEX STRING>\". \
         The system contract registry key is unique and can only take the value: \
         system-contract-registry-0000000000000000000000000000000000000000000000000000000000000000. \
+        Era summary and chainspec registry keys are only supported by later versions of the \
+        network. To check how a key is parsed, run 'key parse'. \
         \nThe public key may instead be read in from a file, in which case \
         enter the path to the file as the --key argument. The file should be one of the two public \
         key files generated via the `keygen` subcommand; \"public_key_hex\" or \"public_key.pem\"";
@@ -1,17 +1,30 @@
-use std::{convert::Infallible, fs, net::SocketAddr, sync::Arc, time::Duration};
+use std::{
+    convert::Infallible,
+    fs, io,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
 use futures::{channel::oneshot, future};
 use hyper::{Body, Response, Server};
 use serde::Deserialize;
 use tempfile::TempDir;
-use tokio::{sync::Mutex, task::JoinHandle};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::Mutex,
+    task::JoinHandle,
+};
 use tower::builder::ServiceBuilder;
 use warp::{Filter, Rejection};
 use warp_json_rpc::Builder;
 
 use casper_client::{
-    DeployStrParams, DictionaryItemStrParams, Error, GlobalStateStrParams, PaymentStrParams,
-    SessionStrParams,
+    BatchTransferSummary, DeployLimitError, DeployStrParams, DictionaryItemStrParams, Error,
+    GlobalStateStrParams, PaymentStrParams, PutDeploysSummary, SessionStrParams,
 };
 use casper_node::{
     crypto::Error as CryptoError,
@@ -60,9 +73,6 @@ struct MockServerHandle {
     address: SocketAddr,
 }
 
-trait Captures<'a> {}
-impl<'a, T: ?Sized> Captures<'a> for T {}
-
 impl MockServerHandle {
     fn url(&self) -> String {
         format!("http://{}", self.address)
@@ -214,13 +224,59 @@ impl MockServerHandle {
             deploy_params,
             session_params,
             payment_params,
+            false,
         )
         .await
         .map(|_| ())
     }
 
+    async fn put_deploys(
+        &self,
+        manifest_path: &str,
+        results_path: &str,
+        resume: bool,
+        force: bool,
+    ) -> Result<PutDeploysSummary, Error> {
+        casper_client::put_deploys(
+            &self.url(),
+            0,
+            manifest_path,
+            results_path,
+            2,
+            resume,
+            force,
+            deploy_params::test_data_valid(),
+        )
+        .await
+    }
+
+    async fn batch_transfer(
+        &self,
+        transfers_path: &str,
+        report_path: &str,
+        completion_timeout: &str,
+        dry_run_dir: &str,
+        resume: bool,
+    ) -> Result<BatchTransferSummary, Error> {
+        casper_client::batch_transfer(
+            &self.url(),
+            0,
+            transfers_path,
+            report_path,
+            1,
+            completion_timeout,
+            dry_run_dir,
+            "",
+            resume,
+            false,
+            deploy_params::test_data_valid(),
+            payment_params::test_data_with_name(),
+        )
+        .await
+    }
+
     async fn send_deploy_file(&self, input_path: &str) -> Result<(), Error> {
-        casper_client::send_deploy_file("1", &self.url(), 0, input_path)
+        casper_client::send_deploy_file("1", &self.url(), 0, input_path, "")
             .await
             .map(|_| ())
     }
@@ -249,6 +305,79 @@ impl Drop for MockServerHandle {
     }
 }
 
+/// A mock node which reads each request in full, as if accepting it, then drops the connection
+/// without responding.
+struct DroppingServer {
+    address: SocketAddr,
+    requests: Arc<AtomicUsize>,
+    acceptor: JoinHandle<()>,
+}
+
+impl DroppingServer {
+    async fn spawn() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&requests);
+        let acceptor = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                if Self::read_request(&stream).await {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                }
+            }
+        });
+        DroppingServer {
+            address,
+            requests,
+            acceptor,
+        }
+    }
+
+    fn url(&self) -> String {
+        format!("http://{}", self.address)
+    }
+
+    /// Returns the number of requests read in full.
+    fn requests(&self) -> usize {
+        self.requests.load(Ordering::SeqCst)
+    }
+
+    /// Reads an HTTP request from `stream`, returning whether its whole body was read.
+    async fn read_request(stream: &TcpStream) -> bool {
+        let mut request = vec![];
+        let mut buffer = [0; 4096];
+        loop {
+            if stream.readable().await.is_err() {
+                return false;
+            }
+            match stream.try_read(&mut buffer) {
+                Ok(0) => return false,
+                Ok(count) => request.extend_from_slice(&buffer[..count]),
+                Err(error) if error.kind() == io::ErrorKind::WouldBlock => continue,
+                Err(_) => return false,
+            }
+            let header_end = match request.windows(4).position(|window| window == b"\r\n\r\n") {
+                Some(index) => index + 4,
+                None => continue,
+            };
+            let content_length = String::from_utf8_lossy(&request[..header_end])
+                .to_lowercase()
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length:")?.trim().parse().ok())
+                .unwrap_or(0);
+            if request.len() >= header_end + content_length {
+                return true;
+            }
+        }
+    }
+}
+
+impl Drop for DroppingServer {
+    fn drop(&mut self) {
+        self.acceptor.abort();
+    }
+}
+
 mod deploy_params {
     use super::*;
 
@@ -864,6 +993,7 @@ mod make_deploy {
     fn should_succeed_for_stdout() {
         assert!(matches!(
             casper_client::make_deploy(
+                "",
                 "",
                 deploy_params::test_data_valid(),
                 session_params::test_data_with_package_hash(),
@@ -883,6 +1013,7 @@ mod make_deploy {
         assert!(matches!(
             casper_client::make_deploy(
                 file_path.to_str().unwrap(),
+                "",
                 deploy_params::test_data_valid(),
                 session_params::test_data_with_package_hash(),
                 payment_params::test_data_with_name(),
@@ -898,12 +1029,13 @@ mod make_deploy {
             .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
         let file_path = temp_dir.path().join("test_deploy.json");
         let contents = "contents of test file";
-        fs::write(file_path.clone(), &contents)
+        fs::write(file_path.clone(), contents)
             .unwrap_or_else(|err| panic!("Failed to create temp file with error: {}", err));
 
         assert!(matches!(
             casper_client::make_deploy(
                 file_path.to_str().unwrap(),
+                "",
                 deploy_params::test_data_valid(),
                 session_params::test_data_with_package_hash(),
                 payment_params::test_data_with_name(),
@@ -930,6 +1062,7 @@ mod make_deploy {
         assert!(matches!(
             casper_client::make_deploy(
                 file_path.to_str().unwrap(),
+                "",
                 deploy_params::test_data_valid(),
                 session_params::test_data_with_package_hash(),
                 payment_params::test_data_with_name(),
@@ -940,6 +1073,269 @@ mod make_deploy {
     }
 }
 
+mod make_deploy_from_template {
+    use super::*;
+
+    const TEMPLATE: &str = r#"
+[deploy]
+chain_name = "casper-test-chain-name-1"
+ttl = "1h"
+
+[session]
+package_hash = "09dcee4b212cfd53642ab323fbef07dafafc6f945a80a00147f62910a915c4e6"
+version = "0.1.0"
+entry_point = "${entry_point}"
+args = ["name_01:bool='false'", "name_02:i32='${value}'"]
+
+[payment]
+amount = "${fee}"
+"#;
+
+    fn write_template(temp_dir: &TempDir) -> String {
+        let path = temp_dir.path().join("template.toml");
+        fs::write(&path, TEMPLATE).unwrap();
+        path.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn should_fill_in_template() {
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
+        let template_path = write_template(&temp_dir);
+        let file_path = temp_dir.path().join("test_deploy.json");
+
+        casper_client::make_deploy_from_template(
+            file_path.to_str().unwrap(),
+            "",
+            &template_path,
+            vec!["entry_point=entrypoint", "value=42", "fee=100"],
+            DeployStrParams {
+                secret_key: "resources/test.pem",
+                gas_price: "2",
+                ..Default::default()
+            },
+            false,
+        )
+        .unwrap();
+
+        let deploy: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(file_path).unwrap()).unwrap();
+        assert_eq!(deploy["header"]["ttl"], "1h");
+        assert_eq!(deploy["header"]["gas_price"], 2);
+        assert_eq!(deploy["header"]["chain_name"], "casper-test-chain-name-1");
+        let session = &deploy["session"]["StoredVersionedContractByHash"];
+        assert_eq!(session["entry_point"], "entrypoint");
+        assert_eq!(session["args"][1][1]["parsed"], 42);
+        assert_eq!(
+            deploy["payment"]["ModuleBytes"]["args"][0][1]["parsed"],
+            "100"
+        );
+    }
+
+    #[test]
+    fn should_fail_with_missing_variable() {
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
+        let template_path = write_template(&temp_dir);
+
+        assert!(matches!(
+            casper_client::make_deploy_from_template(
+                "",
+                "",
+                &template_path,
+                vec!["entry_point=entrypoint", "value=42"],
+                deploy_params::test_data_valid(),
+                false,
+            ),
+            Err(Error::FailedToParseTemplate { .. })
+        ));
+    }
+
+    #[test]
+    fn should_fail_with_invalid_substituted_arg() {
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
+        let template_path = write_template(&temp_dir);
+
+        assert!(matches!(
+            casper_client::make_deploy_from_template(
+                "",
+                "",
+                &template_path,
+                vec!["entry_point=entrypoint", "value=not an i32", "fee=100"],
+                deploy_params::test_data_valid(),
+                false,
+            ),
+            Err(Error::InvalidCLValue(_))
+        ));
+    }
+}
+
+mod deploy_limits {
+    use super::*;
+
+    const CHAINSPEC: &str = r#"
+[deploys]
+max_ttl = '5s'
+max_dependencies = 10
+max_deploy_size = 1_048_576
+block_gas_limit = 10_000_000_000_000
+payment_args_max_length = 1024
+"#;
+
+    #[test]
+    fn should_succeed_with_preset() {
+        let mut deploy_params = deploy_params::test_data_valid();
+        deploy_params.chainspec = "mainnet";
+        assert!(matches!(
+            casper_client::make_deploy(
+                "",
+                "",
+                deploy_params,
+                session_params::test_data_with_package_hash(),
+                payment_params::test_data_with_name(),
+                false
+            ),
+            Ok(())
+        ));
+    }
+
+    #[test]
+    fn should_fail_with_unknown_preset() {
+        let mut deploy_params = deploy_params::test_data_valid();
+        deploy_params.chainspec = "<not a preset or path>";
+        assert!(matches!(
+            casper_client::make_deploy(
+                "",
+                "",
+                deploy_params,
+                session_params::test_data_with_package_hash(),
+                payment_params::test_data_with_name(),
+                false
+            ),
+            Err(Error::IoError { .. })
+        ));
+    }
+
+    #[test]
+    fn should_check_gas_bought_by_payment_against_block_gas_limit() {
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
+        let chainspec_path = temp_dir.path().join("chainspec.toml");
+        let chainspec = CHAINSPEC
+            .replace("'5s'", "'1day'")
+            .replace("10_000_000_000_000", "1_000");
+        fs::write(&chainspec_path, chainspec)
+            .unwrap_or_else(|err| panic!("Failed to create temp file with error: {}", err));
+
+        let make_deploy = |gas_price| {
+            let mut deploy_params = deploy_params::test_data_valid();
+            deploy_params.chainspec = chainspec_path.to_str().unwrap();
+            deploy_params.gas_price = gas_price;
+            casper_client::make_deploy(
+                "",
+                "",
+                deploy_params,
+                session_params::test_data_with_package_hash(),
+                PaymentStrParams::with_amount("2000"),
+                false,
+            )
+        };
+        assert!(matches!(make_deploy("2"), Ok(())));
+        assert!(matches!(
+            make_deploy("1"),
+            Err(Error::DeployLimitExceeded(
+                DeployLimitError::ExceededBlockGasLimit {
+                    block_gas_limit: 1_000,
+                    gas_price: 1,
+                    ..
+                }
+            ))
+        ));
+    }
+
+    #[test]
+    fn should_fail_with_ttl_exceeding_chainspec_limit() {
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
+        let chainspec_path = temp_dir.path().join("chainspec.toml");
+        fs::write(&chainspec_path, CHAINSPEC)
+            .unwrap_or_else(|err| panic!("Failed to create temp file with error: {}", err));
+
+        let mut deploy_params = deploy_params::test_data_valid();
+        deploy_params.chainspec = chainspec_path.to_str().unwrap();
+        assert!(matches!(
+            casper_client::make_deploy(
+                "",
+                "",
+                deploy_params,
+                session_params::test_data_with_package_hash(),
+                payment_params::test_data_with_name(),
+                false
+            ),
+            Err(Error::DeployLimitExceeded(
+                DeployLimitError::ExcessiveTimeToLive { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn should_fail_to_sign_deploy_exceeding_chainspec_limit() {
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
+        let chainspec_path = temp_dir.path().join("chainspec.toml");
+        fs::write(&chainspec_path, CHAINSPEC)
+            .unwrap_or_else(|err| panic!("Failed to create temp file with error: {}", err));
+        let unsigned_file_path = temp_dir.path().join("test_deploy.json");
+        assert!(matches!(
+            casper_client::make_deploy(
+                unsigned_file_path.to_str().unwrap(),
+                "",
+                deploy_params::test_data_valid(),
+                session_params::test_data_with_package_hash(),
+                payment_params::test_data_with_name(),
+                false
+            ),
+            Ok(())
+        ));
+        assert!(matches!(
+            casper_client::sign_deploy_file(
+                unsigned_file_path.to_str().unwrap(),
+                "resources/test.pem",
+                "",
+                "",
+                "",
+                chainspec_path.to_str().unwrap(),
+                false
+            ),
+            Err(Error::DeployLimitExceeded(_))
+        ));
+    }
+
+    #[test]
+    fn should_fail_with_invalid_chainspec() {
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
+        let chainspec_path = temp_dir.path().join("chainspec.toml");
+        fs::write(&chainspec_path, "[deploys]\nmax_ttl = 'not a duration'")
+            .unwrap_or_else(|err| panic!("Failed to create temp file with error: {}", err));
+
+        let mut deploy_params = deploy_params::test_data_valid();
+        deploy_params.chainspec = chainspec_path.to_str().unwrap();
+        assert!(matches!(
+            casper_client::make_deploy(
+                "",
+                "",
+                deploy_params,
+                session_params::test_data_with_package_hash(),
+                payment_params::test_data_with_name(),
+                false
+            ),
+            Err(Error::FailedToParseChainspec { .. })
+        ));
+    }
+}
+
 mod send_deploy {
     use super::*;
 
@@ -961,6 +1357,32 @@ mod send_deploy {
         assert!(matches!(
             casper_client::make_deploy(
                 file_path.to_str().unwrap(),
+                "",
+                deploy_params::test_data_valid(),
+                session_params::test_data_with_package_hash(),
+                payment_params::test_data_with_name(),
+                false
+            ),
+            Ok(())
+        ));
+        let server_handle = MockServerHandle::spawn::<PutDeployParams>(PutDeploy::METHOD);
+        assert!(matches!(
+            server_handle
+                .send_deploy_file(file_path.to_str().unwrap())
+                .await,
+            Ok(())
+        ));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_succeed_for_binary_file() {
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
+        let file_path = temp_dir.path().join("test_send_deploy.bin");
+        assert!(matches!(
+            casper_client::make_deploy(
+                file_path.to_str().unwrap(),
+                "binary",
                 deploy_params::test_data_valid(),
                 session_params::test_data_with_package_hash(),
                 payment_params::test_data_with_name(),
@@ -990,6 +1412,7 @@ mod sign_deploy {
         assert!(matches!(
             casper_client::make_deploy(
                 unsigned_file_path.to_str().unwrap(),
+                "",
                 deploy_params::test_data_valid(),
                 session_params::test_data_with_package_hash(),
                 payment_params::test_data_with_name(),
@@ -1001,7 +1424,10 @@ mod sign_deploy {
             casper_client::sign_deploy_file(
                 unsigned_file_path.to_str().unwrap(),
                 "resources/test.pem",
+                "",
                 signed_file_path.to_str().unwrap(),
+                "",
+                "",
                 false
             ),
             Ok(())
@@ -1009,13 +1435,15 @@ mod sign_deploy {
     }
 
     #[test]
-    fn should_succeed_for_stdout() {
+    fn should_succeed_converting_base64_to_json() {
         let temp_dir = TempDir::new()
             .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
-        let unsigned_file_path = temp_dir.path().join("test_deploy.json");
+        let unsigned_file_path = temp_dir.path().join("test_deploy.b64");
+        let signed_file_path = temp_dir.path().join("signed_test_deploy.json");
         assert!(matches!(
             casper_client::make_deploy(
                 unsigned_file_path.to_str().unwrap(),
+                "base64",
                 deploy_params::test_data_valid(),
                 session_params::test_data_with_package_hash(),
                 payment_params::test_data_with_name(),
@@ -1028,45 +1456,45 @@ mod sign_deploy {
                 unsigned_file_path.to_str().unwrap(),
                 "resources/test.pem",
                 "",
+                signed_file_path.to_str().unwrap(),
+                "json",
+                "",
                 false
             ),
             Ok(())
         ));
+        let contents = fs::read_to_string(signed_file_path)
+            .unwrap_or_else(|err| panic!("Failed to read contents of file with error: {}", err));
+        assert!(contents.starts_with('{'));
     }
 
     #[test]
-    fn should_fail_with_bad_secret_key_path() {
+    fn should_fail_with_invalid_format() {
         let temp_dir = TempDir::new()
             .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
-        let unsigned_file_path = temp_dir.path().join("test_deploy.json");
+        let file_path = temp_dir.path().join("test_deploy.json");
         assert!(matches!(
             casper_client::make_deploy(
-                unsigned_file_path.to_str().unwrap(),
+                file_path.to_str().unwrap(),
+                "yaml",
                 deploy_params::test_data_valid(),
                 session_params::test_data_with_package_hash(),
                 payment_params::test_data_with_name(),
                 false
             ),
-            Ok(())
+            Err(Error::InvalidArgument { .. })
         ));
-        assert!(casper_client::sign_deploy_file(
-            unsigned_file_path.to_str().unwrap(),
-            "<this is not a path>",
-            "",
-            false
-        )
-        .is_err());
     }
 
     #[test]
-    fn should_fail_with_existing_file() {
+    fn should_succeed_for_stdout() {
         let temp_dir = TempDir::new()
             .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
         let unsigned_file_path = temp_dir.path().join("test_deploy.json");
-        let signed_file_path = temp_dir.path().join("signed_test_deploy.json");
         assert!(matches!(
             casper_client::make_deploy(
                 unsigned_file_path.to_str().unwrap(),
+                "",
                 deploy_params::test_data_valid(),
                 session_params::test_data_with_package_hash(),
                 payment_params::test_data_with_name(),
@@ -1078,20 +1506,85 @@ mod sign_deploy {
             casper_client::sign_deploy_file(
                 unsigned_file_path.to_str().unwrap(),
                 "resources/test.pem",
-                signed_file_path.to_str().unwrap(),
+                "",
+                "",
+                "",
+                "",
                 false
             ),
             Ok(())
         ));
+    }
 
-        let contents = fs::read_to_string(signed_file_path.clone())
-            .unwrap_or_else(|err| panic!("Failed to read contents of file with error: {}", err));
-
-        assert!(matches!(
-            casper_client::sign_deploy_file(
-                unsigned_file_path.to_str().unwrap(),
-                "resources/test.pem",
+    #[test]
+    fn should_fail_with_bad_secret_key_path() {
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
+        let unsigned_file_path = temp_dir.path().join("test_deploy.json");
+        assert!(matches!(
+            casper_client::make_deploy(
+                unsigned_file_path.to_str().unwrap(),
+                "",
+                deploy_params::test_data_valid(),
+                session_params::test_data_with_package_hash(),
+                payment_params::test_data_with_name(),
+                false
+            ),
+            Ok(())
+        ));
+        assert!(casper_client::sign_deploy_file(
+            unsigned_file_path.to_str().unwrap(),
+            "<this is not a path>",
+            "",
+            "",
+            "",
+            "",
+            false
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn should_fail_with_existing_file() {
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
+        let unsigned_file_path = temp_dir.path().join("test_deploy.json");
+        let signed_file_path = temp_dir.path().join("signed_test_deploy.json");
+        assert!(matches!(
+            casper_client::make_deploy(
+                unsigned_file_path.to_str().unwrap(),
+                "",
+                deploy_params::test_data_valid(),
+                session_params::test_data_with_package_hash(),
+                payment_params::test_data_with_name(),
+                false
+            ),
+            Ok(())
+        ));
+        assert!(matches!(
+            casper_client::sign_deploy_file(
+                unsigned_file_path.to_str().unwrap(),
+                "resources/test.pem",
+                "",
+                signed_file_path.to_str().unwrap(),
+                "",
+                "",
+                false
+            ),
+            Ok(())
+        ));
+
+        let contents = fs::read_to_string(signed_file_path.clone())
+            .unwrap_or_else(|err| panic!("Failed to read contents of file with error: {}", err));
+
+        assert!(matches!(
+            casper_client::sign_deploy_file(
+                unsigned_file_path.to_str().unwrap(),
+                "resources/test.pem",
+                "",
                 signed_file_path.to_str().unwrap(),
+                "",
+                "",
                 false
             ),
             Err(Error::FileAlreadyExists(_))
@@ -1112,6 +1605,7 @@ mod sign_deploy {
         assert!(matches!(
             casper_client::make_deploy(
                 unsigned_file_path.to_str().unwrap(),
+                "",
                 deploy_params::test_data_valid(),
                 session_params::test_data_with_package_hash(),
                 payment_params::test_data_with_name(),
@@ -1123,7 +1617,10 @@ mod sign_deploy {
             casper_client::sign_deploy_file(
                 unsigned_file_path.to_str().unwrap(),
                 "resources/test.pem",
+                "",
                 signed_file_path.to_str().unwrap(),
+                "",
+                "",
                 false
             ),
             Ok(())
@@ -1132,12 +1629,114 @@ mod sign_deploy {
             casper_client::sign_deploy_file(
                 unsigned_file_path.to_str().unwrap(),
                 "resources/test.pem",
+                "",
                 signed_file_path.to_str().unwrap(),
+                "",
+                "",
                 true
             ),
             Ok(())
         ));
     }
+
+    #[test]
+    fn should_succeed_with_pem_signer() {
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
+        let unsigned_file_path = temp_dir.path().join("test_deploy.json");
+        let signed_file_path = temp_dir.path().join("signed_test_deploy.json");
+        casper_client::make_deploy(
+            unsigned_file_path.to_str().unwrap(),
+            "",
+            deploy_params::test_data_valid(),
+            session_params::test_data_with_package_hash(),
+            payment_params::test_data_with_name(),
+            false,
+        )
+        .unwrap();
+        assert!(matches!(
+            casper_client::sign_deploy_file(
+                unsigned_file_path.to_str().unwrap(),
+                "",
+                "pem:resources/test.pem",
+                signed_file_path.to_str().unwrap(),
+                "",
+                "",
+                false
+            ),
+            Ok(())
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn should_fail_if_exec_signer_refuses() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
+        let unsigned_file_path = temp_dir.path().join("test_deploy.json");
+        let signed_file_path = temp_dir.path().join("signed_test_deploy.json");
+        let signer_path = temp_dir.path().join("signer.sh");
+        fs::write(
+            &signer_path,
+            "#!/bin/sh\ncat > /dev/null\necho '{\"error\": \"refused\"}'\n",
+        )
+        .unwrap();
+        fs::set_permissions(&signer_path, fs::Permissions::from_mode(0o755)).unwrap();
+
+        casper_client::make_deploy(
+            unsigned_file_path.to_str().unwrap(),
+            "",
+            deploy_params::test_data_valid(),
+            session_params::test_data_with_package_hash(),
+            payment_params::test_data_with_name(),
+            false,
+        )
+        .unwrap();
+        let signer = format!("exec:{}", signer_path.display());
+        assert!(matches!(
+            casper_client::sign_deploy_file(
+                unsigned_file_path.to_str().unwrap(),
+                "",
+                &signer,
+                signed_file_path.to_str().unwrap(),
+                "",
+                "",
+                false
+            ),
+            Err(Error::SignerError { error, .. }) if error == "refused"
+        ));
+        assert!(!signed_file_path.exists());
+    }
+
+    #[test]
+    fn should_fail_with_missing_exec_signer() {
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
+        let unsigned_file_path = temp_dir.path().join("test_deploy.json");
+        casper_client::make_deploy(
+            unsigned_file_path.to_str().unwrap(),
+            "",
+            deploy_params::test_data_valid(),
+            session_params::test_data_with_package_hash(),
+            payment_params::test_data_with_name(),
+            false,
+        )
+        .unwrap();
+        assert!(matches!(
+            casper_client::sign_deploy_file(
+                unsigned_file_path.to_str().unwrap(),
+                "",
+                "exec:/nonexistent/signer",
+                "",
+                "",
+                "",
+                false
+            ),
+            Err(Error::SignerError { .. })
+        ));
+    }
 }
 
 mod make_transfer {
@@ -1152,6 +1751,7 @@ mod make_transfer {
     fn should_succeed_for_stdout() {
         assert!(matches!(
             casper_client::make_transfer(
+                "",
                 "",
                 AMOUNT,
                 TARGET_ACCOUNT,
@@ -1172,6 +1772,7 @@ mod make_transfer {
         assert!(matches!(
             casper_client::make_transfer(
                 file_path.to_str().unwrap(),
+                "",
                 AMOUNT,
                 TARGET_ACCOUNT,
                 TRANSFER_ID,
@@ -1189,12 +1790,13 @@ mod make_transfer {
             .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
         let file_path = temp_dir.path().join("test_deploy.json");
         let contents = "contents of test file";
-        fs::write(file_path.clone(), &contents)
+        fs::write(file_path.clone(), contents)
             .unwrap_or_else(|err| panic!("Failed to create temp file with error: {}", err));
 
         assert!(matches!(
             casper_client::make_transfer(
                 file_path.to_str().unwrap(),
+                "",
                 AMOUNT,
                 TARGET_ACCOUNT,
                 TRANSFER_ID,
@@ -1222,6 +1824,7 @@ mod make_transfer {
         assert!(matches!(
             casper_client::make_transfer(
                 file_path.to_str().unwrap(),
+                "",
                 AMOUNT,
                 TARGET_ACCOUNT,
                 TRANSFER_ID,
@@ -1232,6 +1835,50 @@ mod make_transfer {
             Ok(())
         ));
     }
+
+    #[test]
+    fn should_accept_amount_in_cspr() {
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
+        let file_path = temp_dir.path().join("test_deploy.json");
+        casper_client::make_transfer(
+            file_path.to_str().unwrap(),
+            "",
+            "30 CSPR",
+            TARGET_ACCOUNT,
+            TRANSFER_ID,
+            deploy_params::test_data_valid(),
+            payment_params::test_data_with_name(),
+            false,
+        )
+        .unwrap_or_else(|err| panic!("Failed to make transfer with error: {}", err));
+
+        let deploy: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(file_path).unwrap()).unwrap();
+        let args = &deploy["session"]["Transfer"]["args"];
+        assert_eq!(args[0][0], "amount");
+        assert_eq!(args[0][1]["parsed"], AMOUNT);
+    }
+
+    #[test]
+    fn should_fail_with_fraction_of_a_mote() {
+        assert!(matches!(
+            casper_client::make_transfer(
+                "",
+                "",
+                "0.0000000001cspr",
+                TARGET_ACCOUNT,
+                TRANSFER_ID,
+                deploy_params::test_data_valid(),
+                payment_params::test_data_with_name(),
+                false
+            ),
+            Err(Error::InvalidArgument {
+                context: "amount",
+                ..
+            })
+        ));
+    }
 }
 
 mod keygen_generate_files {
@@ -1313,14 +1960,630 @@ mod keygen_generate_files {
             casper_client::keygen::generate_files(path, casper_client::keygen::ED25519, true);
         assert!(matches!(result, Err(Error::InvalidArgument { .. })))
     }
+
+    #[test]
+    fn should_succeed_for_encrypted_files() {
+        use casper_types::{AsymmetricType, PublicKey};
+
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create a temp dir with error: {}", err));
+        let path = temp_dir.path().join("test-keygen-encrypted");
+        let result = casper_client::keygen::generate_encrypted_files(
+            path.to_str().unwrap(),
+            casper_client::keygen::ED25519,
+            "hunter2",
+            false,
+        );
+        assert!(matches!(result, Ok(())));
+        assert!(!path.join(casper_client::keygen::SECRET_KEY_PEM).exists());
+
+        let keystore =
+            fs::read_to_string(path.join(casper_client::keygen::SECRET_KEY_JSON)).unwrap();
+        let secret_key = casper_client::keystore::decrypt(&keystore, "hunter2").unwrap();
+        let public_key_hex =
+            fs::read_to_string(path.join(casper_client::keygen::PUBLIC_KEY_HEX)).unwrap();
+        assert_eq!(PublicKey::from(&secret_key).to_hex(), public_key_hex);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn should_write_secret_key_files_readable_only_by_owner() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create a temp dir with error: {}", err));
+        let mode =
+            |path: &std::path::Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+
+        let plain_dir = temp_dir.path().join("plain");
+        let encrypted_dir = temp_dir.path().join("encrypted");
+        casper_client::keygen::generate_files(
+            plain_dir.to_str().unwrap(),
+            casper_client::keygen::ED25519,
+            false,
+        )
+        .unwrap();
+        casper_client::keygen::generate_encrypted_files(
+            encrypted_dir.to_str().unwrap(),
+            casper_client::keygen::ED25519,
+            "hunter2",
+            false,
+        )
+        .unwrap();
+        let secret_key_pem = plain_dir.join(casper_client::keygen::SECRET_KEY_PEM);
+        let secret_key_json = encrypted_dir.join(casper_client::keygen::SECRET_KEY_JSON);
+        assert_eq!(mode(&secret_key_pem), 0o600);
+        assert_eq!(mode(&secret_key_json), 0o600);
+
+        // Existing outputs with wider permissions are overwritten as private files too.
+        let encrypted_path = temp_dir.path().join("encrypted.json");
+        let decrypted_path = temp_dir.path().join("decrypted.pem");
+        fs::write(&decrypted_path, "").unwrap();
+        fs::set_permissions(&decrypted_path, fs::Permissions::from_mode(0o644)).unwrap();
+        casper_client::keystore::encrypt_file(
+            secret_key_pem.to_str().unwrap(),
+            encrypted_path.to_str().unwrap(),
+            "hunter2",
+            false,
+        )
+        .unwrap();
+        casper_client::keystore::decrypt_file(
+            encrypted_path.to_str().unwrap(),
+            decrypted_path.to_str().unwrap(),
+            "hunter2",
+            true,
+        )
+        .unwrap();
+        assert_eq!(mode(&encrypted_path), 0o600);
+        assert_eq!(mode(&decrypted_path), 0o600);
+    }
+
+    #[test]
+    fn should_restore_same_keys_from_mnemonic() {
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create a temp dir with error: {}", err));
+        let mnemonic = casper_client::keygen::generate_mnemonic(12).unwrap();
+        for algorithm in [
+            casper_client::keygen::ED25519,
+            casper_client::keygen::SECP256K1,
+        ] {
+            let public_keys: Vec<String> = ["first", "second"]
+                .iter()
+                .map(|dir| {
+                    let path = temp_dir.path().join(algorithm).join(dir);
+                    casper_client::keygen::generate_files_from_mnemonic(
+                        path.to_str().unwrap(),
+                        algorithm,
+                        &mnemonic,
+                        "",
+                        "",
+                        false,
+                    )
+                    .unwrap();
+                    fs::read_to_string(path.join(casper_client::keygen::PUBLIC_KEY_HEX)).unwrap()
+                })
+                .collect();
+            assert_eq!(public_keys[0], public_keys[1]);
+        }
+    }
+
+    #[test]
+    fn should_fail_for_invalid_mnemonic() {
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create a temp dir with error: {}", err));
+        let result = casper_client::keygen::generate_files_from_mnemonic(
+            temp_dir.path().to_str().unwrap(),
+            casper_client::keygen::SECP256K1,
+            "not a valid mnemonic",
+            "",
+            "",
+            false,
+        );
+        assert!(matches!(
+            result,
+            Err(Error::InvalidArgument {
+                context: "mnemonic",
+                ..
+            })
+        ));
+        assert!(!temp_dir
+            .path()
+            .join(casper_client::keygen::PUBLIC_KEY_HEX)
+            .exists());
+    }
+
+    #[test]
+    fn should_generate_numbered_files_with_index() {
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create a temp dir with error: {}", err));
+        let output_dir = temp_dir.path().to_str().unwrap();
+        casper_client::keygen::generate_numbered_files(
+            output_dir,
+            casper_client::keygen::ED25519,
+            3,
+            "",
+            false,
+        )
+        .unwrap();
+
+        let index =
+            fs::read_to_string(temp_dir.path().join(casper_client::keygen::INDEX_CSV)).unwrap();
+        let lines: Vec<&str> = index.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "directory,public_key,account_hash");
+        for (line, dir) in lines[1..].iter().zip(["001", "002", "003"]) {
+            let public_key_hex = fs::read_to_string(
+                temp_dir
+                    .path()
+                    .join(dir)
+                    .join(casper_client::keygen::PUBLIC_KEY_HEX),
+            )
+            .unwrap();
+            assert!(line.starts_with(&format!("{},{},account-hash-", dir, public_key_hex)));
+        }
+
+        assert!(matches!(
+            casper_client::keygen::generate_numbered_files(
+                output_dir,
+                casper_client::keygen::ED25519,
+                3,
+                "",
+                false,
+            ),
+            Err(Error::FileAlreadyExists(_))
+        ));
+    }
+
+    #[test]
+    fn should_generate_vanity_files() {
+        use casper_types::AsymmetricType;
+
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create a temp dir with error: {}", err));
+        let public_key = casper_client::keygen::generate_vanity_files(
+            temp_dir.path().to_str().unwrap(),
+            casper_client::keygen::ED25519,
+            "C",
+            casper_client::keygen::VanityTarget::PublicKey,
+            "",
+            false,
+            |_| {},
+        )
+        .unwrap();
+        assert!(public_key.to_hex().starts_with("01c"));
+        let public_key_hex =
+            fs::read_to_string(temp_dir.path().join(casper_client::keygen::PUBLIC_KEY_HEX))
+                .unwrap();
+        assert_eq!(public_key_hex, public_key.to_hex());
+    }
 }
 
-mod put_deploy {
+mod keystore {
+    use std::path::Path;
+
+    use casper_node::crypto::AsymmetricKeyExt;
+    use casper_types::{PublicKey, SecretKey};
+
     use super::*;
 
-    #[tokio::test(flavor = "multi_thread")]
-    async fn should_send_put_deploy() {
-        let server_handle = MockServerHandle::spawn::<PutDeployParams>(PutDeploy::METHOD);
+    #[test]
+    fn should_round_trip_secret_key_file() {
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create a temp dir with error: {}", err));
+        let keystore_path = temp_dir.path().join("secret_key.json");
+        let keystore_path = keystore_path.to_str().unwrap();
+        let pem_path = temp_dir.path().join("secret_key.pem");
+        let pem_path = pem_path.to_str().unwrap();
+
+        casper_client::keystore::encrypt_file(
+            "resources/test.pem",
+            keystore_path,
+            "hunter2",
+            false,
+        )
+        .unwrap();
+        assert!(matches!(
+            casper_client::keystore::encrypt_file(
+                "resources/test.pem",
+                keystore_path,
+                "hunter2",
+                false
+            ),
+            Err(Error::FileAlreadyExists(_))
+        ));
+        assert!(matches!(
+            casper_client::keystore::decrypt_file(keystore_path, pem_path, "hunter3", false),
+            Err(Error::KeystoreError { .. })
+        ));
+        assert!(!Path::new(pem_path).exists());
+
+        casper_client::keystore::decrypt_file(keystore_path, pem_path, "hunter2", false).unwrap();
+        assert_eq!(
+            PublicKey::from(&SecretKey::from_file(pem_path).unwrap()),
+            PublicKey::from(&SecretKey::from_file("resources/test.pem").unwrap())
+        );
+    }
+}
+
+mod key_format {
+    use std::fs;
+
+    use casper_node::crypto::AsymmetricKeyExt;
+    use casper_types::{AsymmetricType, PublicKey, SecretKey};
+
+    use super::*;
+
+    #[test]
+    fn should_show_key_from_any_format() {
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create a temp dir with error: {}", err));
+        let public_key =
+            PublicKey::from(&SecretKey::from_file("resources/test.pem").unwrap()).to_hex();
+
+        for format in casper_client::key_format::FORMATS {
+            let output = temp_dir.path().join(format);
+            let output = output.to_str().unwrap();
+            casper_client::key_format::convert("resources/test.pem", format, false, output, false)
+                .unwrap();
+            let info = casper_client::key_format::show(output, true).unwrap();
+            assert!(info.is_secret_key);
+            assert_eq!(info.public_key, public_key);
+        }
+
+        let info = casper_client::key_format::show(&public_key, false).unwrap();
+        assert!(!info.is_secret_key);
+        assert_eq!(
+            info.public_key_checksummed.to_lowercase(),
+            info.public_key.to_lowercase()
+        );
+        assert!(info.account_hash.starts_with("account-hash-"));
+    }
+
+    #[test]
+    fn should_generate_public_key_files_from_secret_key() {
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create a temp dir with error: {}", err));
+        let output_dir = temp_dir.path().to_str().unwrap();
+
+        casper_client::keygen::generate_public_key_files("resources/test.pem", output_dir, false)
+            .unwrap();
+        let public_key = PublicKey::from(&SecretKey::from_file("resources/test.pem").unwrap());
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join(casper_client::keygen::PUBLIC_KEY_HEX))
+                .unwrap(),
+            public_key.to_hex()
+        );
+        assert_eq!(
+            PublicKey::from_file(temp_dir.path().join(casper_client::keygen::PUBLIC_KEY_PEM))
+                .unwrap(),
+            public_key
+        );
+        assert!(matches!(
+            casper_client::keygen::generate_public_key_files(
+                "resources/test.pem",
+                output_dir,
+                false
+            ),
+            Err(Error::FileAlreadyExists(_))
+        ));
+    }
+}
+
+mod sign_message {
+    use casper_node::crypto::AsymmetricKeyExt;
+    use casper_types::{AsymmetricType, PublicKey, SecretKey};
+
+    use super::*;
+
+    const MESSAGE: &[u8] = b"Vote yes on proposal 7";
+
+    #[test]
+    fn should_verify_signed_message_with_hex_or_pem_public_key() {
+        let signature = casper_client::sign_message(MESSAGE, "resources/test.pem").unwrap();
+        let public_key = PublicKey::from(&SecretKey::from_file("resources/test.pem").unwrap());
+        assert!(signature.starts_with(&public_key.to_hex()[..2]));
+
+        casper_client::verify_message(MESSAGE, &signature, &public_key.to_hex()).unwrap();
+
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create a temp dir with error: {}", err));
+        let public_key_path = temp_dir.path().join("public_key.pem");
+        public_key.to_file(&public_key_path).unwrap();
+        casper_client::verify_message(MESSAGE, &signature, public_key_path.to_str().unwrap())
+            .unwrap();
+    }
+
+    #[test]
+    fn should_fail_to_verify_altered_message() {
+        let signature = casper_client::sign_message(MESSAGE, "resources/test.pem").unwrap();
+        let public_key = PublicKey::from(&SecretKey::from_file("resources/test.pem").unwrap());
+        assert!(matches!(
+            casper_client::verify_message(
+                b"Vote no on proposal 7",
+                &signature,
+                &public_key.to_hex()
+            ),
+            Err(Error::InvalidSignature(_))
+        ));
+        assert!(matches!(
+            casper_client::verify_message(MESSAGE, "01abc", &public_key.to_hex()),
+            Err(Error::InvalidArgument {
+                context: "signature",
+                ..
+            })
+        ));
+    }
+}
+
+mod make_genesis_accounts {
+    use super::*;
+
+    #[test]
+    fn should_write_accounts_for_generated_and_existing_keys() {
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create a temp dir with error: {}", err));
+        let existing_dir = temp_dir.path().join("existing");
+        let existing_dir = existing_dir.to_str().unwrap();
+        casper_client::keygen::generate_files(existing_dir, casper_client::keygen::ED25519, false)
+            .unwrap();
+        let keys_dir = temp_dir.path().join("validators");
+        let output_path = temp_dir.path().join("accounts.toml");
+        let output_path = output_path.to_str().unwrap();
+
+        let validator = format!("{},1000,100,10", existing_dir);
+        casper_client::make_genesis_accounts(
+            &[],
+            &[&validator],
+            &[],
+            "2,500,50",
+            keys_dir.to_str().unwrap(),
+            output_path,
+            false,
+        )
+        .unwrap();
+
+        let accounts = fs::read_to_string(output_path).unwrap();
+        assert_eq!(accounts.matches("[[accounts]]").count(), 3);
+        assert_eq!(accounts.matches("[accounts.validator]").count(), 3);
+        for dir in [
+            existing_dir.to_string(),
+            keys_dir.join("002").display().to_string(),
+        ] {
+            let public_key_hex = fs::read_to_string(
+                std::path::Path::new(&dir).join(casper_client::keygen::PUBLIC_KEY_HEX),
+            )
+            .unwrap();
+            assert!(accounts.contains(&format!("public_key = \"{}\"", public_key_hex)));
+        }
+
+        assert!(matches!(
+            casper_client::make_genesis_accounts(
+                &[],
+                &[&validator],
+                &[],
+                "",
+                "",
+                output_path,
+                false
+            ),
+            Err(Error::FileAlreadyExists(_))
+        ));
+    }
+
+    #[test]
+    fn should_fail_without_validators() {
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create a temp dir with error: {}", err));
+        let output_path = temp_dir.path().join("accounts.toml");
+        let account = "01522ef6c89038019cb7af05c340623804392dd2bb1f4dab5e4a9c3ab752fc0179,1000";
+        assert!(matches!(
+            casper_client::make_genesis_accounts(
+                &[account],
+                &[],
+                &[],
+                "",
+                "",
+                output_path.to_str().unwrap(),
+                false,
+            ),
+            Err(Error::InvalidArgument {
+                context: "genesis_accounts",
+                ..
+            })
+        ));
+        assert!(!output_path.exists());
+    }
+
+    #[test]
+    fn should_fail_for_delegator_to_undeclared_validator_before_generating_keys() {
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create a temp dir with error: {}", err));
+        let keys_dir = temp_dir.path().join("validators");
+        let output_path = temp_dir.path().join("accounts.toml");
+        let delegator = format!(
+            "{},{},1000,100",
+            "01522ef6c89038019cb7af05c340623804392dd2bb1f4dab5e4a9c3ab752fc0179",
+            "0119bf44096984cdfe8541bac167dc3b96c85086aa30b6b6cb0c5c38ad703166e1"
+        );
+        assert!(matches!(
+            casper_client::make_genesis_accounts(
+                &[],
+                &[],
+                &[&delegator],
+                "2,500,50",
+                keys_dir.to_str().unwrap(),
+                output_path.to_str().unwrap(),
+                false,
+            ),
+            Err(Error::InvalidArgument {
+                context: "genesis_accounts",
+                ..
+            })
+        ));
+        assert!(!keys_dir.exists());
+        assert!(!output_path.exists());
+    }
+}
+
+mod config {
+    use super::*;
+
+    #[test]
+    fn should_load_profile_from_config_file() {
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create a temp dir with error: {}", err));
+        let config_path = temp_dir.path().join("config.toml");
+        fs::write(
+            &config_path,
+            "default_profile = \"local\"\n\
+            [profiles.local]\n\
+            node_address = \"http://localhost:11101\"\n\
+            [profiles.testnet]\n\
+            chain_name = \"casper-test\"\n",
+        )
+        .unwrap();
+        let config_path = config_path.to_str().unwrap();
+
+        let config = casper_client::config::load(config_path, "").unwrap();
+        assert_eq!(config.profile.as_deref(), Some("local"));
+        assert_eq!(
+            config.settings.node_address.as_deref(),
+            Some("http://localhost:11101")
+        );
+        assert_eq!(config.settings.chain_name, None);
+
+        let config = casper_client::config::load(config_path, "testnet").unwrap();
+        assert_eq!(config.settings.chain_name.as_deref(), Some("casper-test"));
+        assert_eq!(config.settings.node_address, None);
+
+        assert!(casper_client::config::load(config_path, "mainnet").is_err());
+    }
+}
+
+mod output {
+    use casper_client::output;
+    use jsonrpc_lite::JsonRpc;
+
+    #[test]
+    fn should_select_and_format_response_fields() {
+        let response = JsonRpc::success(
+            1,
+            &serde_json::json!({
+                "api_version": "1.4.6",
+                "balance_value": "123456789",
+                "merkle_proof": "a".repeat(200),
+            }),
+        );
+        let value = serde_json::to_value(&response).unwrap();
+
+        let balance = output::query(&value, "result.balance_value").unwrap();
+        assert_eq!(output::format(&balance, output::JSON).unwrap(), "123456789");
+        assert_eq!(
+            output::format(&balance, output::JSON_COMPACT).unwrap(),
+            "\"123456789\""
+        );
+
+        let abbreviated = output::to_abbreviated_json(&response).unwrap();
+        let proof = output::query(&abbreviated, "result.merkle_proof").unwrap();
+        assert_eq!(proof, serde_json::json!("[200 hex chars]"));
+    }
+}
+
+mod error_category {
+    use casper_client::ErrorCategory;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_categorize_errors() {
+        let error = casper_client::get_state_root_hash("1", "http://127.0.0.1:1", 0, "")
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), "FAILED_TO_GET_RESPONSE");
+        assert_eq!(error.category(), ErrorCategory::Network);
+        assert_eq!(error.category().exit_code(), 4);
+        assert_eq!(error.rpc_error_code(), None);
+
+        let server_handle = MockServerHandle::spawn_without_params(GetStateRootHash::METHOD);
+        let error = server_handle
+            .get_state_root_hash("<not a real block id>")
+            .await
+            .unwrap_err();
+        assert_eq!(error.category(), ErrorCategory::InvalidArgument);
+        assert_eq!(error.category().exit_code(), 2);
+    }
+}
+
+mod cl_value {
+    use serde_json::json;
+
+    use super::*;
+
+    const MAP_TYPE: &str = "map<string,u512>";
+    const MAP_VALUE: &str = "{a: 1, b: 2}";
+
+    #[test]
+    fn should_round_trip_value_with_given_type() {
+        let hex = casper_client::encode_cl_value(MAP_TYPE, MAP_VALUE, false).unwrap();
+        assert_eq!(hex, "020000000100000061010101000000620102");
+
+        let decoded = casper_client::decode_cl_value(MAP_TYPE, &hex).unwrap();
+        assert_eq!(decoded["bytes"], json!(hex));
+        assert_eq!(
+            decoded["parsed"],
+            json!([{"key": "a", "value": "1"}, {"key": "b", "value": "2"}])
+        );
+    }
+
+    #[test]
+    fn should_detect_type_of_serialized_cl_value() {
+        let hex = casper_client::encode_cl_value("option<u64>", "null", true).unwrap();
+        let decoded = casper_client::decode_cl_value("", &hex).unwrap();
+        assert_eq!(decoded["cl_type"], json!({"Option": "U64"}));
+        assert_eq!(decoded["parsed"], json!(null));
+
+        let hex = casper_client::encode_cl_value(MAP_TYPE, MAP_VALUE, true).unwrap();
+        let decoded = casper_client::decode_cl_value("", &hex).unwrap();
+        assert_eq!(
+            decoded["cl_type"],
+            json!({"Map": {"key": "String", "value": "U512"}})
+        );
+    }
+
+    #[test]
+    fn should_fail_to_decode_bytes_not_of_type() {
+        assert!(matches!(
+            casper_client::decode_cl_value("u32", "0700"),
+            Err(Error::InvalidCLValue(_))
+        ));
+        assert!(matches!(
+            casper_client::decode_cl_value("list<u32>", "01000000070000000800"),
+            Err(Error::InvalidCLValue(_))
+        ));
+        assert!(matches!(
+            casper_client::decode_cl_value("", "0400000007000000"),
+            Err(Error::InvalidCLValue(_))
+        ));
+        assert!(matches!(
+            casper_client::decode_cl_value("u32", "zz"),
+            Err(Error::InvalidArgument { context: "hex", .. })
+        ));
+        assert!(matches!(
+            casper_client::encode_cl_value("u33", "1", false),
+            Err(Error::InvalidArgument {
+                context: "cl_type",
+                ..
+            })
+        ));
+    }
+}
+
+mod put_deploy {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_send_put_deploy() {
+        let server_handle = MockServerHandle::spawn::<PutDeployParams>(PutDeploy::METHOD);
         assert!(matches!(
             server_handle
                 .put_deploy(
@@ -1334,6 +2597,609 @@ mod put_deploy {
     }
 }
 
+mod put_deploys {
+    use super::*;
+
+    const JSON_MANIFEST: &str = r#"[
+        {
+            "id": "first",
+            "session_package_hash": "09dcee4b212cfd53642ab323fbef07dafafc6f945a80a00147f62910a915c4e6",
+            "session_version": "0.1.0",
+            "session_entry_point": "entrypoint",
+            "session_args_simple": ["name_01:bool='false'", "name_02:i32='42'"],
+            "payment_amount": "100"
+        },
+        {
+            "id": "second",
+            "session_name": "name",
+            "session_entry_point": "entrypoint",
+            "payment_amount": "not a number"
+        }
+    ]"#;
+
+    const CSV_MANIFEST: &str =
+        "id,session_name,session_entry_point,session_args_simple,payment_amount
+first,name,entrypoint,name_01:bool='false';name_02:i32='42',100
+";
+
+    fn read_results(path: &str) -> Vec<serde_json::Value> {
+        fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_record_results_for_json_manifest() {
+        let server_handle = MockServerHandle::spawn::<PutDeployParams>(PutDeploy::METHOD);
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
+        let manifest_path = temp_dir.path().join("manifest.json");
+        fs::write(&manifest_path, JSON_MANIFEST).unwrap();
+        let results_path = temp_dir.path().join("results.jsonl");
+        let results_path = results_path.to_str().unwrap();
+
+        let summary = server_handle
+            .put_deploys(manifest_path.to_str().unwrap(), results_path, false, false)
+            .await
+            .unwrap();
+        assert_eq!(
+            summary,
+            PutDeploysSummary {
+                succeeded: 1,
+                failed: 1,
+                skipped: 0,
+                unconfirmed: 0
+            }
+        );
+
+        let mut results = read_results(results_path);
+        let sending = results
+            .iter()
+            .filter(|result| result["sending"] == true)
+            .collect::<Vec<_>>();
+        assert_eq!(sending.len(), 1);
+        assert_eq!(sending[0]["id"], "first");
+        let sending_hash = sending[0]["deploy_hash"].clone();
+        results.retain(|result| result.get("sending").is_none());
+        results.sort_by_key(|result| result["id"].as_str().unwrap().to_string());
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0]["id"], "first");
+        let deploy_hash = results[0]["deploy_hash"].as_str().unwrap();
+        assert!(base16::decode(deploy_hash).is_ok());
+        assert_eq!(results[0]["deploy_hash"], sending_hash);
+        assert_eq!(results[1]["id"], "second");
+        assert!(results[1]["error"].is_string());
+        assert!(results[1].get("deploy_hash").is_none());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_succeed_for_csv_manifest() {
+        let server_handle = MockServerHandle::spawn::<PutDeployParams>(PutDeploy::METHOD);
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
+        let manifest_path = temp_dir.path().join("manifest.csv");
+        fs::write(&manifest_path, CSV_MANIFEST).unwrap();
+        let results_path = temp_dir.path().join("results.jsonl");
+        let results_path = results_path.to_str().unwrap();
+
+        let summary = server_handle
+            .put_deploys(manifest_path.to_str().unwrap(), results_path, false, false)
+            .await
+            .unwrap();
+        assert_eq!(summary.succeeded, 1);
+        assert_eq!(summary.failed, 0);
+        assert!(read_results(results_path)
+            .iter()
+            .all(|result| result["deploy_hash"].is_string()));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_skip_sent_entries_when_resuming() {
+        let server_handle = MockServerHandle::spawn::<PutDeployParams>(PutDeploy::METHOD);
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
+        let manifest_path = temp_dir.path().join("manifest.json");
+        fs::write(&manifest_path, JSON_MANIFEST).unwrap();
+        let manifest_path = manifest_path.to_str().unwrap();
+        let results_path = temp_dir.path().join("results.jsonl");
+        let results_path = results_path.to_str().unwrap();
+        fs::write(
+            results_path,
+            "{\"id\":\"first\",\"deploy_hash\":\"00\"}\n{\"id\":\"second\",\"error\":\"failed\"}\n",
+        )
+        .unwrap();
+
+        let summary = server_handle
+            .put_deploys(manifest_path, results_path, true, false)
+            .await
+            .unwrap();
+        assert_eq!(
+            summary,
+            PutDeploysSummary {
+                succeeded: 0,
+                failed: 1,
+                skipped: 1,
+                unconfirmed: 0
+            }
+        );
+        // The new result is appended to the existing ones.
+        assert_eq!(read_results(results_path).len(), 3);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_not_resend_unconfirmed_entries_when_resuming() {
+        let server_handle = MockServerHandle::spawn::<PutDeployParams>(PutDeploy::METHOD);
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
+        let manifest_path = temp_dir.path().join("manifest.json");
+        fs::write(&manifest_path, JSON_MANIFEST).unwrap();
+        let manifest_path = manifest_path.to_str().unwrap();
+        let results_path = temp_dir.path().join("results.jsonl");
+        let results_path = results_path.to_str().unwrap();
+        // A previous run was interrupted after recording "first" as being sent.
+        fs::write(
+            results_path,
+            "{\"id\":\"first\",\"deploy_hash\":\"00\",\"sending\":true}\n",
+        )
+        .unwrap();
+
+        let summary = server_handle
+            .put_deploys(manifest_path, results_path, true, false)
+            .await
+            .unwrap();
+        assert_eq!(
+            summary,
+            PutDeploysSummary {
+                succeeded: 0,
+                failed: 1,
+                skipped: 0,
+                unconfirmed: 1
+            }
+        );
+        let results = read_results(results_path);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[1]["id"], "second");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_not_resend_entries_whose_response_was_dropped() {
+        let server = DroppingServer::spawn().await;
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
+        let manifest_path = temp_dir.path().join("manifest.json");
+        fs::write(&manifest_path, JSON_MANIFEST).unwrap();
+        let manifest_path = manifest_path.to_str().unwrap();
+        let results_path = temp_dir.path().join("results.jsonl");
+        let results_path = results_path.to_str().unwrap();
+        let url = server.url();
+        let send = |resume| {
+            casper_client::put_deploys(
+                &url,
+                0,
+                manifest_path,
+                results_path,
+                1,
+                resume,
+                false,
+                deploy_params::test_data_valid(),
+            )
+        };
+
+        let expected_summary = PutDeploysSummary {
+            succeeded: 0,
+            failed: 1,
+            skipped: 0,
+            unconfirmed: 1,
+        };
+        assert_eq!(send(false).await.unwrap(), expected_summary);
+        assert_eq!(server.requests(), 1);
+        let first = read_results(results_path)
+            .into_iter()
+            .rfind(|result| result["id"] == "first")
+            .unwrap();
+        assert!(first["deploy_hash"].is_string());
+        assert!(first["error"].is_string());
+        assert!(first.get("rejected").is_none());
+
+        assert_eq!(send(true).await.unwrap(), expected_summary);
+        assert_eq!(server.requests(), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_fail_before_writing_results_for_invalid_signer() {
+        let server_handle = MockServerHandle::spawn::<PutDeployParams>(PutDeploy::METHOD);
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
+        let manifest_path = temp_dir.path().join("manifest.json");
+        fs::write(&manifest_path, JSON_MANIFEST).unwrap();
+        let results_path = temp_dir.path().join("results.jsonl");
+
+        let result = casper_client::put_deploys(
+            &server_handle.url(),
+            0,
+            manifest_path.to_str().unwrap(),
+            results_path.to_str().unwrap(),
+            2,
+            false,
+            false,
+            DeployStrParams {
+                secret_key: "missing.pem",
+                ..deploy_params::test_data_valid()
+            },
+        )
+        .await;
+        assert!(matches!(result, Err(Error::CryptoError { .. })));
+        assert!(!results_path.exists());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_fail_if_results_file_exists() {
+        let server_handle = MockServerHandle::spawn::<PutDeployParams>(PutDeploy::METHOD);
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
+        let manifest_path = temp_dir.path().join("manifest.json");
+        fs::write(&manifest_path, JSON_MANIFEST).unwrap();
+        let manifest_path = manifest_path.to_str().unwrap();
+        let results_path = temp_dir.path().join("results.jsonl");
+        let results_path = results_path.to_str().unwrap();
+        fs::write(results_path, "").unwrap();
+
+        assert!(matches!(
+            server_handle
+                .put_deploys(manifest_path, results_path, false, false)
+                .await,
+            Err(Error::FileAlreadyExists(_))
+        ));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_fail_for_unknown_manifest_field() {
+        let server_handle = MockServerHandle::spawn::<PutDeployParams>(PutDeploy::METHOD);
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
+        let manifest_path = temp_dir.path().join("manifest.json");
+        fs::write(&manifest_path, r#"[{"session_nmae": "name"}]"#).unwrap();
+        let results_path = temp_dir.path().join("results.jsonl");
+
+        assert!(matches!(
+            server_handle
+                .put_deploys(
+                    manifest_path.to_str().unwrap(),
+                    results_path.to_str().unwrap(),
+                    false,
+                    false
+                )
+                .await,
+            Err(Error::FailedToParseManifest { .. })
+        ));
+    }
+}
+
+mod batch_transfer {
+    use super::*;
+
+    const TRANSFERS: &str = "target,amount,transfer_id
+01522ef6c89038019cb7af05c340623804392dd2bb1f4dab5e4a9c3ab752fc0179,2500000000,1
+not a target,2500000000,2
+";
+
+    fn write_transfers(temp_dir: &TempDir) -> String {
+        let path = temp_dir.path().join("transfers.csv");
+        fs::write(&path, TRANSFERS).unwrap();
+        path.to_str().unwrap().to_string()
+    }
+
+    fn report_statuses(report_path: &str) -> Vec<String> {
+        fs::read_to_string(report_path)
+            .unwrap()
+            .lines()
+            .skip(1)
+            .map(|line| line.split(',').nth(4).unwrap().to_string())
+            .collect()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_send_transfers_without_tracking() {
+        let server_handle = MockServerHandle::spawn::<PutDeployParams>(PutDeploy::METHOD);
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
+        let transfers_path = write_transfers(&temp_dir);
+        let report_path = temp_dir.path().join("report.csv");
+        let report_path = report_path.to_str().unwrap();
+
+        let summary = server_handle
+            .batch_transfer(&transfers_path, report_path, "", "", false)
+            .await
+            .unwrap();
+        assert_eq!(
+            summary,
+            BatchTransferSummary {
+                written: 0,
+                succeeded: 0,
+                failed: 1,
+                pending: 1,
+                skipped: 0,
+                unconfirmed: 0,
+            }
+        );
+        assert_eq!(
+            report_statuses(report_path),
+            vec!["sending", "pending", "invalid"]
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_report_unexecuted_transfers_as_pending_after_timeout() {
+        // The mock server doesn't handle `info_get_deploy`, so the transfer is never seen executed.
+        let server_handle = MockServerHandle::spawn::<PutDeployParams>(PutDeploy::METHOD);
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
+        let transfers_path = write_transfers(&temp_dir);
+        let report_path = temp_dir.path().join("report.csv");
+        let report_path = report_path.to_str().unwrap();
+
+        let summary = server_handle
+            .batch_transfer(&transfers_path, report_path, "1s", "", false)
+            .await
+            .unwrap();
+        assert_eq!(summary.pending, 1);
+        assert_eq!(
+            report_statuses(report_path),
+            vec!["sending", "pending", "invalid"]
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_write_transfers_in_dry_run() {
+        let server_handle = MockServerHandle::spawn::<PutDeployParams>(PutDeploy::METHOD);
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
+        let transfers_path = write_transfers(&temp_dir);
+        let report_path = temp_dir.path().join("report.csv");
+        let report_path = report_path.to_str().unwrap();
+        let dry_run_dir = temp_dir.path().join("deploys");
+        let dry_run_dir = dry_run_dir.to_str().unwrap();
+
+        let summary = server_handle
+            .batch_transfer(&transfers_path, report_path, "", dry_run_dir, false)
+            .await
+            .unwrap();
+        assert_eq!(summary.written, 1);
+        assert_eq!(summary.failed, 1);
+        assert_eq!(report_statuses(report_path), vec!["written", "invalid"]);
+
+        let deploy_files: Vec<_> = fs::read_dir(dry_run_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        assert_eq!(deploy_files.len(), 1);
+        assert!(server_handle
+            .send_deploy_file(deploy_files[0].to_str().unwrap())
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_write_transfers_in_dry_run_format() {
+        let server_handle = MockServerHandle::spawn::<PutDeployParams>(PutDeploy::METHOD);
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
+        let transfers_path = write_transfers(&temp_dir);
+        let report_path = temp_dir.path().join("report.csv");
+        let dry_run_dir = temp_dir.path().join("deploys");
+        let dry_run_dir = dry_run_dir.to_str().unwrap();
+
+        let summary = casper_client::batch_transfer(
+            &server_handle.url(),
+            0,
+            &transfers_path,
+            report_path.to_str().unwrap(),
+            1,
+            "",
+            dry_run_dir,
+            "base64",
+            false,
+            false,
+            deploy_params::test_data_valid(),
+            payment_params::test_data_with_name(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(summary.written, 1);
+
+        let deploy_files: Vec<_> = fs::read_dir(dry_run_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        assert_eq!(deploy_files.len(), 1);
+        assert_eq!(deploy_files[0].extension().unwrap(), "b64");
+        assert!(server_handle
+            .send_deploy_file(deploy_files[0].to_str().unwrap())
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_fail_if_report_exists() {
+        let server_handle = MockServerHandle::spawn::<PutDeployParams>(PutDeploy::METHOD);
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
+        let transfers_path = write_transfers(&temp_dir);
+        let report_path = temp_dir.path().join("report.csv");
+        fs::write(&report_path, "").unwrap();
+
+        assert!(matches!(
+            server_handle
+                .batch_transfer(
+                    &transfers_path,
+                    report_path.to_str().unwrap(),
+                    "",
+                    "",
+                    false
+                )
+                .await,
+            Err(Error::FileAlreadyExists(_))
+        ));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_only_retry_failed_transfers_when_resuming() {
+        let server_handle = MockServerHandle::spawn::<PutDeployParams>(PutDeploy::METHOD);
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
+        let transfers_path = write_transfers(&temp_dir);
+        let report_path = temp_dir.path().join("report.csv");
+        let report_path = report_path.to_str().unwrap();
+
+        server_handle
+            .batch_transfer(&transfers_path, report_path, "", "", false)
+            .await
+            .unwrap();
+        let summary = server_handle
+            .batch_transfer(&transfers_path, report_path, "", "", true)
+            .await
+            .unwrap();
+        assert_eq!(summary.skipped, 1);
+        assert_eq!(summary.failed, 1);
+        assert_eq!(summary.pending, 0);
+        assert_eq!(
+            report_statuses(report_path),
+            vec!["sending", "pending", "invalid", "invalid"]
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_not_resend_unconfirmed_transfers_when_resuming() {
+        let server_handle = MockServerHandle::spawn::<PutDeployParams>(PutDeploy::METHOD);
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
+        let transfers_path = write_transfers(&temp_dir);
+        let report_path = temp_dir.path().join("report.csv");
+        let report_path = report_path.to_str().unwrap();
+
+        server_handle
+            .batch_transfer(&transfers_path, report_path, "", "", false)
+            .await
+            .unwrap();
+        // Simulate a run interrupted while sending the first transfer by dropping the rows after
+        // its `sending` row.
+        let report = fs::read_to_string(report_path).unwrap();
+        let interrupted: Vec<_> = report.lines().take(2).collect();
+        fs::write(report_path, interrupted.join("\n") + "\n").unwrap();
+
+        let summary = server_handle
+            .batch_transfer(&transfers_path, report_path, "", "", true)
+            .await
+            .unwrap();
+        assert_eq!(summary.unconfirmed, 1);
+        assert_eq!(summary.pending, 0);
+        assert_eq!(summary.failed, 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_not_resend_transfers_whose_response_was_dropped() {
+        let server = DroppingServer::spawn().await;
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
+        let transfers_path = write_transfers(&temp_dir);
+        let report_path = temp_dir.path().join("report.csv");
+        let report_path = report_path.to_str().unwrap();
+        let url = server.url();
+        let send = |resume| {
+            casper_client::batch_transfer(
+                &url,
+                0,
+                &transfers_path,
+                report_path,
+                1,
+                "",
+                "",
+                "",
+                resume,
+                false,
+                deploy_params::test_data_valid(),
+                payment_params::test_data_with_name(),
+            )
+        };
+
+        let summary = send(false).await.unwrap();
+        assert_eq!(summary.unconfirmed, 1);
+        assert_eq!(summary.failed, 1);
+        assert_eq!(server.requests(), 1);
+        assert_eq!(
+            report_statuses(report_path),
+            vec!["sending", "unconfirmed", "invalid"]
+        );
+
+        let summary = send(true).await.unwrap();
+        assert_eq!(summary.unconfirmed, 1);
+        assert_eq!(summary.pending, 0);
+        assert_eq!(server.requests(), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_fail_before_writing_report_for_invalid_signer() {
+        let server_handle = MockServerHandle::spawn::<PutDeployParams>(PutDeploy::METHOD);
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
+        let transfers_path = write_transfers(&temp_dir);
+        let report_path = temp_dir.path().join("report.csv");
+
+        let result = casper_client::batch_transfer(
+            &server_handle.url(),
+            0,
+            &transfers_path,
+            report_path.to_str().unwrap(),
+            1,
+            "",
+            "",
+            "",
+            false,
+            false,
+            DeployStrParams {
+                secret_key: "missing.pem",
+                ..deploy_params::test_data_valid()
+            },
+            payment_params::test_data_with_name(),
+        )
+        .await;
+        assert!(matches!(result, Err(Error::CryptoError { .. })));
+        assert!(!report_path.exists());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_fail_to_resume_from_report_for_other_transfers() {
+        let server_handle = MockServerHandle::spawn::<PutDeployParams>(PutDeploy::METHOD);
+        let temp_dir = TempDir::new()
+            .unwrap_or_else(|err| panic!("Failed to create temp dir with error: {}", err));
+        let transfers_path = write_transfers(&temp_dir);
+        let report_path = temp_dir.path().join("report.csv");
+        let report_path = report_path.to_str().unwrap();
+
+        server_handle
+            .batch_transfer(&transfers_path, report_path, "", "", false)
+            .await
+            .unwrap();
+        fs::write(
+            &transfers_path,
+            TRANSFERS.replace("2500000000,1", "2500000000,7"),
+        )
+        .unwrap();
+
+        assert!(matches!(
+            server_handle
+                .batch_transfer(&transfers_path, report_path, "", "", true)
+                .await,
+            Err(Error::InvalidArgument {
+                context: "resume",
+                ..
+            })
+        ));
+    }
+}
+
 mod rate_limit {
     use super::*;
     use casper_node::types::Timestamp;